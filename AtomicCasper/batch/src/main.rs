@@ -0,0 +1,65 @@
+#![no_std]
+#![no_main]
+
+#[cfg(not(target_arch = "wasm32"))]
+compile_error!("target arch should be wasm32: compile with '--target wasm32-unknown-unknown'");
+
+// We need to explicitly import the std alloc crate and `alloc::string::String` as we're in a
+// `no_std` environment.
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
+use casper_contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
+use casper_types::{runtime_args, ApiError, RuntimeArgs};
+use swap_core::{
+    batch::{self, Initiation, Redemption},
+    constants,
+};
+
+/// Initiates every swap of `initiations`, each on its own contract.
+fn initiate_batch() {
+    let initiations: Vec<Initiation> = runtime::get_named_arg(constants::ARG_INITIATIONS);
+    batch::check_size(initiations.len()).unwrap_or_revert();
+    for initiation in initiations {
+        runtime::call_contract::<()>(
+            initiation.contract_hash,
+            constants::ENTRY_POINT_INITIATE,
+            initiation.args,
+        );
+    }
+}
+
+/// Unlocks every swap of `redemptions` with its secret, and the signed payout if it has one.
+fn unlock_batch() {
+    let redemptions: Vec<Redemption> = runtime::get_named_arg(constants::ARG_REDEMPTIONS);
+    batch::check_size(redemptions.len()).unwrap_or_revert();
+    for redemption in redemptions {
+        let mut args = runtime_args! {
+            constants::ARG_SECRET => redemption.secret,
+            constants::ARG_SWAP_ID => redemption.swap_id,
+        };
+        // swaps of a receiver public key pay out to the key it signed
+        if let Some((payout, signature)) = redemption.signed {
+            args.insert(constants::ARG_PAYOUT, payout)
+                .unwrap_or_revert();
+            args.insert(constants::ARG_SIGNATURE, signature)
+                .unwrap_or_revert();
+        }
+        runtime::call_contract::<()>(
+            redemption.contract_hash,
+            constants::ENTRY_POINT_UNLOCK,
+            args,
+        );
+    }
+}
+
+/// Opens or redeems several swaps in one deploy, as the signer, all or nothing.
+#[no_mangle]
+pub extern "C" fn call() {
+    let batch: String = runtime::get_named_arg(constants::ARG_BATCH);
+    match batch.as_str() {
+        constants::BATCH_INITIATE => initiate_batch(),
+        constants::BATCH_UNLOCK => unlock_batch(),
+        _ => runtime::revert(ApiError::InvalidArgument),
+    }
+}
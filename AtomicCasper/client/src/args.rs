@@ -0,0 +1,396 @@
+//! Typed runtime args for the entry points of the contract.
+use casper_types::{
+    crypto, runtime_args, ContractHash, ContractPackageHash, Key, PublicKey, RuntimeArgs,
+    SecretKey, Signature, U256, U512,
+};
+use swap_core::{
+    batch::{Initiation, Redemption},
+    constants, swap,
+    token::TokenContract,
+    TokenId,
+};
+
+use crate::{hashlock, salted_hashlock, Error, SecretRecord, SwapTerms};
+
+/// What a swap locks, with the `initiate` arguments each contract `type` needs.
+#[derive(Clone, Debug)]
+pub enum Asset {
+    Nft {
+        token_contract: TokenContract,
+        token_ids: Vec<TokenId>,
+    },
+    Erc20 {
+        token_contract: TokenContract,
+        amount: U256,
+    },
+    /// CSPR, in motes, that the owner must have deposited into the contract first.
+    Direct {
+        amount: U512,
+    },
+    Custom {
+        token_contract: TokenContract,
+    },
+}
+
+impl Asset {
+    /// The `type` the contract has to be installed with to lock this asset.
+    pub fn swap_type(&self) -> &'static str {
+        match self {
+            Asset::Nft { .. } => "NFT",
+            Asset::Erc20 { .. } => "ERC-20",
+            Asset::Direct { .. } => "Direct",
+            Asset::Custom { .. } => "Custom",
+        }
+    }
+
+    fn insert_into(&self, args: &mut RuntimeArgs) -> Result<(), Error> {
+        match self {
+            Asset::Nft {
+                token_contract,
+                token_ids,
+            } => {
+                insert_token_contract(args, *token_contract)?;
+                insert(args, constants::ARG_TOKEN_IDS, token_ids.clone())
+            }
+            Asset::Erc20 {
+                token_contract,
+                amount,
+            } => {
+                insert_token_contract(args, *token_contract)?;
+                insert(args, constants::ARG_AMOUNT, *amount)
+            }
+            Asset::Direct { amount } => insert(args, constants::ARG_AMOUNT, *amount),
+            Asset::Custom { token_contract } => insert_token_contract(args, *token_contract),
+        }
+    }
+
+    /// The token contract the swap calls, none for Direct swaps.
+    pub fn token_contract(&self) -> Option<TokenContract> {
+        match self {
+            Asset::Nft { token_contract, .. }
+            | Asset::Erc20 { token_contract, .. }
+            | Asset::Custom { token_contract } => Some(*token_contract),
+            Asset::Direct { .. } => None,
+        }
+    }
+}
+
+/// Args of the installer session, `contract.wasm`.
+pub fn install(swap_type: &str) -> RuntimeArgs {
+    runtime_args! {
+        constants::ARG_TYPE => swap_type.to_string(),
+    }
+}
+
+/// Args of the factory's `create_swap`.
+pub fn create_swap(swap_type: &str) -> RuntimeArgs {
+    runtime_args! {
+        constants::ARG_TYPE => swap_type.to_string(),
+    }
+}
+
+/// Args of `initiate`.
+#[derive(Clone, Debug)]
+pub struct Initiate {
+    asset: Asset,
+    receiver: Key,
+    timeout: u64,
+    hash_type: String,
+    hash: Option<String>,
+    /// Hashlock of the bare secret of a salted swap.
+    secret_hash: Option<String>,
+    receiver_public_key: Option<PublicKey>,
+    terms: Option<SwapTerms>,
+}
+
+impl Initiate {
+    /// Locks `asset` for `receiver` during `timeout` milliseconds.
+    pub fn new(asset: Asset, receiver: Key, timeout: u64) -> Self {
+        Initiate {
+            asset,
+            receiver,
+            timeout,
+            hash_type: String::new(),
+            hash: None,
+            secret_hash: None,
+            receiver_public_key: None,
+            terms: None,
+        }
+    }
+
+    /// Uses an already computed hashlock.
+    pub fn with_hashlock(mut self, hash_type: &str, hash: &str) -> Self {
+        self.hash_type = hash_type.to_string();
+        self.hash = Some(hash.to_string());
+        self
+    }
+
+    /// Computes the hashlock of `secret`.
+    pub fn with_secret(self, hash_type: &str, secret: &str) -> Result<Self, Error> {
+        let hash = hashlock(hash_type, secret)?;
+        Ok(self.with_hashlock(hash_type, hash.as_str()))
+    }
+
+    /// Computes the hashlock of `secret` salted with the contract package and the id of the swap,
+    /// which only opens this swap. Both come from [`NodeClient::salt`](crate::rpc::NodeClient::salt).
+    pub fn with_salted_secret(
+        self,
+        hash_type: &str,
+        secret: &str,
+        contract_package_hash: ContractPackageHash,
+        swap_id: u64,
+    ) -> Result<Self, Error> {
+        let hash = salted_hashlock(hash_type, secret, contract_package_hash, swap_id)?;
+        let secret_hash = hashlock(hash_type, secret)?;
+        Ok(self
+            .with_hashlock(hash_type, hash.as_str())
+            .salted(secret_hash.as_str()))
+    }
+
+    /// Marks the hashlock as the one of a salted secret, see [`salted_hashlock`], whose bare
+    /// secret has the hashlock `secret_hash`. The contract claims both, so the secret locks no
+    /// other swap of it, and `unlock` checks the secret against both.
+    pub fn salted(mut self, secret_hash: &str) -> Self {
+        self.secret_hash = Some(secret_hash.to_string());
+        self
+    }
+
+    /// Uses the hashlock of a secret kept in the [`Vault`](crate::Vault).
+    pub fn with_record(self, record: &SecretRecord) -> Self {
+        self.with_hashlock(record.hash_type.as_str(), record.hashlock.as_str())
+    }
+
+    /// Uses the hashlock of a secret kept in the [`Vault`](crate::Vault), salted like
+    /// [`with_salted_secret`](Self::with_salted_secret). The vault finds the record back with
+    /// [`Vault::find_salted`](crate::Vault::find_salted).
+    pub fn with_salted_record(
+        self,
+        record: &SecretRecord,
+        contract_package_hash: ContractPackageHash,
+        swap_id: u64,
+    ) -> Result<Self, Error> {
+        self.with_salted_secret(
+            &record.hash_type,
+            &record.secret,
+            contract_package_hash,
+            swap_id,
+        )
+    }
+
+    /// Requires the receiver's signature to unlock, see [`Unlock::signed`].
+    pub fn with_receiver_public_key(mut self, public_key: PublicKey) -> Self {
+        self.receiver_public_key = Some(public_key);
+        self
+    }
+
+    /// Binds the swap to signed terms: stores their hash with the swap, and uses their hashlock
+    /// unless one is already set, in which case it must be the same.
+    pub fn with_terms(mut self, terms: &SwapTerms) -> Self {
+        if self.hash.is_none() {
+            self = self.with_hashlock(&terms.hash_type, &terms.hashlock);
+        }
+        self.terms = Some(terms.clone());
+        self
+    }
+
+    pub fn asset(&self) -> &Asset {
+        &self.asset
+    }
+
+    pub fn build(&self) -> Result<RuntimeArgs, Error> {
+        let hash = self.hash.clone().ok_or(Error::MissingHashlock)?;
+        let mut args = runtime_args! {
+            constants::ARG_HASH => hash,
+            constants::ARG_HASH_TYPE => self.hash_type.clone(),
+            constants::ARG_RECEIVER => self.receiver,
+            constants::ARG_TIMEOUT => self.timeout,
+        };
+        self.asset.insert_into(&mut args)?;
+        if let Some(secret_hash) = &self.secret_hash {
+            insert(&mut args, constants::ARG_SALTED, true)?;
+            insert(&mut args, constants::ARG_SECRET_HASH, secret_hash.clone())?;
+        }
+        if let Some(public_key) = &self.receiver_public_key {
+            insert(
+                &mut args,
+                constants::ARG_RECEIVER_PUBLIC_KEY,
+                public_key.clone(),
+            )?;
+        }
+        if let Some(terms) = &self.terms {
+            if terms.hash_type != self.hash_type || Some(&terms.hashlock) != self.hash.as_ref() {
+                return Err(Error::Terms(
+                    "the hashlock is not the one of the terms".to_string(),
+                ));
+            }
+            insert(&mut args, constants::ARG_TERMS_HASH, terms.hash()?)?;
+        }
+        Ok(args)
+    }
+}
+
+/// Args of `unlock`.
+#[derive(Clone, Debug)]
+pub struct Unlock {
+    secret: String,
+    signed: Option<(Key, Signature)>,
+}
+
+impl Unlock {
+    pub fn new(secret: &str) -> Self {
+        Unlock {
+            secret: secret.to_string(),
+            signed: None,
+        }
+    }
+
+    /// For swaps initiated with a receiver public key: pays out to `payout`, which the receiver
+    /// signed with [`sign_redemption`].
+    pub fn signed(mut self, payout: Key, signature: Signature) -> Self {
+        self.signed = Some((payout, signature));
+        self
+    }
+
+    pub fn build(&self) -> Result<RuntimeArgs, Error> {
+        let mut args = runtime_args! {
+            constants::ARG_SECRET => self.secret.clone(),
+        };
+        if let Some((payout, signature)) = &self.signed {
+            insert(&mut args, constants::ARG_PAYOUT, *payout)?;
+            insert(&mut args, constants::ARG_SIGNATURE, *signature)?;
+        }
+        Ok(args)
+    }
+}
+
+/// The receiver's signature allowing `payout` to unlock swap `swap_id` of the contract package.
+pub fn sign_redemption(
+    secret_key: &SecretKey,
+    contract_package_hash: ContractPackageHash,
+    swap_id: u64,
+    secret: &str,
+    payout: Key,
+) -> Signature {
+    let public_key = PublicKey::from(secret_key);
+    let message = swap::redemption_message(contract_package_hash, swap_id, secret, payout);
+    crypto::sign(message, secret_key, &public_key)
+}
+
+/// Args of the deposit session, `session.wasm`, which moves `amount` motes into the contract
+/// through a purse of its own.
+pub fn deposit(contract_hash: ContractHash, amount: U512) -> RuntimeArgs {
+    runtime_args! {
+        constants::ARG_CONTRACT_HASH => Key::from(contract_hash),
+        constants::ARG_AMOUNT => amount,
+    }
+}
+
+/// Args of `withdraw`.
+pub fn withdraw(amount: U512) -> RuntimeArgs {
+    runtime_args! {
+        constants::ARG_AMOUNT => amount,
+    }
+}
+
+/// Args of `set_fee`, `fee_bps` is in basis points.
+pub fn set_fee(asset_type: &str, fee_bps: u32) -> RuntimeArgs {
+    runtime_args! {
+        constants::ARG_ASSET_TYPE => asset_type.to_string(),
+        constants::ARG_FEE_BPS => fee_bps,
+    }
+}
+
+/// Args of `set_treasury`.
+pub fn set_treasury(treasury: Key) -> RuntimeArgs {
+    runtime_args! {
+        constants::ARG_TREASURY => treasury,
+    }
+}
+
+/// Args of the batch session opening one swap on each contract of `swaps`.
+pub fn initiate_batch(swaps: &[(ContractHash, Initiate)]) -> Result<RuntimeArgs, Error> {
+    let initiations = swaps
+        .iter()
+        .map(|(contract_hash, initiate)| {
+            Ok(Initiation {
+                contract_hash: *contract_hash,
+                args: initiate.build()?,
+            })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    Ok(runtime_args! {
+        constants::ARG_BATCH => constants::BATCH_INITIATE.to_string(),
+        constants::ARG_INITIATIONS => initiations,
+    })
+}
+
+/// Args of the batch session unlocking each of `redemptions`.
+pub fn unlock_batch(redemptions: Vec<Redemption>) -> RuntimeArgs {
+    runtime_args! {
+        constants::ARG_BATCH => constants::BATCH_UNLOCK.to_string(),
+        constants::ARG_REDEMPTIONS => redemptions,
+    }
+}
+
+/// Args of `allow_token`, `standard` is `CEP-18`, `CEP-47` or `CEP-78`.
+pub fn allow_token(token_contract: TokenContract, standard: &str) -> RuntimeArgs {
+    match token_contract {
+        TokenContract::Contract(contract_hash) => runtime_args! {
+            constants::ARG_CONTRACT_HASH => contract_hash,
+            constants::ARG_STANDARD => standard.to_string(),
+        },
+        TokenContract::Package(package_hash) => runtime_args! {
+            constants::ARG_CONTRACT_PACKAGE_HASH => package_hash,
+            constants::ARG_STANDARD => standard.to_string(),
+        },
+    }
+}
+
+/// Args of `remove_token`.
+pub fn remove_token(token_contract: TokenContract) -> RuntimeArgs {
+    match token_contract {
+        TokenContract::Contract(contract_hash) => runtime_args! {
+            constants::ARG_CONTRACT_HASH => contract_hash,
+        },
+        TokenContract::Package(package_hash) => runtime_args! {
+            constants::ARG_CONTRACT_PACKAGE_HASH => package_hash,
+        },
+    }
+}
+
+/// Args of `enforce_allowlist`.
+pub fn enforce_allowlist(enforced: bool) -> RuntimeArgs {
+    runtime_args! {
+        constants::ARG_ENFORCED => enforced,
+    }
+}
+
+/// Args of `propose_end_time` and `accept_end_time`.
+pub fn end_time(end_time: u64) -> RuntimeArgs {
+    runtime_args! {
+        constants::ARG_END_TIME => end_time,
+    }
+}
+
+/// Passes a contract as `contract_hash`, a package as `contract_package_hash`.
+fn insert_token_contract(
+    args: &mut RuntimeArgs,
+    token_contract: TokenContract,
+) -> Result<(), Error> {
+    match token_contract {
+        TokenContract::Contract(contract_hash) => {
+            insert(args, constants::ARG_CONTRACT_HASH, contract_hash)
+        }
+        TokenContract::Package(package_hash) => {
+            insert(args, constants::ARG_CONTRACT_PACKAGE_HASH, package_hash)
+        }
+    }
+}
+
+fn insert<T>(args: &mut RuntimeArgs, name: &str, value: T) -> Result<(), Error>
+where
+    T: casper_types::CLTyped + casper_types::bytesrepr::ToBytes,
+{
+    args.insert(name, value)
+        .map_err(|error| Error::Args(error.to_string()))
+}
@@ -0,0 +1,367 @@
+//! Signed deploys for installing and calling the contract.
+use std::path::Path;
+
+use casper_execution_engine::core::engine_state::ExecutableDeployItem;
+pub use casper_node::types::Deploy;
+use casper_node::types::{TimeDiff, Timestamp};
+use casper_types::{
+    bytesrepr::Bytes, runtime_args, ContractHash, Key, RuntimeArgs, SecretKey, U512,
+};
+use swap_core::{batch::Redemption, constants, token::TokenContract};
+
+use crate::{
+    args::{self, Initiate, Unlock},
+    Error,
+};
+
+/// Loads a secret key from a PEM file, like the ones in `keys/`.
+pub fn load_secret_key<P: AsRef<Path>>(path: P) -> Result<SecretKey, Error> {
+    SecretKey::from_file(path).map_err(|error| Error::Key(error.to_string()))
+}
+
+/// Common parameters of the deploys sent to one network.
+#[derive(Clone, Debug)]
+pub struct DeployBuilder {
+    chain_name: String,
+    payment_amount: U512,
+    ttl: TimeDiff,
+    gas_price: u64,
+    timestamp: Option<Timestamp>,
+}
+
+impl DeployBuilder {
+    /// `chain_name` is `casper` or `casper-test`, `payment_amount` is in motes.
+    pub fn new(chain_name: &str, payment_amount: U512) -> Self {
+        DeployBuilder {
+            chain_name: chain_name.to_string(),
+            payment_amount,
+            ttl: TimeDiff::from(30 * 60 * 1000),
+            gas_price: 1,
+            timestamp: None,
+        }
+    }
+
+    pub fn with_ttl(mut self, ttl: TimeDiff) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    pub fn with_gas_price(mut self, gas_price: u64) -> Self {
+        self.gas_price = gas_price;
+        self
+    }
+
+    /// Defaults to the time the deploy is built.
+    pub fn with_timestamp(mut self, timestamp: Timestamp) -> Self {
+        self.timestamp = Some(timestamp);
+        self
+    }
+
+    pub fn with_payment_amount(mut self, payment_amount: U512) -> Self {
+        self.payment_amount = payment_amount;
+        self
+    }
+
+    /// Installs `contract.wasm` for swaps of the given `type`.
+    pub fn install(
+        &self,
+        contract_wasm: Vec<u8>,
+        swap_type: &str,
+        secret_key: &SecretKey,
+    ) -> Deploy {
+        let session = ExecutableDeployItem::ModuleBytes {
+            module_bytes: Bytes::from(contract_wasm),
+            args: args::install(swap_type),
+        };
+        self.sign(session, secret_key)
+    }
+
+    /// Installs a swap contract of `swap_type` through the factory, owned by the signer.
+    pub fn create_swap(
+        &self,
+        factory_hash: ContractHash,
+        swap_type: &str,
+        secret_key: &SecretKey,
+    ) -> Deploy {
+        self.call(
+            factory_hash,
+            constants::ENTRY_POINT_CREATE_SWAP,
+            args::create_swap(swap_type),
+            secret_key,
+        )
+    }
+
+    pub fn initiate(
+        &self,
+        contract_hash: ContractHash,
+        initiate: &Initiate,
+        secret_key: &SecretKey,
+    ) -> Result<Deploy, Error> {
+        let args = initiate.build()?;
+        Ok(self.call(
+            contract_hash,
+            constants::ENTRY_POINT_INITIATE,
+            args,
+            secret_key,
+        ))
+    }
+
+    pub fn unlock(
+        &self,
+        contract_hash: ContractHash,
+        unlock: &Unlock,
+        secret_key: &SecretKey,
+    ) -> Result<Deploy, Error> {
+        let args = unlock.build()?;
+        Ok(self.call(
+            contract_hash,
+            constants::ENTRY_POINT_UNLOCK,
+            args,
+            secret_key,
+        ))
+    }
+
+    pub fn refund(&self, contract_hash: ContractHash, secret_key: &SecretKey) -> Deploy {
+        self.call(
+            contract_hash,
+            constants::ENTRY_POINT_REFUND,
+            RuntimeArgs::new(),
+            secret_key,
+        )
+    }
+
+    pub fn cancel(&self, contract_hash: ContractHash, secret_key: &SecretKey) -> Deploy {
+        self.call(
+            contract_hash,
+            constants::ENTRY_POINT_CANCEL,
+            RuntimeArgs::new(),
+            secret_key,
+        )
+    }
+
+    pub fn propose_end_time(
+        &self,
+        contract_hash: ContractHash,
+        end_time: u64,
+        secret_key: &SecretKey,
+    ) -> Deploy {
+        self.call(
+            contract_hash,
+            constants::ENTRY_POINT_PROPOSE_END_TIME,
+            args::end_time(end_time),
+            secret_key,
+        )
+    }
+
+    pub fn accept_end_time(
+        &self,
+        contract_hash: ContractHash,
+        end_time: u64,
+        secret_key: &SecretKey,
+    ) -> Deploy {
+        self.call(
+            contract_hash,
+            constants::ENTRY_POINT_ACCEPT_END_TIME,
+            args::end_time(end_time),
+            secret_key,
+        )
+    }
+
+    /// Deposits `amount` motes into a `Direct` swap contract with `session.wasm`, crediting the
+    /// signer. Deposited CSPR can be locked by `initiate` or taken back with `withdraw`.
+    pub fn deposit(
+        &self,
+        session_wasm: Vec<u8>,
+        contract_hash: ContractHash,
+        amount: U512,
+        secret_key: &SecretKey,
+    ) -> Deploy {
+        let session = ExecutableDeployItem::ModuleBytes {
+            module_bytes: Bytes::from(session_wasm),
+            args: args::deposit(contract_hash, amount),
+        };
+        self.sign(session, secret_key)
+    }
+
+    pub fn withdraw(
+        &self,
+        contract_hash: ContractHash,
+        amount: U512,
+        secret_key: &SecretKey,
+    ) -> Deploy {
+        self.call(
+            contract_hash,
+            constants::ENTRY_POINT_WITHDRAW,
+            args::withdraw(amount),
+            secret_key,
+        )
+    }
+
+    /// Initiates a swap on each contract of `swaps` in one deploy of the batch session. If one
+    /// of them fails, none is initiated.
+    pub fn initiate_batch(
+        &self,
+        batch_wasm: Vec<u8>,
+        swaps: &[(ContractHash, Initiate)],
+        secret_key: &SecretKey,
+    ) -> Result<Deploy, Error> {
+        let session = ExecutableDeployItem::ModuleBytes {
+            module_bytes: Bytes::from(batch_wasm),
+            args: args::initiate_batch(swaps)?,
+        };
+        Ok(self.sign(session, secret_key))
+    }
+
+    /// Unlocks every swap of `redemptions` in one deploy of the batch session, all or nothing.
+    pub fn unlock_batch(
+        &self,
+        batch_wasm: Vec<u8>,
+        redemptions: Vec<Redemption>,
+        secret_key: &SecretKey,
+    ) -> Deploy {
+        let session = ExecutableDeployItem::ModuleBytes {
+            module_bytes: Bytes::from(batch_wasm),
+            args: args::unlock_batch(redemptions),
+        };
+        self.sign(session, secret_key)
+    }
+
+    /// Sets the fee of `asset_type` swaps, as the admin.
+    pub fn set_fee(
+        &self,
+        contract_hash: ContractHash,
+        asset_type: &str,
+        fee_bps: u32,
+        secret_key: &SecretKey,
+    ) -> Deploy {
+        self.call(
+            contract_hash,
+            constants::ENTRY_POINT_SET_FEE,
+            args::set_fee(asset_type, fee_bps),
+            secret_key,
+        )
+    }
+
+    /// Sets the key fees are paid to, as the admin.
+    pub fn set_treasury(
+        &self,
+        contract_hash: ContractHash,
+        treasury: Key,
+        secret_key: &SecretKey,
+    ) -> Deploy {
+        self.call(
+            contract_hash,
+            constants::ENTRY_POINT_SET_TREASURY,
+            args::set_treasury(treasury),
+            secret_key,
+        )
+    }
+
+    /// Lists a token contract of `standard`, as the admin.
+    pub fn allow_token(
+        &self,
+        contract_hash: ContractHash,
+        token_contract: TokenContract,
+        standard: &str,
+        secret_key: &SecretKey,
+    ) -> Deploy {
+        self.call(
+            contract_hash,
+            constants::ENTRY_POINT_ALLOW_TOKEN,
+            args::allow_token(token_contract, standard),
+            secret_key,
+        )
+    }
+
+    pub fn remove_token(
+        &self,
+        contract_hash: ContractHash,
+        token_contract: TokenContract,
+        secret_key: &SecretKey,
+    ) -> Deploy {
+        self.call(
+            contract_hash,
+            constants::ENTRY_POINT_REMOVE_TOKEN,
+            args::remove_token(token_contract),
+            secret_key,
+        )
+    }
+
+    /// Makes `initiate` reject unlisted tokens, or accept them again, as the admin.
+    pub fn enforce_allowlist(
+        &self,
+        contract_hash: ContractHash,
+        enforced: bool,
+        secret_key: &SecretKey,
+    ) -> Deploy {
+        self.call(
+            contract_hash,
+            constants::ENTRY_POINT_ENFORCE_ALLOWLIST,
+            args::enforce_allowlist(enforced),
+            secret_key,
+        )
+    }
+
+    /// Calls any entry point of an installed contract.
+    pub fn call(
+        &self,
+        contract_hash: ContractHash,
+        entry_point: &str,
+        args: RuntimeArgs,
+        secret_key: &SecretKey,
+    ) -> Deploy {
+        let session = ExecutableDeployItem::StoredContractByHash {
+            hash: contract_hash,
+            entry_point: entry_point.to_string(),
+            args,
+        };
+        self.sign(session, secret_key)
+    }
+
+    /// Calls an entry point of a token contract, of its latest version if it is a package.
+    pub fn call_token(
+        &self,
+        token_contract: TokenContract,
+        entry_point: &str,
+        args: RuntimeArgs,
+        secret_key: &SecretKey,
+    ) -> Deploy {
+        let session = match token_contract {
+            TokenContract::Contract(contract_hash) => ExecutableDeployItem::StoredContractByHash {
+                hash: contract_hash,
+                entry_point: entry_point.to_string(),
+                args,
+            },
+            TokenContract::Package(package_hash) => {
+                ExecutableDeployItem::StoredVersionedContractByHash {
+                    hash: package_hash,
+                    version: None,
+                    entry_point: entry_point.to_string(),
+                    args,
+                }
+            }
+        };
+        self.sign(session, secret_key)
+    }
+
+    fn sign(&self, session: ExecutableDeployItem, secret_key: &SecretKey) -> Deploy {
+        // standard payment
+        let payment = ExecutableDeployItem::ModuleBytes {
+            module_bytes: Bytes::new(),
+            args: runtime_args! {
+                "amount" => self.payment_amount,
+            },
+        };
+        Deploy::new(
+            self.timestamp.unwrap_or_else(Timestamp::now),
+            self.ttl,
+            self.gas_price,
+            vec![],
+            self.chain_name.clone(),
+            payment,
+            session,
+            secret_key,
+            None,
+        )
+    }
+}
@@ -0,0 +1,147 @@
+//! Secrets derived from one master seed, so backing up the seed backs up every swap.
+//!
+//! The secret of a swap is `HMAC-SHA256(seed, domain || index || counterparty || chain)`, hex
+//! encoded like [`generate_secret`](crate::generate_secret). Each field is length prefixed, and
+//! the counterparty and chain are part of the input so the same index used with two
+//! counterparties never gives the same secret.
+use std::fmt;
+
+use casper_types::ContractPackageHash;
+use hmac::{Hmac, Mac};
+use rand::{rngs::OsRng, RngCore};
+use sha2::Sha256;
+
+use crate::{swap_hashlock, Error, Salt, SwapInfo};
+
+const DOMAIN: &[u8] = b"casper-atomic-swap/secret/v1";
+
+/// The master seed. Keep it as safe as a secret key.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Seed([u8; 32]);
+
+impl Seed {
+    pub fn generate() -> Self {
+        let mut bytes = [0u8; 32];
+        OsRng.fill_bytes(&mut bytes);
+        Seed(bytes)
+    }
+
+    pub fn from_hex(value: &str) -> Result<Self, Error> {
+        let bytes = hex::decode(value.trim()).map_err(|error| Error::Key(error.to_string()))?;
+        if bytes.len() != 32 {
+            return Err(Error::Key(format!(
+                "a seed is 32 bytes, got {}",
+                bytes.len()
+            )));
+        }
+        let mut seed = [0u8; 32];
+        seed.copy_from_slice(&bytes);
+        Ok(Seed(seed))
+    }
+
+    pub fn to_hex(&self) -> String {
+        hex::encode(self.0)
+    }
+
+    /// The secret of swap number `index` with `counterparty` on `chain`.
+    pub fn derive_secret(&self, index: u64, context: &DerivationContext) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.0).expect("hmac takes keys of any size");
+        let index = index.to_be_bytes();
+        let fields: [&[u8]; 4] = [
+            DOMAIN,
+            &index,
+            context.counterparty.as_bytes(),
+            context.chain.as_bytes(),
+        ];
+        for field in fields.iter() {
+            mac.update(&(field.len() as u32).to_be_bytes());
+            mac.update(field);
+        }
+        hex::encode(mac.finalize().into_bytes())
+    }
+}
+
+impl fmt::Debug for Seed {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("Seed(..)")
+    }
+}
+
+/// Who and where a derived secret is used with. `counterparty` is the receiver of the swap as a
+/// formatted key (`account-hash-...`), `chain` the chain name of the swap contract.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DerivationContext {
+    pub counterparty: String,
+    pub chain: String,
+}
+
+/// What recovery needs to know about a swap found on chain.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OnChainSwap {
+    pub contract_hash: String,
+    pub swap_id: u64,
+    pub hash_type: String,
+    pub hashlock: String,
+    /// Set if the hashlock is over the salted secret.
+    pub salt: Option<Salt>,
+    pub context: DerivationContext,
+}
+
+impl OnChainSwap {
+    /// A swap of the history of `contract_hash` on `chain`, open or closed. A salted swap is
+    /// salted with `contract_package_hash`, the package of the contract.
+    pub fn from_info(
+        contract_hash: &str,
+        contract_package_hash: ContractPackageHash,
+        info: &SwapInfo,
+        chain: &str,
+    ) -> Self {
+        OnChainSwap {
+            contract_hash: contract_hash.to_string(),
+            swap_id: info.swap_id,
+            hash_type: info.hash_type.clone(),
+            hashlock: info.hashlock.clone(),
+            salt: if info.salted {
+                Some(Salt {
+                    contract_package_hash,
+                    swap_id: info.swap_id,
+                })
+            } else {
+                None
+            },
+            context: DerivationContext {
+                counterparty: info.receiver.to_formatted_string(),
+                chain: chain.to_string(),
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RecoveredSecret {
+    pub swap: OnChainSwap,
+    pub index: u64,
+    pub secret: String,
+}
+
+/// Re-derives the secrets of indexes `0..gap_limit` for the context of every swap, and returns
+/// the ones matching its hashlock, salted if the swap is. Swaps with no match are not ours, or
+/// used a higher index.
+pub fn recover(seed: &Seed, swaps: &[OnChainSwap], gap_limit: u64) -> Vec<RecoveredSecret> {
+    swaps
+        .iter()
+        .filter_map(|swap| {
+            (0..gap_limit).find_map(|index| {
+                let secret = seed.derive_secret(index, &swap.context);
+                match swap_hashlock(&swap.hash_type, &secret, swap.salt.as_ref()) {
+                    Ok(hash) if hash == swap.hashlock => Some(RecoveredSecret {
+                        swap: swap.clone(),
+                        index,
+                        secret,
+                    }),
+                    _ => None,
+                }
+            })
+        })
+        .collect()
+}
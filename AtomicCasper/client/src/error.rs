@@ -0,0 +1,56 @@
+use std::convert::TryFrom;
+
+use casper_types::ApiError;
+
+use crate::ContractError;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("hash type {0} is not supported")]
+    HashTypeNotSupported(String),
+    #[error("the hashlock of the swap is not set")]
+    MissingHashlock,
+    #[error("invalid runtime argument: {0}")]
+    Args(String),
+    #[error("failed to load key: {0}")]
+    Key(String),
+    #[error("failed to decode value: {0}")]
+    Decode(String),
+    #[error("the contract reverted with {0:?}")]
+    Contract(ContractError),
+    #[error("rpc error: {0}")]
+    Rpc(String),
+    #[error("vault error: {0}")]
+    Vault(String),
+    #[error("invalid swap terms: {0}")]
+    Terms(String),
+    #[error("unsuitable token contract: {0}")]
+    TokenContract(String),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+/// The contract error behind an `ApiError::User`, if it is one the contract knows.
+pub fn contract_error(api_error: ApiError) -> Option<ContractError> {
+    match api_error {
+        ApiError::User(code) => ContractError::try_from(code).ok(),
+        _ => None,
+    }
+}
+
+/// Finds the contract error in the error message of a failed deploy, which looks like
+/// `User error: 19` or `ApiError::User(19) [65555]` depending on the node version.
+pub fn parse_execution_error(message: &str) -> Option<ContractError> {
+    let code = if let Some(index) = message.find("User error: ") {
+        &message[index + "User error: ".len()..]
+    } else if let Some(index) = message.find("User(") {
+        &message[index + "User(".len()..]
+    } else {
+        return None;
+    };
+    let end = code
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or_else(|| code.len());
+    let code = code[..end].parse::<u16>().ok()?;
+    contract_error(ApiError::User(code))
+}
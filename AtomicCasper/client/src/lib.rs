@@ -0,0 +1,86 @@
+//! Client side of the AtomicSwap contract.
+//!
+//! Builds the runtime args of every entry point, wraps them into signed `Deploy`s and decodes
+//! what the contract gives back, so services don't have to assemble session arguments by hand.
+pub mod args;
+pub mod deploy;
+pub mod derivation;
+mod error;
+pub mod query;
+pub mod rpc;
+pub mod terms;
+pub mod vault;
+
+pub use args::{Asset, Initiate, Unlock};
+pub use deploy::{load_secret_key, Deploy, DeployBuilder};
+pub use error::{contract_error, parse_execution_error, Error};
+pub use swap_core::{
+    allowlist::Allowlist,
+    batch::{Initiation, Redemption},
+    constants,
+    fee::Fees,
+    hash::HASH_TYPES,
+    info::{SwapInfo, SwapState},
+    token::TokenContract,
+    Error as ContractError, TokenId,
+};
+pub use terms::{SignedTerms, SwapTerms};
+pub use vault::{SecretRecord, SwapContext, Vault};
+
+use casper_types::ContractPackageHash;
+use rand::{rngs::OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+
+/// The hashlock the contract stores for `secret`, computed exactly like `unlock` does.
+pub fn hashlock(hash_type: &str, secret: &str) -> Result<String, Error> {
+    if !swap_core::hash::check_hash_type(hash_type) {
+        return Err(Error::HashTypeNotSupported(hash_type.to_string()));
+    }
+    Ok(swap_core::hash::generate_hash(hash_type, secret))
+}
+
+/// The hashlock of a salted swap: the hash of `secret` bound to the contract package and the id
+/// the swap gets, see [`rpc::NodeClient::salt`].
+pub fn salted_hashlock(
+    hash_type: &str,
+    secret: &str,
+    contract_package_hash: ContractPackageHash,
+    swap_id: u64,
+) -> Result<String, Error> {
+    let salt = Salt {
+        contract_package_hash,
+        swap_id,
+    };
+    swap_hashlock(hash_type, secret, Some(&salt))
+}
+
+/// What the secret of a salted swap is bound to: the contract package and the id of the swap,
+/// see [`rpc::NodeClient::salt`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Salt {
+    pub contract_package_hash: ContractPackageHash,
+    pub swap_id: u64,
+}
+
+impl Salt {
+    /// What the swap hashes instead of the bare `secret`, see [`salted_hashlock`].
+    pub fn preimage(&self, secret: &str) -> String {
+        swap_core::hash::salted_secret(secret, self.contract_package_hash, self.swap_id)
+    }
+}
+
+/// The hashlock of a swap: of `secret` salted with `salt` if the swap is salted, of the bare
+/// secret otherwise.
+pub fn swap_hashlock(hash_type: &str, secret: &str, salt: Option<&Salt>) -> Result<String, Error> {
+    match salt {
+        Some(salt) => hashlock(hash_type, &salt.preimage(secret)),
+        None => hashlock(hash_type, secret),
+    }
+}
+
+/// A new random secret: 32 bytes from the OS random generator, hex encoded.
+pub fn generate_secret() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
@@ -0,0 +1,162 @@
+//! Reading the state of a swap with the node's `query_global_state` rpc.
+use casper_types::{
+    bytesrepr::{self, FromBytes},
+    CLTyped, ContractHash,
+};
+use serde_json::{json, Value};
+use swap_core::constants;
+
+use crate::Error;
+
+/// The named keys of the contract that describe the current swap.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SwapField {
+    Hash,
+    HashType,
+    Salted,
+    Type,
+    Owner,
+    Receiver,
+    ReceiverPublicKey,
+    SwapId,
+    TermsHash,
+    Amount,
+    TokenIds,
+    StartTime,
+    EndTime,
+    ProposedEndTime,
+    ContractPackageHash,
+    Admin,
+    Treasury,
+    Fees,
+    FeeBps,
+    Allowlist,
+    AllowlistEnforced,
+}
+
+impl SwapField {
+    pub fn named_key(&self) -> &'static str {
+        match self {
+            SwapField::Hash => constants::NAMED_KEY_HASH,
+            SwapField::HashType => constants::NAMED_KEY_HASH_TYPE,
+            SwapField::Salted => constants::NAMED_KEY_SALTED,
+            SwapField::Type => constants::NAMED_KEY_TYPE,
+            SwapField::Owner => constants::NAMED_KEY_OWNER,
+            SwapField::Receiver => constants::NAMED_KEY_RECIVER,
+            SwapField::ReceiverPublicKey => constants::NAMED_KEY_RECEIVER_PUBLIC_KEY,
+            SwapField::SwapId => constants::NAMED_KEY_SWAP_ID,
+            SwapField::TermsHash => constants::NAMED_KEY_TERMS_HASH,
+            SwapField::Amount => constants::NAMED_KEY_AMOUNT,
+            SwapField::TokenIds => constants::NAMED_KEY_TOKEN_IDS,
+            SwapField::StartTime => constants::NAMED_KEY_START_TIME,
+            SwapField::EndTime => constants::NAMED_KEY_END_TIME,
+            SwapField::ProposedEndTime => constants::NAMED_KEY_PROPOSED_END_TIME,
+            SwapField::ContractPackageHash => constants::NAMED_KEY_OWN_CONTRACT_PACKAGE_HASH,
+            SwapField::Admin => constants::NAMED_KEY_ADMIN,
+            SwapField::Treasury => constants::NAMED_KEY_TREASURY,
+            SwapField::Fees => constants::NAMED_KEY_FEES,
+            SwapField::FeeBps => constants::NAMED_KEY_FEE_BPS,
+            SwapField::Allowlist => constants::NAMED_KEY_ALLOWLIST,
+            SwapField::AllowlistEnforced => constants::NAMED_KEY_ALLOWLIST_ENFORCED,
+        }
+    }
+}
+
+/// Params of a `query_global_state` request reading `field` of the contract at the given state
+/// root hash.
+pub fn query_global_state_params(
+    state_root_hash: &str,
+    contract_hash: ContractHash,
+    field: SwapField,
+) -> Value {
+    json!({
+        "state_identifier": { "StateRootHash": state_root_hash },
+        "key": format!("hash-{}", hex::encode(contract_hash.value())),
+        "path": [field.named_key()],
+    })
+}
+
+/// Params of a `query_global_state` request reading the contract or package stored at `address`,
+/// e.g. a token contract.
+pub fn stored_value_params(state_root_hash: &str, address: [u8; 32]) -> Value {
+    json!({
+        "state_identifier": { "StateRootHash": state_root_hash },
+        "key": format!("hash-{}", hex::encode(address)),
+        "path": [],
+    })
+}
+
+/// Params of a `state_get_dictionary_item` request reading `item_key` of the dictionary the
+/// contract keeps under the named key `dictionary_name`.
+pub fn dictionary_item_params(
+    state_root_hash: &str,
+    contract_hash: ContractHash,
+    dictionary_name: &str,
+    item_key: &str,
+) -> Value {
+    json!({
+        "state_root_hash": state_root_hash,
+        "dictionary_identifier": {
+            "ContractNamedKey": {
+                "key": format!("hash-{}", hex::encode(contract_hash.value())),
+                "dictionary_name": dictionary_name,
+                "dictionary_item_key": item_key,
+            }
+        },
+    })
+}
+
+/// Decodes the `stored_value` of a `query_global_state` response holding a `CLValue`.
+pub fn decode<T: CLTyped + FromBytes>(stored_value: &Value) -> Result<T, Error> {
+    let bytes = stored_value
+        .get("CLValue")
+        .and_then(|cl_value| cl_value.get("bytes"))
+        .and_then(Value::as_str)
+        .ok_or_else(|| Error::Decode("stored value is not a CLValue".to_string()))?;
+    let bytes = hex::decode(bytes).map_err(|error| Error::Decode(error.to_string()))?;
+    bytesrepr::deserialize(bytes).map_err(|error| Error::Decode(error.to_string()))
+}
+
+/// The names of the entry points of a stored `Contract`.
+pub fn entry_point_names(stored_value: &Value) -> Result<Vec<String>, Error> {
+    let entry_points = stored_value
+        .get("Contract")
+        .and_then(|contract| contract.get("entry_points"))
+        .and_then(Value::as_array)
+        .ok_or_else(|| Error::TokenContract("not a contract".to_string()))?;
+    Ok(entry_points
+        .iter()
+        .filter_map(|entry_point| entry_point.get("name").and_then(Value::as_str))
+        .map(str::to_string)
+        .collect())
+}
+
+/// The version of a stored `ContractPackage` a call without a version goes to: the latest one
+/// that isn't disabled.
+pub fn latest_version(stored_value: &Value) -> Result<ContractHash, Error> {
+    let package = stored_value
+        .get("ContractPackage")
+        .ok_or_else(|| Error::TokenContract("not a contract package".to_string()))?;
+    let version_key = |version: &Value| {
+        Some((
+            version.get("protocol_version_major")?.as_u64()?,
+            version.get("contract_version")?.as_u64()?,
+        ))
+    };
+    let disabled: Vec<_> = package
+        .get("disabled_versions")
+        .and_then(Value::as_array)
+        .map(|versions| versions.iter().filter_map(version_key).collect())
+        .unwrap_or_default();
+    let latest = package
+        .get("versions")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|version| Some((version_key(version)?, version)))
+        .filter(|(key, _)| !disabled.contains(key))
+        .max_by_key(|(key, _)| *key)
+        .and_then(|(_, version)| version.get("contract_hash")?.as_str())
+        .ok_or_else(|| Error::TokenContract("the package has no enabled version".to_string()))?;
+    ContractHash::from_formatted_str(latest).map_err(|error| Error::Decode(format!("{:?}", error)))
+}
@@ -0,0 +1,382 @@
+//! A minimal JSON-RPC client for the node endpoints the swap tools use.
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use casper_types::{bytesrepr::FromBytes, CLTyped, ContractHash, ContractPackageHash, Key, U512};
+use serde_json::{json, Value};
+use swap_core::{
+    allowlist::Allowlist,
+    constants,
+    fee::Fees,
+    info::{self, SwapInfo, SwapState},
+    ledger, registry,
+    token::{self, TokenContract},
+};
+
+use crate::{
+    query::{self, SwapField},
+    terms::LegTerms,
+    Deploy, Error, SwapTerms,
+};
+
+/// Sends one JSON-RPC request and returns its `result`.
+pub trait Transport {
+    fn call(&self, method: &str, params: Value) -> Result<Value, Error>;
+}
+
+/// Talks to a node over http, e.g. `http://<node address>:7777/rpc`.
+pub struct HttpTransport {
+    url: String,
+    next_id: AtomicU64,
+}
+
+impl HttpTransport {
+    pub fn new(url: &str) -> Self {
+        HttpTransport {
+            url: url.to_string(),
+            next_id: AtomicU64::new(1),
+        }
+    }
+}
+
+impl Transport for HttpTransport {
+    fn call(&self, method: &str, params: Value) -> Result<Value, Error> {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": self.next_id.fetch_add(1, Ordering::Relaxed),
+            "method": method,
+            "params": params,
+        });
+        let response: Value = ureq::post(&self.url)
+            .send_json(request)
+            .map_err(|error| Error::Rpc(error.to_string()))?
+            .into_json()?;
+        if let Some(error) = response.get("error") {
+            return Err(Error::Rpc(error.to_string()));
+        }
+        response
+            .get("result")
+            .cloned()
+            .ok_or_else(|| Error::Rpc("response has no result".to_string()))
+    }
+}
+
+pub struct NodeClient<T> {
+    transport: T,
+}
+
+impl<T: Transport> NodeClient<T> {
+    pub fn new(transport: T) -> Self {
+        NodeClient { transport }
+    }
+
+    pub fn transport(&self) -> &T {
+        &self.transport
+    }
+
+    pub fn state_root_hash(&self) -> Result<String, Error> {
+        let result = self
+            .transport
+            .call("chain_get_state_root_hash", json!({}))?;
+        result
+            .get("state_root_hash")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .ok_or_else(|| Error::Rpc("no state root hash".to_string()))
+    }
+
+    /// Reads one named key of the swap contract at the latest state root hash.
+    pub fn query<V: CLTyped + FromBytes>(
+        &self,
+        contract_hash: ContractHash,
+        field: SwapField,
+    ) -> Result<V, Error> {
+        let state_root_hash = self.state_root_hash()?;
+        self.query_at(&state_root_hash, contract_hash, field)
+    }
+
+    fn query_at<V: CLTyped + FromBytes>(
+        &self,
+        state_root_hash: &str,
+        contract_hash: ContractHash,
+        field: SwapField,
+    ) -> Result<V, Error> {
+        let params = query::query_global_state_params(state_root_hash, contract_hash, field);
+        let result = self.transport.call("query_global_state", params)?;
+        let stored_value = result
+            .get("stored_value")
+            .ok_or_else(|| Error::Rpc("no stored value".to_string()))?;
+        query::decode(stored_value)
+    }
+
+    /// The treasury and fee rates of the contract, what its `get_fees` entry point returns.
+    pub fn fees(&self, contract_hash: ContractHash) -> Result<Fees, Error> {
+        let state_root_hash = self.state_root_hash()?;
+        Ok(Fees {
+            treasury: self.query_at(&state_root_hash, contract_hash, SwapField::Treasury)?,
+            rates: self.query_at(&state_root_hash, contract_hash, SwapField::Fees)?,
+        })
+    }
+
+    /// The contract package and the id the next swap of the contract gets, what the secret of a
+    /// salted swap is bound to. Only the owner initiates, so the id holds until it does.
+    pub fn salt(&self, contract_hash: ContractHash) -> Result<(ContractPackageHash, u64), Error> {
+        let state_root_hash = self.state_root_hash()?;
+        let swap_id: u64 = self.query_at(&state_root_hash, contract_hash, SwapField::SwapId)?;
+        let contract_package_hash = self.query_at(
+            &state_root_hash,
+            contract_hash,
+            SwapField::ContractPackageHash,
+        )?;
+        Ok((contract_package_hash, swap_id + 1))
+    }
+
+    /// The token contracts the admin listed, what the `get_allowlist` entry point returns.
+    pub fn allowlist(&self, contract_hash: ContractHash) -> Result<Allowlist, Error> {
+        let state_root_hash = self.state_root_hash()?;
+        Ok(Allowlist {
+            enforced: self.query_at(
+                &state_root_hash,
+                contract_hash,
+                SwapField::AllowlistEnforced,
+            )?,
+            tokens: self.query_at(&state_root_hash, contract_hash, SwapField::Allowlist)?,
+        })
+    }
+
+    /// The record of swap `swap_id` of the contract, what its `get_swap` entry point returns.
+    pub fn swap(&self, contract_hash: ContractHash, swap_id: u64) -> Result<SwapInfo, Error> {
+        let state_root_hash = self.state_root_hash()?;
+        self.swap_at(&state_root_hash, contract_hash, swap_id)
+    }
+
+    /// One page of the swaps of the contract, oldest first, like its `list_swaps` entry point.
+    pub fn swaps(
+        &self,
+        contract_hash: ContractHash,
+        offset: u64,
+        limit: u64,
+    ) -> Result<Vec<SwapInfo>, Error> {
+        let state_root_hash = self.state_root_hash()?;
+        let count: u64 = self.query_at(&state_root_hash, contract_hash, SwapField::SwapId)?;
+        info::page(count, offset, limit)
+            .map(|swap_id| self.swap_at(&state_root_hash, contract_hash, swap_id))
+            .collect()
+    }
+
+    /// Every swap the contract ran, oldest first, the open one included.
+    pub fn history(&self, contract_hash: ContractHash) -> Result<Vec<SwapInfo>, Error> {
+        let state_root_hash = self.state_root_hash()?;
+        let count: u64 = self.query_at(&state_root_hash, contract_hash, SwapField::SwapId)?;
+        (1..=count)
+            .map(|swap_id| self.swap_at(&state_root_hash, contract_hash, swap_id))
+            .collect()
+    }
+
+    /// CSPR `depositor` deposited into the contract, or a contract was paid by a swap, and hasn't
+    /// locked or withdrawn yet. Fails if `depositor` never had any.
+    pub fn deposit(&self, contract_hash: ContractHash, depositor: Key) -> Result<U512, Error> {
+        self.dictionary_item(
+            contract_hash,
+            constants::NAMED_KEY_DEPOSITS,
+            &ledger::item_key(&depositor),
+        )
+    }
+
+    /// The swap contracts the factory installed for `owner`, oldest first. Fails if the factory
+    /// never installed one for `owner`.
+    pub fn instances(
+        &self,
+        factory_hash: ContractHash,
+        owner: Key,
+    ) -> Result<Vec<ContractHash>, Error> {
+        self.dictionary_item(
+            factory_hash,
+            constants::NAMED_KEY_INSTANCES_BY_OWNER,
+            &registry::owner_item_key(&owner),
+        )
+    }
+
+    /// The swap contract of the factory `hashlock` was initiated on.
+    pub fn find_swap(
+        &self,
+        factory_hash: ContractHash,
+        hashlock: &str,
+    ) -> Result<ContractHash, Error> {
+        self.dictionary_item(
+            factory_hash,
+            constants::NAMED_KEY_INSTANCES_BY_HASHLOCK,
+            &registry::hashlock_item_key(hashlock),
+        )
+    }
+
+    /// Checks the token contract exists and exposes the entry points swaps of `asset_type` call,
+    /// those of its latest version if it is a package. The contract can't look at the entry
+    /// points of another contract, so this is worth running before `initiate`.
+    pub fn check_token_contract(
+        &self,
+        token_contract: TokenContract,
+        asset_type: &str,
+    ) -> Result<(), Error> {
+        let state_root_hash = self.state_root_hash()?;
+        let contract_hash = match token_contract {
+            TokenContract::Contract(contract_hash) => contract_hash,
+            TokenContract::Package(package_hash) => {
+                query::latest_version(&self.stored_value(&state_root_hash, package_hash.value())?)?
+            }
+        };
+        let contract = self.stored_value(&state_root_hash, contract_hash.value())?;
+        let missing =
+            token::missing_entry_points(asset_type, &query::entry_point_names(&contract)?);
+        if !missing.is_empty() {
+            return Err(Error::TokenContract(format!(
+                "{} swaps call {}, which contract-{} lacks",
+                asset_type,
+                missing.join(", "),
+                hex::encode(contract_hash.value())
+            )));
+        }
+        Ok(())
+    }
+
+    fn stored_value(&self, state_root_hash: &str, address: [u8; 32]) -> Result<Value, Error> {
+        let params = query::stored_value_params(state_root_hash, address);
+        let result = self.transport.call("query_global_state", params)?;
+        result.get("stored_value").cloned().ok_or_else(|| {
+            Error::TokenContract(format!("nothing stored at hash-{}", hex::encode(address)))
+        })
+    }
+
+    fn dictionary_item<T: CLTyped + FromBytes>(
+        &self,
+        contract_hash: ContractHash,
+        dictionary_name: &str,
+        item_key: &str,
+    ) -> Result<T, Error> {
+        let params = query::dictionary_item_params(
+            &self.state_root_hash()?,
+            contract_hash,
+            dictionary_name,
+            item_key,
+        );
+        let result = self.transport.call("state_get_dictionary_item", params)?;
+        let stored_value = result
+            .get("stored_value")
+            .ok_or_else(|| Error::Rpc("no stored value".to_string()))?;
+        query::decode(stored_value)
+    }
+
+    fn swap_at(
+        &self,
+        state_root_hash: &str,
+        contract_hash: ContractHash,
+        swap_id: u64,
+    ) -> Result<SwapInfo, Error> {
+        let params = query::dictionary_item_params(
+            state_root_hash,
+            contract_hash,
+            constants::NAMED_KEY_SWAPS,
+            &swap_id.to_string(),
+        );
+        let result = self.transport.call("state_get_dictionary_item", params)?;
+        let stored_value = result
+            .get("stored_value")
+            .ok_or_else(|| Error::Rpc("no stored value".to_string()))?;
+        query::decode(stored_value)
+    }
+
+    /// Checks the swap the contract holds against the signed terms it claims to implement, and
+    /// `leg` of the terms, the one locked in this contract: the asset, amount and token ids, who
+    /// locked it and for whom, the hashlock and the terms hash. The lock may end earlier than
+    /// agreed, never later.
+    pub fn check_terms(
+        &self,
+        contract_hash: ContractHash,
+        terms: &SwapTerms,
+        leg: &LegTerms,
+    ) -> Result<(), Error> {
+        let mismatch = |field: &str| {
+            Err(Error::Terms(format!(
+                "the swap doesn't have the {} of the terms",
+                field
+            )))
+        };
+        let state_root_hash = self.state_root_hash()?;
+        let swap_id: u64 = self.query_at(&state_root_hash, contract_hash, SwapField::SwapId)?;
+        let swap = self.swap_at(&state_root_hash, contract_hash, swap_id)?;
+        if swap.state != SwapState::Open {
+            return Err(Error::Terms("the contract holds no open swap".to_string()));
+        }
+        if swap.terms_hash != terms.hash()? {
+            return mismatch("terms hash");
+        }
+        if swap.hash_type != terms.hash_type || swap.hashlock != terms.hashlock {
+            return mismatch("hashlock");
+        }
+        if !is_asset(&leg.asset, &swap) {
+            return mismatch("asset");
+        }
+        if swap.amount.to_string() != leg.amount.to_string() {
+            return mismatch("amount");
+        }
+        if swap.token_ids != leg.token_ids {
+            return mismatch("token ids");
+        }
+        if swap.owner.to_formatted_string() != leg.sender {
+            return mismatch("sender");
+        }
+        if swap.receiver.to_formatted_string() != leg.receiver {
+            return mismatch("receiver");
+        }
+        if swap.end_time > leg.end_time {
+            return mismatch("end time");
+        }
+        Ok(())
+    }
+
+    /// The block at `height`, or the latest block.
+    pub fn block(&self, height: Option<u64>) -> Result<Value, Error> {
+        let params = match height {
+            Some(height) => json!({ "block_identifier": { "Height": height } }),
+            None => json!({}),
+        };
+        let result = self.transport.call("chain_get_block", params)?;
+        result
+            .get("block")
+            .cloned()
+            .ok_or_else(|| Error::Rpc("no block".to_string()))
+    }
+
+    /// The deploy and its `execution_results`.
+    pub fn deploy(&self, deploy_hash: &str) -> Result<Value, Error> {
+        self.transport
+            .call("info_get_deploy", json!({ "deploy_hash": deploy_hash }))
+    }
+
+    /// Sends a signed deploy and returns its hash.
+    pub fn put_deploy(&self, deploy: &Deploy) -> Result<String, Error> {
+        let result = self
+            .transport
+            .call("account_put_deploy", json!({ "deploy": deploy }))?;
+        result
+            .get("deploy_hash")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .ok_or_else(|| Error::Rpc("no deploy hash".to_string()))
+    }
+}
+
+/// Whether the swap locks `asset` as terms name it: `CSPR`, or its token contract as a contract
+/// hash or package hash in any of the forms `swapctl` accepts.
+fn is_asset(asset: &str, swap: &SwapInfo) -> bool {
+    match &swap.token_contract {
+        None => asset == "CSPR",
+        Some(TokenContract::Contract(contract_hash)) => {
+            asset == contract_hash.to_formatted_string()
+                || asset == format!("hash-{}", hex::encode(contract_hash.value()))
+        }
+        Some(TokenContract::Package(package_hash)) => {
+            asset == package_hash.to_formatted_string()
+                || asset == format!("contract-package-{}", hex::encode(package_hash.value()))
+        }
+    }
+}
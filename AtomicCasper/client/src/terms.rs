@@ -0,0 +1,182 @@
+//! The terms of a swap, as both parties agree on them before anything is locked.
+//!
+//! [`SwapTerms`] names both legs: what each party locks, on which chain, for whom and until
+//! when, under one hashlock. Its canonical bytes are the casper `ToBytes` encoding of every
+//! field in declaration order, after a domain tag and the format version, so both parties get
+//! the same [`SwapTerms::hash`] whatever language or JSON library they use. The hash is what
+//! both parties sign, and what `initiate` stores in the contract's `terms_hash` named key.
+use casper_types::{bytesrepr::ToBytes, crypto, PublicKey, SecretKey, Signature, U256};
+use serde::{Deserialize, Serialize};
+
+use swap_core::hash::{blake2b, check_hash_type};
+
+use crate::Error;
+
+/// Version of the canonical encoding, bumped whenever fields change.
+pub const TERMS_VERSION: u8 = 1;
+
+const DOMAIN: &str = "casper-atomic-swap/terms";
+
+/// What one party locks.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LegTerms {
+    /// e.g. `casper`, `casper-test` or the name of another chain.
+    pub chain: String,
+    /// `CSPR`, or the contract of the token on that chain.
+    pub asset: String,
+    /// In the smallest unit of the asset, 0 for NFTs.
+    pub amount: U256,
+    #[serde(default)]
+    pub token_ids: Vec<U256>,
+    /// Address that locks the asset, and gets it back on refund.
+    pub sender: String,
+    /// Address that gets the asset on redemption.
+    pub receiver: String,
+    /// Deadline of the lock, in milliseconds since the unix epoch.
+    pub end_time: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SwapTerms {
+    pub version: u8,
+    pub hash_type: String,
+    pub hashlock: String,
+    /// The party that knows the secret and locks first.
+    pub initiator: LegTerms,
+    /// The party that locks second, once the initiator's lock is on chain.
+    pub participant: LegTerms,
+    pub initiator_key: PublicKey,
+    pub participant_key: PublicKey,
+}
+
+impl SwapTerms {
+    /// Checks what both parties should check before signing. The initiator learns nothing
+    /// before redeeming, so its lock must outlast the participant's: otherwise it could redeem
+    /// the participant's leg at the last moment and refund its own.
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.version != TERMS_VERSION {
+            return Err(Error::Terms(format!(
+                "version {} is not supported",
+                self.version
+            )));
+        }
+        if !check_hash_type(&self.hash_type) {
+            return Err(Error::HashTypeNotSupported(self.hash_type.clone()));
+        }
+        if self.hashlock.is_empty() {
+            return Err(Error::MissingHashlock);
+        }
+        if self.initiator.end_time <= self.participant.end_time {
+            return Err(Error::Terms(
+                "the initiator's lock must end after the participant's".to_string(),
+            ));
+        }
+        if self.initiator_key == self.participant_key {
+            return Err(Error::Terms("both parties have the same key".to_string()));
+        }
+        Ok(())
+    }
+
+    /// The canonical encoding of the terms.
+    pub fn to_canonical_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut bytes = Vec::new();
+        append(&mut bytes, DOMAIN.to_string())?;
+        append(&mut bytes, self.version)?;
+        append(&mut bytes, self.hash_type.clone())?;
+        append(&mut bytes, self.hashlock.clone())?;
+        for leg in [&self.initiator, &self.participant] {
+            append(&mut bytes, leg.chain.clone())?;
+            append(&mut bytes, leg.asset.clone())?;
+            append(&mut bytes, leg.amount)?;
+            append(&mut bytes, leg.token_ids.clone())?;
+            append(&mut bytes, leg.sender.clone())?;
+            append(&mut bytes, leg.receiver.clone())?;
+            append(&mut bytes, leg.end_time)?;
+        }
+        append(&mut bytes, self.initiator_key.clone())?;
+        append(&mut bytes, self.participant_key.clone())?;
+        Ok(bytes)
+    }
+
+    /// Blake2b of the canonical bytes, what the parties sign.
+    pub fn digest(&self) -> Result<[u8; 32], Error> {
+        Ok(blake2b(&self.to_canonical_bytes()?))
+    }
+
+    /// The digest hex encoded: the value of `initiate`'s `terms_hash`.
+    pub fn hash(&self) -> Result<String, Error> {
+        Ok(hex::encode(self.digest()?))
+    }
+}
+
+fn append<T: ToBytes>(bytes: &mut Vec<u8>, value: T) -> Result<(), Error> {
+    let value = value
+        .into_bytes()
+        .map_err(|error| Error::Terms(error.to_string()))?;
+    bytes.extend(value);
+    Ok(())
+}
+
+/// The terms and the signatures of both parties over their hash, the document the parties
+/// exchange.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedTerms {
+    pub terms: SwapTerms,
+    #[serde(default)]
+    pub initiator_signature: Option<Signature>,
+    #[serde(default)]
+    pub participant_signature: Option<Signature>,
+}
+
+impl SignedTerms {
+    pub fn new(terms: SwapTerms) -> Self {
+        SignedTerms {
+            terms,
+            initiator_signature: None,
+            participant_signature: None,
+        }
+    }
+
+    /// Validates the terms and signs their hash as whichever party `secret_key` belongs to.
+    pub fn sign(&mut self, secret_key: &SecretKey) -> Result<(), Error> {
+        self.terms.validate()?;
+        let public_key = PublicKey::from(secret_key);
+        let signature = crypto::sign(self.terms.digest()?, secret_key, &public_key);
+        if public_key == self.terms.initiator_key {
+            self.initiator_signature = Some(signature);
+        } else if public_key == self.terms.participant_key {
+            self.participant_signature = Some(signature);
+        } else {
+            return Err(Error::Terms(
+                "the key is not one of the parties'".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Checks that the terms are valid and signed by both parties, and returns their hash.
+    pub fn verify(&self) -> Result<String, Error> {
+        self.terms.validate()?;
+        let digest = self.terms.digest()?;
+        let signatures = [
+            (
+                &self.initiator_signature,
+                &self.terms.initiator_key,
+                "initiator",
+            ),
+            (
+                &self.participant_signature,
+                &self.terms.participant_key,
+                "participant",
+            ),
+        ];
+        for (signature, public_key, party) in signatures {
+            let signature = signature
+                .as_ref()
+                .ok_or_else(|| Error::Terms(format!("the {} has not signed", party)))?;
+            crypto::verify(digest, signature, public_key)
+                .map_err(|_| Error::Terms(format!("invalid signature of the {}", party)))?;
+        }
+        self.terms.hash()
+    }
+}
@@ -0,0 +1,251 @@
+//! Encrypted, file based store of the secrets of the swaps we initiate.
+//!
+//! The whole swap rests on the initiator keeping its secret until the counterparty locks its
+//! side, so a secret is written to disk, encrypted, before its hashlock is ever handed out.
+//! The vault file is JSON holding the argon2 salt, the XChaCha20-Poly1305 nonce and the
+//! encrypted list of records. Every save uses a fresh nonce and replaces the file atomically, so
+//! a crash leaves either the old or the new vault, never a half written one.
+use std::{
+    fs::{self, File, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use argon2::Argon2;
+use chacha20poly1305::{
+    aead::{Aead, NewAead},
+    Key, XChaCha20Poly1305, XNonce,
+};
+use rand::{rngs::OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+
+use crate::{generate_secret, hashlock, swap_hashlock, Error, Salt};
+
+const VAULT_VERSION: u32 = 1;
+
+/// Where and until when a secret is used.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SwapContext {
+    /// Swap id on the Casper contract, known once `initiate` went through.
+    pub swap_id: Option<u64>,
+    /// Hash of the swap contract on Casper.
+    pub contract_hash: Option<String>,
+    /// The chain the secret is revealed on, e.g. `casper-test`.
+    pub local_chain: String,
+    /// The chain of the other leg of the swap, e.g. `ethereum`.
+    pub remote_chain: String,
+    /// Deadline of our lock, in milliseconds since the unix epoch.
+    pub local_end_time: Option<u64>,
+    /// Deadline of the counterparty's lock, in milliseconds since the unix epoch.
+    pub remote_end_time: Option<u64>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SecretRecord {
+    pub secret: String,
+    pub hash_type: String,
+    pub hashlock: String,
+    #[serde(flatten)]
+    pub context: SwapContext,
+    /// Milliseconds since the unix epoch.
+    pub created_at: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct VaultFile {
+    version: u32,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+pub struct Vault {
+    path: PathBuf,
+    salt: [u8; 16],
+    key: [u8; 32],
+    records: Vec<SecretRecord>,
+}
+
+impl Vault {
+    /// Creates a new, empty vault. Fails if `path` already exists.
+    pub fn create<P: AsRef<Path>>(path: P, password: &str) -> Result<Self, Error> {
+        let path = path.as_ref().to_path_buf();
+        if path.exists() {
+            return Err(Error::Vault(format!("{} already exists", path.display())));
+        }
+        let mut salt = [0u8; 16];
+        OsRng.fill_bytes(&mut salt);
+        let vault = Vault {
+            key: derive_key(password, &salt)?,
+            path,
+            salt,
+            records: Vec::new(),
+        };
+        vault.save()?;
+        Ok(vault)
+    }
+
+    pub fn open<P: AsRef<Path>>(path: P, password: &str) -> Result<Self, Error> {
+        let path = path.as_ref().to_path_buf();
+        let file: VaultFile = serde_json::from_slice(&fs::read(&path)?)
+            .map_err(|error| Error::Vault(error.to_string()))?;
+        if file.version != VAULT_VERSION {
+            return Err(Error::Vault(format!(
+                "unsupported vault version {}",
+                file.version
+            )));
+        }
+        let salt = decode_array::<16>(&file.salt)?;
+        let nonce = decode_array::<24>(&file.nonce)?;
+        let ciphertext =
+            hex::decode(&file.ciphertext).map_err(|error| Error::Vault(error.to_string()))?;
+        let key = derive_key(password, &salt)?;
+        let plaintext = XChaCha20Poly1305::new(Key::from_slice(&key))
+            .decrypt(XNonce::from_slice(&nonce), ciphertext.as_slice())
+            .map_err(|_| Error::Vault("wrong password or corrupted vault".to_string()))?;
+        let records =
+            serde_json::from_slice(&plaintext).map_err(|error| Error::Vault(error.to_string()))?;
+        Ok(Vault {
+            path,
+            salt,
+            key,
+            records,
+        })
+    }
+
+    pub fn records(&self) -> &[SecretRecord] {
+        &self.records
+    }
+
+    pub fn find(&self, hashlock: &str) -> Option<&SecretRecord> {
+        self.records
+            .iter()
+            .find(|record| record.hashlock == hashlock)
+    }
+
+    /// The record of the secret a salted swap was locked with, `hashlock` being the one of the
+    /// secret salted with `salt`.
+    pub fn find_salted(&self, hashlock: &str, salt: &Salt) -> Option<&SecretRecord> {
+        self.records.iter().find(|record| {
+            swap_hashlock(&record.hash_type, &record.secret, Some(salt))
+                .map_or(false, |salted| salted == hashlock)
+        })
+    }
+
+    /// Generates a secret for a new swap and stores it. The secret is on disk by the time this
+    /// returns, so the hashlock can safely be used in `initiate`.
+    pub fn new_secret(
+        &mut self,
+        hash_type: &str,
+        context: SwapContext,
+    ) -> Result<SecretRecord, Error> {
+        let secret = generate_secret();
+        self.insert(secret.as_str(), hash_type, context)
+    }
+
+    /// Stores a secret that was generated elsewhere.
+    pub fn insert(
+        &mut self,
+        secret: &str,
+        hash_type: &str,
+        context: SwapContext,
+    ) -> Result<SecretRecord, Error> {
+        let record = SecretRecord {
+            secret: secret.to_string(),
+            hash_type: hash_type.to_string(),
+            hashlock: hashlock(hash_type, secret)?,
+            context,
+            created_at: now(),
+        };
+        if self.find(&record.hashlock).is_some() {
+            return Err(Error::Vault(format!(
+                "a secret with hashlock {} is already stored",
+                record.hashlock
+            )));
+        }
+        self.records.push(record.clone());
+        self.save()?;
+        Ok(record)
+    }
+
+    /// Updates the context of a stored secret, e.g. with the swap id once `initiate` went
+    /// through.
+    pub fn update<F: FnOnce(&mut SwapContext)>(
+        &mut self,
+        hashlock: &str,
+        f: F,
+    ) -> Result<(), Error> {
+        let record = self
+            .records
+            .iter_mut()
+            .find(|record| record.hashlock == hashlock)
+            .ok_or_else(|| Error::Vault(format!("no secret with hashlock {}", hashlock)))?;
+        f(&mut record.context);
+        self.save()
+    }
+
+    fn save(&self) -> Result<(), Error> {
+        let plaintext =
+            serde_json::to_vec(&self.records).map_err(|error| Error::Vault(error.to_string()))?;
+        let mut nonce = [0u8; 24];
+        OsRng.fill_bytes(&mut nonce);
+        let ciphertext = XChaCha20Poly1305::new(Key::from_slice(&self.key))
+            .encrypt(XNonce::from_slice(&nonce), plaintext.as_slice())
+            .map_err(|_| Error::Vault("encryption failed".to_string()))?;
+        let file = VaultFile {
+            version: VAULT_VERSION,
+            salt: hex::encode(self.salt),
+            nonce: hex::encode(nonce),
+            ciphertext: hex::encode(ciphertext),
+        };
+        let json =
+            serde_json::to_vec_pretty(&file).map_err(|error| Error::Vault(error.to_string()))?;
+
+        let tmp_path = self.path.with_extension("tmp");
+        let mut tmp = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&tmp_path)?;
+        tmp.write_all(&json)?;
+        tmp.sync_all()?;
+        fs::rename(&tmp_path, &self.path)?;
+        // make the rename itself durable
+        if let Some(dir) = self.path.parent() {
+            if let Ok(dir) = File::open(dir) {
+                dir.sync_all()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn derive_key(password: &str, salt: &[u8]) -> Result<[u8; 32], Error> {
+    let mut key = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(password.as_bytes(), salt, &mut key)
+        .map_err(|error| Error::Vault(error.to_string()))?;
+    Ok(key)
+}
+
+fn decode_array<const N: usize>(value: &str) -> Result<[u8; N], Error> {
+    let bytes = hex::decode(value).map_err(|error| Error::Vault(error.to_string()))?;
+    let mut result = [0u8; N];
+    if bytes.len() != N {
+        return Err(Error::Vault(format!(
+            "expected {} bytes, got {}",
+            N,
+            bytes.len()
+        )));
+    }
+    result.copy_from_slice(&bytes);
+    Ok(result)
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or_default()
+}
@@ -0,0 +1,222 @@
+use casper_types::{
+    account::AccountHash, ApiError, ContractHash, ContractPackageHash, Key, RuntimeArgs, U256, U512,
+};
+use serde_json::json;
+use swap_client::{
+    args::{self, Asset},
+    constants, contract_error, hashlock, parse_execution_error, query, salted_hashlock,
+    ContractError, Initiate, TokenContract, Unlock,
+};
+use swap_core::batch::Initiation;
+
+fn receiver() -> Key {
+    Key::Account(AccountHash::new([2u8; 32]))
+}
+
+fn get<T: casper_types::CLTyped + casper_types::bytesrepr::FromBytes>(
+    args: &RuntimeArgs,
+    name: &str,
+) -> T {
+    args.get(name).unwrap().clone().into_t().unwrap()
+}
+
+#[test]
+fn hashlock_matches_contract() {
+    assert_eq!(
+        hashlock("sha3-256", "abc").unwrap(),
+        swap_core::hash::generate_hash("sha3-256", "abc")
+    );
+    assert!(hashlock("md5", "abc").is_err());
+}
+
+#[test]
+fn initiate_args() {
+    let initiate = Initiate::new(
+        Asset::Direct {
+            amount: U512::from(5_000_000_000u64),
+        },
+        receiver(),
+        60_000,
+    );
+    assert!(initiate.build().is_err());
+
+    let args = initiate
+        .with_secret("Keccak256", "abc")
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(
+        get::<String>(&args, constants::ARG_HASH),
+        hashlock("Keccak256", "abc").unwrap()
+    );
+    assert_eq!(get::<Key>(&args, constants::ARG_RECEIVER), receiver());
+    assert_eq!(get::<u64>(&args, constants::ARG_TIMEOUT), 60_000);
+    assert_eq!(
+        get::<U512>(&args, constants::ARG_AMOUNT),
+        U512::from(5_000_000_000u64)
+    );
+    assert!(args.get(constants::ARG_CONTRACT_HASH).is_none());
+    assert!(args.get(constants::ARG_SALTED).is_none());
+    assert!(args.get(constants::ARG_SECRET_HASH).is_none());
+}
+
+#[test]
+fn salted_initiate_args() {
+    let package_hash = ContractPackageHash::new([4u8; 32]);
+    let asset = Asset::Direct {
+        amount: U512::from(5_000_000_000u64),
+    };
+    let args = Initiate::new(asset, receiver(), 60_000)
+        .with_salted_secret("sha3-256", "abc", package_hash, 3)
+        .unwrap()
+        .build()
+        .unwrap();
+    assert!(get::<bool>(&args, constants::ARG_SALTED));
+    let hash = get::<String>(&args, constants::ARG_HASH);
+    assert_eq!(
+        hash,
+        hashlock(
+            "sha3-256",
+            &swap_core::hash::salted_secret("abc", package_hash, 3)
+        )
+        .unwrap()
+    );
+    assert_ne!(hash, hashlock("sha3-256", "abc").unwrap());
+    assert_ne!(
+        hash,
+        salted_hashlock("sha3-256", "abc", package_hash, 4).unwrap()
+    );
+    // the contract claims the hashlock of the bare secret too
+    assert_eq!(
+        get::<String>(&args, constants::ARG_SECRET_HASH),
+        hashlock("sha3-256", "abc").unwrap()
+    );
+}
+
+#[test]
+fn token_contract_args() {
+    let contract_hash = ContractHash::new([3u8; 32]);
+    let asset = Asset::Erc20 {
+        token_contract: TokenContract::Contract(contract_hash),
+        amount: U256::from(10u64),
+    };
+    let args = Initiate::new(asset, receiver(), 60_000)
+        .with_secret("sha3-256", "abc")
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(
+        get::<ContractHash>(&args, constants::ARG_CONTRACT_HASH),
+        contract_hash
+    );
+    assert!(args.get(constants::ARG_CONTRACT_PACKAGE_HASH).is_none());
+
+    let package_hash = ContractPackageHash::new([4u8; 32]);
+    let args = args::remove_token(TokenContract::Package(package_hash));
+    assert_eq!(
+        get::<ContractPackageHash>(&args, constants::ARG_CONTRACT_PACKAGE_HASH),
+        package_hash
+    );
+    assert!(args.get(constants::ARG_CONTRACT_HASH).is_none());
+}
+
+#[test]
+fn unlock_args() {
+    let args = Unlock::new("abc").build().unwrap();
+    assert_eq!(get::<String>(&args, constants::ARG_SECRET), "abc");
+    assert!(args.get(constants::ARG_SIGNATURE).is_none());
+}
+
+#[test]
+fn batch_args() {
+    let initiate = Initiate::new(
+        Asset::Direct {
+            amount: U512::from(5_000_000_000u64),
+        },
+        receiver(),
+        60_000,
+    )
+    .with_secret("sha3-256", "abc")
+    .unwrap();
+    let contract_hash = ContractHash::new([1u8; 32]);
+    let args = args::initiate_batch(&[(contract_hash, initiate.clone())]).unwrap();
+    assert_eq!(
+        get::<String>(&args, constants::ARG_BATCH),
+        constants::BATCH_INITIATE
+    );
+    let initiations: Vec<Initiation> = get(&args, constants::ARG_INITIATIONS);
+    assert_eq!(initiations[0].contract_hash, contract_hash);
+    assert_eq!(initiations[0].args, initiate.build().unwrap());
+    // a swap without a hashlock fails the whole batch
+    let unhashed = Initiate::new(
+        Asset::Direct {
+            amount: U512::one(),
+        },
+        receiver(),
+        60_000,
+    );
+    assert!(args::initiate_batch(&[(contract_hash, unhashed)]).is_err());
+}
+
+#[test]
+fn decode_contract_errors() {
+    assert_eq!(
+        contract_error(ApiError::User(19)),
+        Some(ContractError::HashMismatch)
+    );
+    assert_eq!(contract_error(ApiError::User(9999)), None);
+    assert_eq!(contract_error(ApiError::MissingArgument), None);
+    assert_eq!(
+        parse_execution_error("User error: 15"),
+        Some(ContractError::EndTimePassed)
+    );
+    assert_eq!(
+        parse_execution_error("ApiError::User(6) [65542]"),
+        Some(ContractError::AccessDenied)
+    );
+    assert_eq!(parse_execution_error("Out of gas error"), None);
+}
+
+#[test]
+fn decode_stored_value() {
+    let stored_value = json!({
+        "CLValue": { "cl_type": "U64", "bytes": "2a00000000000000", "parsed": 42 }
+    });
+    assert_eq!(query::decode::<u64>(&stored_value).unwrap(), 42);
+    assert!(query::decode::<u64>(&json!({ "Account": {} })).is_err());
+}
+
+#[test]
+fn decode_token_contracts() {
+    let contract = json!({
+        "Contract": {
+            "entry_points": [
+                { "name": "owner_of", "args": [] },
+                { "name": "transfer", "args": [] },
+            ]
+        }
+    });
+    assert_eq!(
+        query::entry_point_names(&contract).unwrap(),
+        vec!["owner_of", "transfer"]
+    );
+    let version = |n: u8| {
+        json!({
+            "protocol_version_major": 1,
+            "contract_version": n,
+            "contract_hash": format!("contract-{}", hex::encode([n; 32])),
+        })
+    };
+    let package = json!({
+        "ContractPackage": {
+            "versions": [version(1), version(2), version(3)],
+            "disabled_versions": [{ "protocol_version_major": 1, "contract_version": 3 }],
+        }
+    });
+    assert_eq!(
+        query::latest_version(&package).unwrap(),
+        ContractHash::new([2u8; 32])
+    );
+    assert!(query::entry_point_names(&package).is_err());
+    assert!(query::latest_version(&contract).is_err());
+}
@@ -0,0 +1,87 @@
+use casper_types::ContractPackageHash;
+use swap_client::{
+    derivation::{recover, DerivationContext, OnChainSwap, Seed},
+    hashlock, salted_hashlock, Salt,
+};
+
+fn seed() -> Seed {
+    Seed::from_hex("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f").unwrap()
+}
+
+fn context(counterparty: &str) -> DerivationContext {
+    DerivationContext {
+        counterparty: counterparty.to_string(),
+        chain: "casper-test".to_string(),
+    }
+}
+
+#[test]
+fn derivation_is_deterministic() {
+    let seed = seed();
+    let alice = context("account-hash-aa");
+    assert_eq!(seed.derive_secret(0, &alice), seed.derive_secret(0, &alice));
+    assert_ne!(seed.derive_secret(0, &alice), seed.derive_secret(1, &alice));
+    assert_ne!(
+        seed.derive_secret(0, &alice),
+        seed.derive_secret(0, &context("account-hash-bb"))
+    );
+    assert_eq!(seed.derive_secret(0, &alice).len(), 64);
+    assert!(Seed::from_hex("0011").is_err());
+    assert_eq!(Seed::from_hex(&seed.to_hex()).unwrap(), seed);
+}
+
+#[test]
+fn recover_matches_on_chain_hashlocks() {
+    let seed = seed();
+    let ours = OnChainSwap {
+        contract_hash: "hash-01".to_string(),
+        swap_id: 1,
+        hash_type: "Keccak256".to_string(),
+        hashlock: hashlock(
+            "Keccak256",
+            &seed.derive_secret(7, &context("account-hash-aa")),
+        )
+        .unwrap(),
+        salt: None,
+        context: context("account-hash-aa"),
+    };
+    let theirs = OnChainSwap {
+        contract_hash: "hash-02".to_string(),
+        swap_id: 1,
+        hash_type: "Keccak256".to_string(),
+        hashlock: hashlock("Keccak256", "someone else's secret").unwrap(),
+        salt: None,
+        context: context("account-hash-aa"),
+    };
+
+    let recovered = recover(&seed, &[ours.clone(), theirs], 20);
+    assert_eq!(recovered.len(), 1);
+    assert_eq!(recovered[0].swap, ours);
+    assert_eq!(recovered[0].index, 7);
+    // outside of the gap limit
+    assert!(recover(&seed, &[ours], 5).is_empty());
+}
+
+#[test]
+fn recover_matches_salted_hashlocks() {
+    let seed = seed();
+    let secret = seed.derive_secret(3, &context("account-hash-aa"));
+    let contract_package_hash = ContractPackageHash::new([9u8; 32]);
+    let mut swap = OnChainSwap {
+        contract_hash: "hash-01".to_string(),
+        swap_id: 4,
+        hash_type: "sha3-256".to_string(),
+        hashlock: salted_hashlock("sha3-256", &secret, contract_package_hash, 4).unwrap(),
+        salt: Some(Salt {
+            contract_package_hash,
+            swap_id: 4,
+        }),
+        context: context("account-hash-aa"),
+    };
+    let recovered = recover(&seed, &[swap.clone()], 20);
+    assert_eq!(recovered.len(), 1);
+    assert_eq!(recovered[0].secret, secret);
+    // the bare secret doesn't hash to a salted hashlock
+    swap.salt = None;
+    assert!(recover(&seed, &[swap], 20).is_empty());
+}
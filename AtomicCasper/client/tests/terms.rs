@@ -0,0 +1,147 @@
+use casper_types::{account::AccountHash, Key, PublicKey, SecretKey, U256, U512};
+use swap_client::{
+    args::Asset, constants, hashlock, terms::LegTerms, Error, Initiate, SignedTerms, SwapTerms,
+};
+
+fn initiator_key() -> SecretKey {
+    SecretKey::ed25519_from_bytes([1u8; 32]).unwrap()
+}
+
+fn participant_key() -> SecretKey {
+    SecretKey::secp256k1_from_bytes([2u8; 32]).unwrap()
+}
+
+fn terms() -> SwapTerms {
+    SwapTerms {
+        version: 1,
+        hash_type: "sha3-256".to_string(),
+        hashlock: hashlock("sha3-256", "secret").unwrap(),
+        initiator: LegTerms {
+            chain: "casper-test".to_string(),
+            asset: "CSPR".to_string(),
+            amount: U256::from(500_000_000_000u64),
+            token_ids: Vec::new(),
+            sender: "account-hash-01".to_string(),
+            receiver: "account-hash-02".to_string(),
+            end_time: 2_000_000,
+        },
+        participant: LegTerms {
+            chain: "ethereum".to_string(),
+            asset: "0xdac17f958d2ee523a2206206994597c13d831ec7".to_string(),
+            amount: U256::from(100_000_000u64),
+            token_ids: Vec::new(),
+            sender: "0x02".to_string(),
+            receiver: "0x01".to_string(),
+            end_time: 1_000_000,
+        },
+        initiator_key: PublicKey::from(&initiator_key()),
+        participant_key: PublicKey::from(&participant_key()),
+    }
+}
+
+fn signed() -> SignedTerms {
+    let mut signed = SignedTerms::new(terms());
+    signed.sign(&initiator_key()).unwrap();
+    signed.sign(&participant_key()).unwrap();
+    signed
+}
+
+#[test]
+fn hash_is_deterministic() {
+    let hash = terms().hash().unwrap();
+    assert_eq!(hash.len(), 64);
+    assert_eq!(hash, terms().hash().unwrap());
+
+    // a JSON round trip keeps the hash
+    let json = serde_json::to_string(&terms()).unwrap();
+    let parsed: SwapTerms = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed.hash().unwrap(), hash);
+
+    // every field counts
+    let mut changed = terms();
+    changed.participant.amount = U256::from(100_000_001u64);
+    assert_ne!(changed.hash().unwrap(), hash);
+    let mut swapped = terms();
+    std::mem::swap(
+        &mut swapped.initiator.sender,
+        &mut swapped.initiator.receiver,
+    );
+    assert_ne!(swapped.hash().unwrap(), hash);
+}
+
+#[test]
+fn both_parties_must_sign() {
+    let mut signed = SignedTerms::new(terms());
+    assert!(signed.verify().is_err());
+    signed.sign(&initiator_key()).unwrap();
+    assert!(signed.verify().is_err());
+    signed.sign(&participant_key()).unwrap();
+    assert_eq!(signed.verify().unwrap(), terms().hash().unwrap());
+
+    let stranger = SecretKey::ed25519_from_bytes([3u8; 32]).unwrap();
+    assert!(matches!(
+        SignedTerms::new(terms()).sign(&stranger),
+        Err(Error::Terms(_))
+    ));
+}
+
+#[test]
+fn signatures_cover_the_terms() {
+    let mut tampered = signed();
+    tampered.terms.initiator.receiver = "account-hash-03".to_string();
+    assert!(matches!(tampered.verify(), Err(Error::Terms(_))));
+
+    let json = serde_json::to_string(&signed()).unwrap();
+    let parsed: SignedTerms = serde_json::from_str(&json).unwrap();
+    assert!(parsed.verify().is_ok());
+}
+
+#[test]
+fn validates_terms() {
+    let mut terms = terms();
+    terms.version = 2;
+    assert!(terms.validate().is_err());
+
+    let mut terms = self::terms();
+    terms.participant.end_time = terms.initiator.end_time;
+    assert!(matches!(terms.validate(), Err(Error::Terms(_))));
+
+    let mut terms = self::terms();
+    terms.hash_type = "md5".to_string();
+    assert!(matches!(
+        terms.validate(),
+        Err(Error::HashTypeNotSupported(_))
+    ));
+}
+
+#[test]
+fn initiate_stores_terms_hash() {
+    let receiver = Key::Account(AccountHash::new([2u8; 32]));
+    let asset = Asset::Direct {
+        amount: U512::from(500_000_000_000u64),
+    };
+    let args = Initiate::new(asset.clone(), receiver, 60_000)
+        .with_terms(&terms())
+        .build()
+        .unwrap();
+    let terms_hash: String = args
+        .get(constants::ARG_TERMS_HASH)
+        .unwrap()
+        .clone()
+        .into_t()
+        .unwrap();
+    assert_eq!(terms_hash, terms().hash().unwrap());
+    let hash: String = args
+        .get(constants::ARG_HASH)
+        .unwrap()
+        .clone()
+        .into_t()
+        .unwrap();
+    assert_eq!(hash, terms().hashlock);
+
+    let other_hashlock = Initiate::new(asset, receiver, 60_000)
+        .with_secret("sha3-256", "another secret")
+        .unwrap()
+        .with_terms(&terms());
+    assert!(matches!(other_hashlock.build(), Err(Error::Terms(_))));
+}
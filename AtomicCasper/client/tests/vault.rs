@@ -0,0 +1,86 @@
+use casper_types::{account::AccountHash, ContractPackageHash, Key, U512};
+use swap_client::{constants, hashlock, Asset, Initiate, Salt, SwapContext, Vault};
+
+fn context() -> SwapContext {
+    SwapContext {
+        local_chain: "casper-test".to_string(),
+        remote_chain: "ethereum".to_string(),
+        local_end_time: Some(1_700_000_000_000),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn secrets_survive_reopening() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("secrets.vault");
+
+    let mut vault = Vault::create(&path, "password").unwrap();
+    let record = vault.new_secret("sha3-256", context()).unwrap();
+    assert_eq!(
+        record.hashlock,
+        hashlock("sha3-256", &record.secret).unwrap()
+    );
+    vault
+        .update(&record.hashlock, |context| context.swap_id = Some(3))
+        .unwrap();
+    drop(vault);
+
+    let vault = Vault::open(&path, "password").unwrap();
+    let stored = vault.find(&record.hashlock).unwrap();
+    assert_eq!(stored.secret, record.secret);
+    assert_eq!(stored.context.swap_id, Some(3));
+    assert_eq!(stored.context.remote_chain, "ethereum");
+}
+
+#[test]
+fn wrong_password() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("secrets.vault");
+    Vault::create(&path, "password").unwrap();
+    assert!(Vault::open(&path, "not the password").is_err());
+    // an existing vault is never overwritten
+    assert!(Vault::create(&path, "password").is_err());
+}
+
+#[test]
+fn secrets_are_not_stored_in_clear() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("secrets.vault");
+    let mut vault = Vault::create(&path, "password").unwrap();
+    let record = vault.new_secret("blake2b", context()).unwrap();
+    let file = std::fs::read_to_string(&path).unwrap();
+    assert!(!file.contains(&record.secret));
+    assert!(vault.insert(&record.secret, "blake2b", context()).is_err());
+}
+
+#[test]
+fn finds_secrets_of_salted_swaps() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("secrets.vault");
+    let mut vault = Vault::create(&path, "password").unwrap();
+    let record = vault.new_secret("sha3-256", context()).unwrap();
+    let salt = Salt {
+        contract_package_hash: ContractPackageHash::new([4u8; 32]),
+        swap_id: 3,
+    };
+    let asset = Asset::Direct {
+        amount: U512::from(5_000_000_000u64),
+    };
+    let receiver = Key::Account(AccountHash::new([2u8; 32]));
+    let args = Initiate::new(asset, receiver, 60_000)
+        .with_salted_record(&record, salt.contract_package_hash, salt.swap_id)
+        .unwrap()
+        .build()
+        .unwrap();
+    let salted: String = args
+        .get(constants::ARG_HASH)
+        .unwrap()
+        .clone()
+        .into_t()
+        .unwrap();
+    assert!(vault.find(&salted).is_none());
+    assert_eq!(vault.find_salted(&salted, &salt), Some(&record));
+    let other_swap = Salt { swap_id: 4, ..salt };
+    assert!(vault.find_salted(&salted, &other_swap).is_none());
+}
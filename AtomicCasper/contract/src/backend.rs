@@ -0,0 +1,50 @@
+use casper_contract::contract_api::{runtime, storage};
+use casper_types::{
+    bytesrepr::{FromBytes, ToBytes},
+    CLTyped, Key,
+};
+use swap_core::{
+    backend::{Runtime, Storage},
+    Error,
+};
+
+use crate::utils;
+
+/// Runs the swap logic of `swap_core` on the contract's own named keys.
+pub struct CasperBackend;
+
+impl Runtime for CasperBackend {
+    fn block_time(&self) -> u64 {
+        runtime::get_blocktime().into()
+    }
+
+    fn caller(&self) -> Key {
+        utils::get_caller_key()
+    }
+}
+
+impl Storage for CasperBackend {
+    fn read<T: CLTyped + FromBytes>(&self, name: &str) -> Result<T, Error> {
+        match runtime::get_key(name) {
+            Some(Key::URef(uref)) => match storage::read(uref) {
+                Ok(Some(value)) => Ok(value),
+                Ok(None) => Err(Error::MissingValue),
+                Err(_error) => Err(Error::StorageError),
+            },
+            _ => Err(Error::MissingKey),
+        }
+    }
+
+    fn write<T: CLTyped + ToBytes>(&mut self, name: &str, value: T) {
+        match runtime::get_key(name) {
+            None => {
+                let key_uref = storage::new_uref(value).into();
+                runtime::put_key(name, key_uref);
+            }
+            Some(_) => {
+                let key_uref = utils::get_named_key_by_name(name);
+                storage::write(key_uref, value);
+            }
+        }
+    }
+}
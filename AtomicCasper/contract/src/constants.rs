@@ -1,45 +1,178 @@
-use alloc::string::ToString;
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec;
 use alloc::vec::Vec;
-use casper_contract::contract_api::storage;
-use casper_types::U512;
+use casper_contract::contract_api::{runtime, storage};
 use casper_types::{
     contracts::{NamedKeys, Parameters},
-    EntryPoint, EntryPoints,
+    CLType, EntryPoint, EntryPointAccess, EntryPoints, Group, Parameter,
 };
+use casper_types::{CLTyped, ContractHash, Key, PublicKey, U512};
+
+use swap_core::{allowlist::Allowlist, fee::Fees, info::SwapInfo, token::TokenContract};
 
 use crate::TokenId;
 
-pub const NAMED_KEY_TOKEN_IDS: &str = "token_ids";
-pub const NAMED_KEY_HASH: &str = "hash";
-pub const NAMED_KEY_HASH_TYPE: &str = "hash_type";
-pub const NAMED_KEY_SECRET: &str = "secret";
-pub const NAMED_KEY_TYPE: &str = "type";
-pub const NAMED_KEY_OWNER: &str = "owner";
-pub const NAMED_KEY_RECIVER: &str = "reciver";
-pub const NAMED_KEY_CONTRACT_HASH: &str = "contract_hash";
-pub const NAMED_KEY_OWN_CONTRACT_PACKAGE_HASH: &str = "own_contract_package_hash";
-pub const NAMED_KEY_AMOUNT: &str = "amount";
-pub const NAMED_KEY_START_TIME: &str = "start_time";
-pub const NAMED_KEY_END_TIME: &str = "end_time";
-pub const NAMED_KEY_PURSE: &str = "purse";
-pub const NAMED_KEY_OWN_CONTRACT_HASH: &str = "own_contract_hash";
+pub use swap_core::constants::*;
 
-pub const ARG_SECRET: &str = "secret";
-pub const ARG_CONTRACT_HASH: &str = "contract_hash";
-pub const ARG_HASH: &str = "hash";
-pub const ARG_HASH_TYPE: &str = "hash_type";
-pub const ARG_TIMEOUT: &str = "timeout";
-pub const ARG_TYPE: &str = "type";
-pub const ARG_AMOUNT: &str = "amount";
-pub const ARG_PURSE: &str = "purse";
-pub const ARG_RECEIVER: &str = "receiver";
-pub const ARG_TOKEN_IDS: &str = "token_ids";
+/// Only the installer runs `init`, through the "constructor" group it creates for the call and
+/// empties right after.
+fn constructor_access() -> EntryPointAccess {
+    EntryPointAccess::Groups(vec![Group::new("constructor")])
+}
 
 pub fn get_entrypoints() -> EntryPoints {
     let mut result = EntryPoints::new();
     result.add_entry_point(EntryPoint::new(
-        "init",
+        ENTRY_POINT_INIT,
+        Parameters::new(),
+        casper_types::CLType::Unit,
+        constructor_access(),
+        casper_types::EntryPointType::Contract,
+    ));
+    result.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_INITIATE,
+        vec![
+            Parameter::new(ARG_HASH, CLType::String),
+            Parameter::new(ARG_HASH_TYPE, CLType::String),
+            Parameter::new(ARG_RECEIVER, CLType::Key),
+            Parameter::new(ARG_TIMEOUT, CLType::U64),
+        ],
+        casper_types::CLType::Unit,
+        casper_types::EntryPointAccess::Public,
+        casper_types::EntryPointType::Contract,
+    ));
+    result.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_UNLOCK,
+        vec![Parameter::new(ARG_SECRET, CLType::String)],
+        casper_types::CLType::Unit,
+        casper_types::EntryPointAccess::Public,
+        casper_types::EntryPointType::Contract,
+    ));
+    result.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_REFUND,
+        Parameters::new(),
+        casper_types::CLType::Unit,
+        casper_types::EntryPointAccess::Public,
+        casper_types::EntryPointType::Contract,
+    ));
+    result.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_CANCEL,
+        Parameters::new(),
+        casper_types::CLType::Unit,
+        casper_types::EntryPointAccess::Public,
+        casper_types::EntryPointType::Contract,
+    ));
+    result.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_PROPOSE_END_TIME,
+        vec![Parameter::new(ARG_END_TIME, CLType::U64)],
+        casper_types::CLType::Unit,
+        casper_types::EntryPointAccess::Public,
+        casper_types::EntryPointType::Contract,
+    ));
+    result.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_ACCEPT_END_TIME,
+        vec![Parameter::new(ARG_END_TIME, CLType::U64)],
+        casper_types::CLType::Unit,
+        casper_types::EntryPointAccess::Public,
+        casper_types::EntryPointType::Contract,
+    ));
+    result.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_GET_HASH,
+        Parameters::new(),
+        casper_types::CLType::String,
+        casper_types::EntryPointAccess::Public,
+        casper_types::EntryPointType::Contract,
+    ));
+    result.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_GET_SWAP,
+        vec![Parameter::new(ARG_SWAP_ID, CLType::U64)],
+        SwapInfo::cl_type(),
+        casper_types::EntryPointAccess::Public,
+        casper_types::EntryPointType::Contract,
+    ));
+    result.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_LIST_SWAPS,
+        vec![
+            Parameter::new(ARG_OFFSET, CLType::U64),
+            Parameter::new(ARG_LIMIT, CLType::U64),
+        ],
+        Vec::<SwapInfo>::cl_type(),
+        casper_types::EntryPointAccess::Public,
+        casper_types::EntryPointType::Contract,
+    ));
+    result.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_DEPOSIT,
+        vec![
+            Parameter::new(ARG_PURSE, CLType::URef),
+            Parameter::new(ARG_AMOUNT, CLType::U512),
+        ],
+        casper_types::CLType::Unit,
+        casper_types::EntryPointAccess::Public,
+        casper_types::EntryPointType::Contract,
+    ));
+    result.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_SET_FEE,
+        vec![
+            Parameter::new(ARG_ASSET_TYPE, CLType::String),
+            Parameter::new(ARG_FEE_BPS, CLType::U32),
+        ],
+        casper_types::CLType::Unit,
+        casper_types::EntryPointAccess::Public,
+        casper_types::EntryPointType::Contract,
+    ));
+    result.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_SET_TREASURY,
+        vec![Parameter::new(ARG_TREASURY, CLType::Key)],
+        casper_types::CLType::Unit,
+        casper_types::EntryPointAccess::Public,
+        casper_types::EntryPointType::Contract,
+    ));
+    result.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_ALLOW_TOKEN,
+        vec![Parameter::new(ARG_STANDARD, CLType::String)],
+        casper_types::CLType::Unit,
+        casper_types::EntryPointAccess::Public,
+        casper_types::EntryPointType::Contract,
+    ));
+    result.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_REMOVE_TOKEN,
+        Parameters::new(),
+        casper_types::CLType::Unit,
+        casper_types::EntryPointAccess::Public,
+        casper_types::EntryPointType::Contract,
+    ));
+    result.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_ENFORCE_ALLOWLIST,
+        vec![Parameter::new(ARG_ENFORCED, CLType::Bool)],
+        casper_types::CLType::Unit,
+        casper_types::EntryPointAccess::Public,
+        casper_types::EntryPointType::Contract,
+    ));
+    result.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_GET_ALLOWLIST,
+        Parameters::new(),
+        Allowlist::cl_type(),
+        casper_types::EntryPointAccess::Public,
+        casper_types::EntryPointType::Contract,
+    ));
+    result.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_GET_FEES,
         Parameters::new(),
+        Fees::cl_type(),
+        casper_types::EntryPointAccess::Public,
+        casper_types::EntryPointType::Contract,
+    ));
+    result.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_SET_RECEIVER_HOOK,
+        vec![Parameter::new(ARG_ENABLED, CLType::Bool)],
+        casper_types::CLType::Unit,
+        casper_types::EntryPointAccess::Public,
+        casper_types::EntryPointType::Contract,
+    ));
+    result.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_WITHDRAW,
+        vec![Parameter::new(ARG_AMOUNT, CLType::U512)],
         casper_types::CLType::Unit,
         casper_types::EntryPointAccess::Public,
         casper_types::EntryPointType::Contract,
@@ -57,6 +190,14 @@ pub fn get_named_keys() -> alloc::collections::BTreeMap<alloc::string::String, c
         NAMED_KEY_HASH_TYPE.to_string(),
         storage::new_uref("".to_string()).into(),
     );
+    named_keys.insert(
+        NAMED_KEY_SALTED.to_string(),
+        storage::new_uref(false).into(),
+    );
+    named_keys.insert(
+        NAMED_KEY_SECRET_HASH.to_string(),
+        storage::new_uref("".to_string()).into(),
+    );
     named_keys.insert(
         NAMED_KEY_SECRET.to_string(),
         storage::new_uref("".to_string()).into(),
@@ -67,16 +208,56 @@ pub fn get_named_keys() -> alloc::collections::BTreeMap<alloc::string::String, c
     );
     named_keys.insert(
         NAMED_KEY_CONTRACT_HASH.to_string(),
-        storage::new_uref("".to_string()).into(),
+        storage::new_uref(None::<TokenContract>).into(),
     );
     named_keys.insert(
         NAMED_KEY_END_TIME.to_string(),
         storage::new_uref(0u64).into(),
     );
+    named_keys.insert(
+        NAMED_KEY_PROPOSED_END_TIME.to_string(),
+        storage::new_uref(0u64).into(),
+    );
     named_keys.insert(
         NAMED_KEY_START_TIME.to_string(),
         storage::new_uref(0u64).into(),
     );
+    named_keys.insert(
+        NAMED_KEY_RECEIVER_PUBLIC_KEY.to_string(),
+        storage::new_uref(None::<PublicKey>).into(),
+    );
+    named_keys.insert(
+        NAMED_KEY_SWAP_ID.to_string(),
+        storage::new_uref(0u64).into(),
+    );
+    named_keys.insert(
+        NAMED_KEY_TERMS_HASH.to_string(),
+        storage::new_uref("".to_string()).into(),
+    );
+    named_keys.insert(
+        NAMED_KEY_LOCKED.to_string(),
+        storage::new_uref(false).into(),
+    );
+    named_keys.insert(
+        NAMED_KEY_TREASURY.to_string(),
+        storage::new_uref(None::<Key>).into(),
+    );
+    named_keys.insert(
+        NAMED_KEY_FEES.to_string(),
+        storage::new_uref(BTreeMap::<String, u32>::new()).into(),
+    );
+    named_keys.insert(
+        NAMED_KEY_FEE_BPS.to_string(),
+        storage::new_uref(0u32).into(),
+    );
+    named_keys.insert(
+        NAMED_KEY_ALLOWLIST.to_string(),
+        storage::new_uref(BTreeMap::<TokenContract, String>::new()).into(),
+    );
+    named_keys.insert(
+        NAMED_KEY_ALLOWLIST_ENFORCED.to_string(),
+        storage::new_uref(false).into(),
+    );
     let empty_vec: Vec<TokenId> = Vec::new();
     named_keys.insert(
         NAMED_KEY_TOKEN_IDS.to_string(),
@@ -84,3 +265,56 @@ pub fn get_named_keys() -> alloc::collections::BTreeMap<alloc::string::String, c
     );
     named_keys
 }
+
+pub fn get_factory_entrypoints() -> EntryPoints {
+    let mut result = EntryPoints::new();
+    result.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_INIT_FACTORY,
+        Parameters::new(),
+        casper_types::CLType::Unit,
+        constructor_access(),
+        casper_types::EntryPointType::Contract,
+    ));
+    result.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_CREATE_SWAP,
+        vec![Parameter::new(ARG_TYPE, CLType::String)],
+        ContractHash::cl_type(),
+        casper_types::EntryPointAccess::Public,
+        casper_types::EntryPointType::Contract,
+    ));
+    result.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_RECORD_SWAP,
+        vec![Parameter::new(ARG_HASH, CLType::String)],
+        casper_types::CLType::Unit,
+        casper_types::EntryPointAccess::Public,
+        casper_types::EntryPointType::Contract,
+    ));
+    result.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_GET_INSTANCES,
+        vec![Parameter::new(ARG_OWNER, CLType::Key)],
+        Vec::<ContractHash>::cl_type(),
+        casper_types::EntryPointAccess::Public,
+        casper_types::EntryPointType::Contract,
+    ));
+    result.add_entry_point(EntryPoint::new(
+        ENTRY_POINT_FIND_SWAP,
+        vec![Parameter::new(ARG_HASH, CLType::String)],
+        ContractHash::cl_type(),
+        casper_types::EntryPointAccess::Public,
+        casper_types::EntryPointType::Contract,
+    ));
+    result
+}
+
+pub fn get_factory_named_keys() -> NamedKeys {
+    let mut named_keys = NamedKeys::new();
+    named_keys.insert(
+        NAMED_KEY_ADMIN.to_string(),
+        storage::new_uref(Key::Account(runtime::get_caller())).into(),
+    );
+    named_keys.insert(
+        NAMED_KEY_INSTANCE_COUNT.to_string(),
+        storage::new_uref(0u64).into(),
+    );
+    named_keys
+}
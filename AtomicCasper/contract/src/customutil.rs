@@ -0,0 +1,36 @@
+use crate::{
+    utils::{call_token_contract, get_contract_package_hash, ToKey},
+    Error,
+};
+use casper_contract::unwrap_or_revert::UnwrapOrRevert;
+use casper_types::{Key, RuntimeArgs, U256};
+use swap_core::token::{self, ReturnValue, TokenContract};
+fn balance_of(custom_contract: TokenContract, address: Key) -> U256 {
+    let mut runtimeargs = RuntimeArgs::new();
+    runtimeargs
+        .insert("address", address)
+        .unwrap_or_revert_with(Error::RuntimeArgFailed);
+    call_token_contract::<U256>(custom_contract, "balance_of", runtimeargs)
+}
+pub fn get_own_contract_balance(custom_contract: TokenContract) -> U256 {
+    balance_of(custom_contract, get_contract_package_hash().to_key())
+}
+/// A Custom token's `transfer` only takes the recipient, and moves all the contract holds of it.
+pub fn transfer_custom_tokens_to(custom_contract: TokenContract, reciver: Key) {
+    let own_key = get_contract_package_hash().to_key();
+    let own_balance = balance_of(custom_contract, own_key);
+    let reciver_balance = balance_of(custom_contract, reciver);
+    let mut runtimeargs = RuntimeArgs::new();
+    runtimeargs
+        .insert("recipient", reciver)
+        .unwrap_or_revert_with(Error::RuntimeArgFailed);
+    let returned = call_token_contract::<ReturnValue>(custom_contract, "transfer", runtimeargs);
+    token::check_transfer_return(&returned).unwrap_or_revert();
+    // don't take the token's word for it
+    token::check_balances_moved(
+        (own_balance, balance_of(custom_contract, own_key)),
+        (reciver_balance, balance_of(custom_contract, reciver)),
+        own_balance,
+    )
+    .unwrap_or_revert();
+}
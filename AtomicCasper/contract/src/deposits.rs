@@ -0,0 +1,26 @@
+use casper_contract::{contract_api::storage, unwrap_or_revert::UnwrapOrRevert};
+use casper_types::{Key, URef, U512};
+use swap_core::ledger;
+
+use crate::{constants, utils::get_named_key_by_name, Error};
+
+fn deposits() -> URef {
+    get_named_key_by_name(constants::NAMED_KEY_DEPOSITS)
+}
+
+/// CSPR `depositor` has in the contract's purse that isn't locked in a swap.
+pub fn balance(depositor: &Key) -> U512 {
+    storage::dictionary_get::<U512>(deposits(), &ledger::item_key(depositor))
+        .unwrap_or_revert_with(Error::StorageError)
+        .unwrap_or_default()
+}
+
+pub fn credit(depositor: &Key, amount: U512) {
+    let balance = ledger::credit(balance(depositor), amount).unwrap_or_revert();
+    storage::dictionary_put(deposits(), &ledger::item_key(depositor), balance);
+}
+
+pub fn debit(depositor: &Key, amount: U512) {
+    let balance = ledger::debit(balance(depositor), amount).unwrap_or_revert();
+    storage::dictionary_put(deposits(), &ledger::item_key(depositor), balance);
+}
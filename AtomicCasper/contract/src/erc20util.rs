@@ -1,25 +1,24 @@
 use crate::{
-    constants,
-    transfers::U512ToU256,
-    utils::{get_contract_package_hash, get_key_val, ToKey},
+    utils::{call_token_contract, get_contract_package_hash, ToKey},
     Error,
 };
-use casper_contract::{contract_api::runtime::call_contract, unwrap_or_revert::UnwrapOrRevert};
-use casper_types::{ContractHash, Key, RuntimeArgs, U256, U512};
-pub fn get_own_contract_balance() -> u64 {
-    let erc20_contract_hash = get_key_val::<ContractHash>(constants::NAMED_KEY_CONTRACT_HASH);
-    let own_contract_hash = get_contract_package_hash();
-    let own_contract_hash_key = own_contract_hash.to_key();
+use casper_contract::unwrap_or_revert::UnwrapOrRevert;
+use casper_types::{Key, RuntimeArgs, U256};
+use swap_core::token::{self, ReturnValue, TokenContract};
+fn balance_of(erc20_contract: TokenContract, address: Key) -> U256 {
     let mut runtimeargs = RuntimeArgs::new();
     runtimeargs
-        .insert("address", own_contract_hash_key)
+        .insert("address", address)
         .unwrap_or_revert_with(Error::RuntimeArgFailed);
-    let ballance = call_contract::<U256>(erc20_contract_hash, "balance_of", runtimeargs);
-    ballance.as_u64()
+    call_token_contract::<U256>(erc20_contract, "balance_of", runtimeargs)
 }
-pub fn transfer_erc20_tokens_to(reciver: Key) {
-    let amount = get_key_val::<U512>(constants::NAMED_KEY_AMOUNT).to_u256();
-    let erc20_contract_hash = get_key_val::<ContractHash>(constants::NAMED_KEY_CONTRACT_HASH);
+pub fn get_own_contract_balance(erc20_contract: TokenContract) -> U256 {
+    balance_of(erc20_contract, get_contract_package_hash().to_key())
+}
+pub fn transfer_erc20_tokens_to(erc20_contract: TokenContract, reciver: Key, amount: U256) {
+    let own_key = get_contract_package_hash().to_key();
+    let own_balance = balance_of(erc20_contract, own_key);
+    let reciver_balance = balance_of(erc20_contract, reciver);
     let mut runtimeargs = RuntimeArgs::new();
     runtimeargs
         .insert("amount", amount)
@@ -27,10 +26,13 @@ pub fn transfer_erc20_tokens_to(reciver: Key) {
     runtimeargs
         .insert("recipient", reciver)
         .unwrap_or_revert_with(Error::RuntimeArgFailed);
-    let _ = call_contract::<()>(erc20_contract_hash, "transfer", runtimeargs);
-}
-
-pub fn transfer_back() {
-    let reciver = get_key_val::<Key>(constants::NAMED_KEY_OWNER);
-    transfer_erc20_tokens_to(reciver);
+    let returned = call_token_contract::<ReturnValue>(erc20_contract, "transfer", runtimeargs);
+    token::check_transfer_return(&returned).unwrap_or_revert();
+    // don't take the token's word for it
+    token::check_balances_moved(
+        (own_balance, balance_of(erc20_contract, own_key)),
+        (reciver_balance, balance_of(erc20_contract, reciver)),
+        amount,
+    )
+    .unwrap_or_revert();
 }
@@ -0,0 +1,91 @@
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+};
+use casper_contract::contract_api::storage;
+use casper_types::{Key, URef, U512};
+
+use crate::utils::get_contract_package_hash;
+
+pub enum SwapEvent {
+    EndTimeProposed {
+        end_time: u64,
+    },
+    EndTimeExtended {
+        old_end_time: u64,
+        new_end_time: u64,
+    },
+    Deposited {
+        depositor: Key,
+        amount: U512,
+    },
+    Withdrawn {
+        depositor: Key,
+        amount: U512,
+    },
+    /// A payout to a contract, credited to it instead of sent.
+    Credited {
+        depositor: Key,
+        amount: U512,
+    },
+    Redeemed {
+        swap_id: u64,
+        receiver: Key,
+        amount: U512,
+        fee: U512,
+    },
+}
+
+impl SwapEvent {
+    fn name(&self) -> &str {
+        match self {
+            SwapEvent::EndTimeProposed { .. } => "end_time_proposed",
+            SwapEvent::EndTimeExtended { .. } => "end_time_extended",
+            SwapEvent::Deposited { .. } => "deposited",
+            SwapEvent::Withdrawn { .. } => "withdrawn",
+            SwapEvent::Credited { .. } => "credited",
+            SwapEvent::Redeemed { .. } => "redeemed",
+        }
+    }
+}
+
+/// Events are written as new urefs holding a string map, like the CEP-47/ERC-20 reference
+/// contracts do, so they can be picked up from the deploy's execution effects.
+pub fn emit(event: &SwapEvent) {
+    let mut param: BTreeMap<String, String> = BTreeMap::new();
+    param.insert(
+        "contract_package_hash".to_string(),
+        get_contract_package_hash().to_string(),
+    );
+    param.insert("event_type".to_string(), event.name().to_string());
+    match event {
+        SwapEvent::EndTimeProposed { end_time } => {
+            param.insert("end_time".to_string(), end_time.to_string());
+        }
+        SwapEvent::EndTimeExtended {
+            old_end_time,
+            new_end_time,
+        } => {
+            param.insert("old_end_time".to_string(), old_end_time.to_string());
+            param.insert("new_end_time".to_string(), new_end_time.to_string());
+        }
+        SwapEvent::Deposited { depositor, amount }
+        | SwapEvent::Withdrawn { depositor, amount }
+        | SwapEvent::Credited { depositor, amount } => {
+            param.insert("depositor".to_string(), depositor.to_formatted_string());
+            param.insert("amount".to_string(), amount.to_string());
+        }
+        SwapEvent::Redeemed {
+            swap_id,
+            receiver,
+            amount,
+            fee,
+        } => {
+            param.insert("swap_id".to_string(), swap_id.to_string());
+            param.insert("receiver".to_string(), receiver.to_formatted_string());
+            param.insert("amount".to_string(), amount.to_string());
+            param.insert("fee".to_string(), fee.to_string());
+        }
+    }
+    let _: URef = storage::new_uref(param);
+}
@@ -0,0 +1,175 @@
+//! The factory: installing `contract.wasm` with the `Factory` type installs a contract that
+//! installs swap contracts on demand and keeps a registry of them, so an owner can find the
+//! contracts it created and anyone can find the contract a hashlock was initiated on.
+use alloc::{collections::BTreeSet, format, string::String, vec, vec::Vec};
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    runtime_args, CLValue, ContractHash, ContractPackageHash, Key, RuntimeArgs, URef,
+};
+use swap_core::registry;
+
+use crate::{
+    constants,
+    utils::{self, get_key_val, get_named_key_by_name, set_key},
+    Error,
+};
+
+fn dictionary(name: &str) -> URef {
+    get_named_key_by_name(name)
+}
+
+fn instances_of(owner: &Key) -> Vec<ContractHash> {
+    storage::dictionary_get::<Vec<ContractHash>>(
+        dictionary(constants::NAMED_KEY_INSTANCES_BY_OWNER),
+        &registry::owner_item_key(owner),
+    )
+    .unwrap_or_revert_with(Error::StorageError)
+    .unwrap_or_default()
+}
+
+#[no_mangle]
+pub extern "C" fn init_factory() {
+    let contract_hash =
+        runtime::get_named_arg::<ContractHash>(constants::NAMED_KEY_OWN_CONTRACT_HASH);
+    runtime::put_key(
+        constants::NAMED_KEY_OWN_CONTRACT_HASH,
+        storage::new_uref(contract_hash).into(),
+    );
+    storage::new_dictionary(constants::NAMED_KEY_INSTANCES).unwrap_or_revert();
+    storage::new_dictionary(constants::NAMED_KEY_INSTANCES_BY_OWNER).unwrap_or_revert();
+    storage::new_dictionary(constants::NAMED_KEY_INSTANCES_BY_HASHLOCK).unwrap_or_revert();
+}
+
+/// Installs a swap contract of the given `type` for the caller. The factory's admin is the admin
+/// of every contract it installs.
+#[no_mangle]
+pub extern "C" fn create_swap() {
+    let type_ = runtime::get_named_arg::<String>(constants::ARG_TYPE);
+    if type_ != "NFT" && type_ != "ERC-20" && type_ != "Direct" && type_ != "Custom" {
+        runtime::revert(Error::TypeNotSupported);
+    }
+    let owner = utils::get_caller_key();
+    let count = get_key_val::<u64>(constants::NAMED_KEY_INSTANCE_COUNT) + 1;
+    set_key(constants::NAMED_KEY_INSTANCE_COUNT, count);
+
+    let factory = get_key_val::<ContractHash>(constants::NAMED_KEY_OWN_CONTRACT_HASH);
+    let (contract_hash, package_hash) = crate::install_swap(
+        type_,
+        get_key_val::<Key>(constants::NAMED_KEY_ADMIN),
+        Some(factory),
+        &format!("atomic_swap_package_{}", count),
+    );
+    storage::dictionary_put(
+        dictionary(constants::NAMED_KEY_INSTANCES),
+        &registry::instance_item_key(package_hash),
+        contract_hash,
+    );
+    let mut instances = instances_of(&owner);
+    instances.push(contract_hash);
+    storage::dictionary_put(
+        dictionary(constants::NAMED_KEY_INSTANCES_BY_OWNER),
+        &registry::owner_item_key(&owner),
+        instances,
+    );
+    runtime::ret(CLValue::from_t(contract_hash).unwrap_or_revert());
+}
+
+/// Called by an instance when a swap is initiated on it, to point the hashlock at the instance.
+#[no_mangle]
+pub extern "C" fn record_swap() {
+    let hash = runtime::get_named_arg::<String>(constants::ARG_HASH);
+    let package_hash = match utils::get_caller_key() {
+        Key::Hash(package_hash) => ContractPackageHash::new(package_hash),
+        _ => runtime::revert(Error::UnknownInstance),
+    };
+    let instance = storage::dictionary_get::<ContractHash>(
+        dictionary(constants::NAMED_KEY_INSTANCES),
+        &registry::instance_item_key(package_hash),
+    )
+    .unwrap_or_revert_with(Error::StorageError)
+    .unwrap_or_revert_with(Error::UnknownInstance);
+    // the secret of a hashlock another instance was locked with may already be public
+    let by_hashlock = dictionary(constants::NAMED_KEY_INSTANCES_BY_HASHLOCK);
+    let item_key = registry::hashlock_item_key(&hash);
+    if storage::dictionary_get::<ContractHash>(by_hashlock, &item_key)
+        .unwrap_or_revert_with(Error::StorageError)
+        .is_some()
+    {
+        runtime::revert(Error::HashlockReused);
+    }
+    storage::dictionary_put(by_hashlock, &item_key, instance);
+}
+
+#[no_mangle]
+pub extern "C" fn get_instances() {
+    let owner = runtime::get_named_arg::<Key>(constants::ARG_OWNER);
+    runtime::ret(CLValue::from_t(instances_of(&owner)).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn find_swap() {
+    let hash = runtime::get_named_arg::<String>(constants::ARG_HASH);
+    let instance = storage::dictionary_get::<ContractHash>(
+        dictionary(constants::NAMED_KEY_INSTANCES_BY_HASHLOCK),
+        &registry::hashlock_item_key(&hash),
+    )
+    .unwrap_or_revert_with(Error::StorageError)
+    .unwrap_or_revert_with(Error::SwapNotFound);
+    runtime::ret(CLValue::from_t(instance).unwrap_or_revert());
+}
+
+/// Records the swap just initiated in the registry of the factory that installed this contract,
+/// if any, under its hashlock and, for a salted swap, the hashlock of the bare secret.
+pub fn record_swap_of_instance() {
+    if let Some(factory) = get_key_val::<Option<ContractHash>>(constants::NAMED_KEY_FACTORY) {
+        let mut hashes = vec![get_key_val::<String>(constants::NAMED_KEY_HASH)];
+        if get_key_val::<bool>(constants::NAMED_KEY_SALTED) {
+            hashes.push(get_key_val::<String>(constants::NAMED_KEY_SECRET_HASH));
+        }
+        for hash in hashes {
+            runtime::call_contract::<()>(
+                factory,
+                constants::ENTRY_POINT_RECORD_SWAP,
+                runtime_args! {
+                    constants::ARG_HASH => hash,
+                },
+            );
+        }
+    }
+}
+
+/// Installs the factory, owned and administered by the account of the deploy.
+pub fn install_factory() {
+    let (contract_hash, _contract_version) = storage::new_contract(
+        constants::get_factory_entrypoints(),
+        Some(constants::get_factory_named_keys()),
+        Some("atomic_swap_factory_package_hash".into()),
+        None,
+    );
+    let package_hash = ContractPackageHash::new(
+        runtime::get_key("atomic_swap_factory_package_hash")
+            .unwrap_or_revert()
+            .into_hash()
+            .unwrap_or_revert(),
+    );
+    let constructor_access: URef =
+        storage::create_contract_user_group(package_hash, "constructor", 1, Default::default())
+            .unwrap_or_revert()
+            .pop()
+            .unwrap_or_revert();
+    let _: () = runtime::call_contract(
+        contract_hash,
+        constants::ENTRY_POINT_INIT_FACTORY,
+        runtime_args! {
+            constants::NAMED_KEY_OWN_CONTRACT_HASH => contract_hash,
+        },
+    );
+    let mut urefs = BTreeSet::new();
+    urefs.insert(constructor_access);
+    storage::remove_contract_user_group_urefs(package_hash, "constructor", urefs)
+        .unwrap_or_revert();
+    runtime::put_key("atomic_swap_factory", contract_hash.into());
+}
@@ -0,0 +1,22 @@
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use swap_core::registry;
+
+use crate::{constants, utils::get_named_key_by_name, Error};
+
+/// Takes `hash` for the swap being initiated. Each hashlock locks one swap of the contract: once
+/// its secret is revealed anyone can open a swap locked with it, and after a refund it may still
+/// have been revealed on the other leg.
+pub fn claim(hash: &str) {
+    let hashlocks = get_named_key_by_name(constants::NAMED_KEY_HASHLOCKS);
+    let item_key = registry::hashlock_item_key(hash);
+    let used = storage::dictionary_get::<bool>(hashlocks, &item_key)
+        .unwrap_or_revert_with(Error::StorageError)
+        .unwrap_or_default();
+    if used {
+        runtime::revert(Error::HashlockReused);
+    }
+    storage::dictionary_put(hashlocks, &item_key, true);
+}
@@ -0,0 +1,32 @@
+use alloc::{string::ToString, vec::Vec};
+use casper_contract::{contract_api::storage, unwrap_or_revert::UnwrapOrRevert};
+use casper_types::URef;
+use swap_core::info::{self, SwapInfo, SwapState};
+
+use crate::{
+    backend::CasperBackend,
+    constants,
+    utils::{get_key_val, get_named_key_by_name},
+    Error,
+};
+
+fn swaps() -> URef {
+    get_named_key_by_name(constants::NAMED_KEY_SWAPS)
+}
+
+/// Writes the current swap to the `swaps` dictionary. Must run before `clear_all`.
+pub fn record(state: SwapState) {
+    let info = SwapInfo::current(&CasperBackend, state).unwrap_or_revert();
+    storage::dictionary_put(swaps(), &info.swap_id.to_string(), info);
+}
+
+pub fn get(swap_id: u64) -> SwapInfo {
+    storage::dictionary_get::<SwapInfo>(swaps(), &swap_id.to_string())
+        .unwrap_or_revert_with(Error::StorageError)
+        .unwrap_or_revert_with(Error::SwapNotFound)
+}
+
+pub fn list(offset: u64, limit: u64) -> Vec<SwapInfo> {
+    let count = get_key_val::<u64>(constants::NAMED_KEY_SWAP_ID);
+    info::page(count, offset, limit).map(get).collect()
+}
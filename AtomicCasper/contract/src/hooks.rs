@@ -0,0 +1,63 @@
+use alloc::string::String;
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{runtime_args, ContractPackageHash, Key, RuntimeArgs, URef, U512};
+use swap_core::ledger;
+
+use crate::{constants, utils::get_named_key_by_name, Error};
+
+fn hooks() -> URef {
+    get_named_key_by_name(constants::NAMED_KEY_HOOKS)
+}
+
+/// Whether the contract `receiver` asked to be called back when a swap pays it.
+pub fn is_registered(receiver: &Key) -> bool {
+    storage::dictionary_get::<bool>(hooks(), &ledger::item_key(receiver))
+        .unwrap_or_revert_with(Error::StorageError)
+        .unwrap_or_default()
+}
+
+/// Checks a swap can pay `receiver`. A contract is paid at the package hash it calls with, the key
+/// `withdraw` debits and `notify` calls back, so it must have called `set_receiver_hook`, enabled
+/// or not, beforehand. A contract hash, or any hash of no contract, would be credited CSPR no one
+/// can ever withdraw.
+pub fn check_receiver(receiver: &Key) {
+    if receiver.into_hash().is_none() {
+        return;
+    }
+    let registered = storage::dictionary_get::<bool>(hooks(), &ledger::item_key(receiver))
+        .unwrap_or_revert_with(Error::StorageError);
+    if registered.is_none() {
+        runtime::revert(Error::ReceiverNotRegistered);
+    }
+}
+
+pub fn set(receiver: &Key, enabled: bool) {
+    storage::dictionary_put(hooks(), &ledger::item_key(receiver), enabled);
+}
+
+/// Calls `on_swap_received` on the latest version of `receiver`'s package if it registered a
+/// hook. A contract receiver is always a package hash, [`check_receiver`] turned any other hash
+/// away before the swap could pay it. A revert of the hook reverts the whole unlock, so a
+/// receiver that can't take the asset shouldn't register one.
+pub fn notify(receiver: Key, swap_id: u64, asset: String, amount: U512) {
+    let package_hash = match receiver {
+        Key::Hash(address) => ContractPackageHash::new(address),
+        _ => return,
+    };
+    if !is_registered(&receiver) {
+        return;
+    }
+    let _: () = runtime::call_versioned_contract(
+        package_hash,
+        None,
+        constants::ENTRY_POINT_ON_SWAP_RECEIVED,
+        runtime_args! {
+            constants::ARG_SWAP_ID => swap_id,
+            constants::ARG_ASSET => asset,
+            constants::ARG_AMOUNT => amount,
+        },
+    );
+}
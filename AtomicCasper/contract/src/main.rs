@@ -1,7 +1,15 @@
 #![no_std]
 #![no_main]
+mod backend;
 pub mod constants;
+mod customutil;
+mod deposits;
 mod erc20util;
+mod events;
+mod factory;
+mod hashlocks;
+mod history;
+mod hooks;
 mod native_util;
 mod nftutil;
 mod transfers;
@@ -16,64 +24,32 @@ use alloc::{
     string::{String, ToString},
     vec::Vec,
 };
+use backend::CasperBackend;
 use casper_contract::{
     contract_api::{
         runtime::{self, get_caller},
         storage,
-        system::{create_purse, get_purse_balance},
+        system::{create_purse, transfer_from_purse_to_purse},
     },
     unwrap_or_revert::UnwrapOrRevert,
 };
 use casper_types::{
-    runtime_args, AccessRights, ApiError, CLValue, ContractHash, ContractPackageHash, Key,
-    RuntimeArgs, URef, U256, U512,
+    runtime_args, CLValue, ContractHash, ContractPackageHash, Key, PublicKey, RuntimeArgs,
+    Signature, URef, U256, U512,
 };
 use constants::{get_entrypoints, get_named_keys};
-use utils::{
-    check_hash_type, check_ownership, generate_hash, get_current_time, get_key_val, is_timed_out,
-    set_end_time, set_key, set_start_time, ToKey,
+use events::SwapEvent;
+use swap_core::{
+    allowlist::{self, Allowlist},
+    convert::U256ToU512,
+    fee::{self, Fees},
+    guard,
+    info::SwapState,
+    swap,
 };
-/// An error enum which can be converted to a `u16` so it can be returned as an `ApiError::User`.
-#[repr(u16)]
-pub enum Error {
-    StartTimeNotSet = 0,
-    StartTimeReadError = 1,
-    EndTimeNotSet = 2,
-    EndTimeReadError = 3,
-    HashNotSet = 4,
-    HashReadError = 5,
-    AccessDenied = 6,
-    TypeNotSupported = 7,
-    ContractHashNotSet = 8,
-    ContractHashReadError = 9,
-    NotEnoughBalance = 10,
-    PurseNotSet = 11,
-    PurseReadError = 12,
-    KeyNotFound = 13,
-    EndTimeNotReached = 14,
-    EndTimePassed = 15,
-    HashTypeNotSupported = 16,
-    MissingValue = 17,
-    MissingKey = 18,
-    HashMismatch = 19,
-    TypeNotFound = 20,
-    UnexpectedKeyVariant = 21,
-    StorageError = 22,
-    InvalidContext = 23,
-    ContractAlreadyInitialized = 24,
-    NFTsNotOwnedByContract = 25,
-    ReciverNotAnAccount = 26,
-    OwnerNotSet = 27,
-    OwnerReadError = 28,
-    NativeTransferFailed = 29,
-    RuntimeArgFailed = 30,
-}
-impl From<Error> for ApiError {
-    fn from(error: Error) -> Self {
-        ApiError::User(error as u16)
-    }
-}
-pub type TokenId = U256;
+pub use swap_core::{Error, TokenId};
+use transfers::Payout;
+use utils::{get_key_val, get_optional_named_arg, set_key, ToKey};
 
 #[no_mangle]
 pub extern "C" fn get_hash() {
@@ -81,72 +57,161 @@ pub extern "C" fn get_hash() {
     runtime::ret(CLValue::from_t(hash).unwrap_or_revert());
 }
 
+#[no_mangle]
+pub extern "C" fn get_swap() {
+    let swap_id = runtime::get_named_arg::<u64>(constants::ARG_SWAP_ID);
+    runtime::ret(CLValue::from_t(history::get(swap_id)).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn list_swaps() {
+    let offset = runtime::get_named_arg::<u64>(constants::ARG_OFFSET);
+    let limit = runtime::get_named_arg::<u64>(constants::ARG_LIMIT);
+    runtime::ret(CLValue::from_t(history::list(offset, limit)).unwrap_or_revert());
+}
+
 #[no_mangle]
 pub extern "C" fn refund() {
-    if !check_ownership() {
-        runtime::revert(Error::AccessDenied);
-    }
-    if !is_timed_out() {
-        runtime::revert(Error::EndTimeNotReached);
-    }
-    transfers::transfer_back();
-    utils::clear_all();
+    guard::enter(&mut CasperBackend).unwrap_or_revert();
+    swap::check_refund(&CasperBackend).unwrap_or_revert();
+    close(SwapState::Refunded).send(get_key_val::<Key>(constants::NAMED_KEY_OWNER));
+    guard::exit(&mut CasperBackend);
+}
+
+#[no_mangle]
+pub extern "C" fn cancel() {
+    // only the receiver can agree to release the funds back to the owner before the timeout
+    guard::enter(&mut CasperBackend).unwrap_or_revert();
+    swap::check_cancel(&CasperBackend).unwrap_or_revert();
+    close(SwapState::Cancelled).send(get_key_val::<Key>(constants::NAMED_KEY_OWNER));
+    guard::exit(&mut CasperBackend);
+}
+
+#[no_mangle]
+pub extern "C" fn propose_end_time() {
+    // only the owner can ask for more time, and the deadline can only be pushed back
+    guard::enter(&mut CasperBackend).unwrap_or_revert();
+    let end_time = runtime::get_named_arg::<u64>(constants::ARG_END_TIME);
+    swap::propose_end_time(&mut CasperBackend, end_time).unwrap_or_revert();
+    events::emit(&SwapEvent::EndTimeProposed { end_time });
+    guard::exit(&mut CasperBackend);
+}
+
+#[no_mangle]
+pub extern "C" fn accept_end_time() {
+    // only the receiver can accept the new deadline proposed by the owner
+    guard::enter(&mut CasperBackend).unwrap_or_revert();
+    let end_time = runtime::get_named_arg::<u64>(constants::ARG_END_TIME);
+    let old_end_time = swap::accept_end_time(&mut CasperBackend, end_time).unwrap_or_revert();
+    events::emit(&SwapEvent::EndTimeExtended {
+        old_end_time,
+        new_end_time: end_time,
+    });
+    history::record(SwapState::Open);
+    guard::exit(&mut CasperBackend);
 }
 
 #[no_mangle]
 pub extern "C" fn unlock() {
+    guard::enter(&mut CasperBackend).unwrap_or_revert();
     let secret = runtime::get_named_arg::<String>(constants::ARG_SECRET);
-    if check_ownership() {
-        runtime::revert(Error::AccessDenied);
+    swap::check_unlock(&CasperBackend, secret.as_str()).unwrap_or_revert();
+    // batches name the swap they redeem
+    if let Some(swap_id) = get_optional_named_arg::<u64>(constants::ARG_SWAP_ID) {
+        swap::check_swap_id(&CasperBackend, swap_id).unwrap_or_revert();
     }
-    if is_timed_out() {
-        runtime::revert(Error::EndTimePassed);
-    }
-    let hash_type = get_key_val::<String>(constants::NAMED_KEY_HASH_TYPE);
-    let _secret_hash = generate_hash(hash_type.as_str(), secret.as_str());
-    transfers::transfer_to(_secret_hash.as_str());
-    utils::clear_all();
+    // If the receiver registered a public key, the payout key must be signed by it. This lets the
+    // receiver redirect the payout to a fresh key, or hand the signature to a relayer.
+    let reciver = match utils::get_receiver_public_key() {
+        Some(public_key) => {
+            let payout = runtime::get_named_arg::<Key>(constants::ARG_PAYOUT);
+            let signature = get_optional_named_arg::<Signature>(constants::ARG_SIGNATURE)
+                .unwrap_or_revert_with(Error::SignatureRequired);
+            swap::verify_redemption(
+                &CasperBackend,
+                &public_key,
+                secret.as_str(),
+                payout,
+                &signature,
+            )
+            .unwrap_or_revert();
+            hooks::check_receiver(&payout);
+            payout
+        }
+        None => get_key_val::<Key>(constants::NAMED_KEY_RECIVER),
+    };
+    let swap_id = get_key_val::<u64>(constants::NAMED_KEY_SWAP_ID);
+    let asset = get_key_val::<String>(constants::NAMED_KEY_TYPE);
+    let (amount, fee) = close(SwapState::Redeemed).redeem(reciver);
+    events::emit(&SwapEvent::Redeemed {
+        swap_id,
+        receiver: reciver,
+        amount,
+        fee,
+    });
+    guard::exit(&mut CasperBackend);
+    // the swap is closed and the lock released, so the hook can call back into the contract,
+    // e.g. to withdraw what it was credited
+    hooks::notify(reciver, swap_id, asset, amount);
 }
 
 #[no_mangle]
 pub extern "C" fn initiate() {
-    // only the owner can initiate or reset the contract
-    if !check_ownership() {
-        runtime::revert(Error::AccessDenied);
-    }
-    // If the hash is already set, then the contract is already initialized, so we should revert
-    if utils::already_initialized() {
-        runtime::revert(Error::ContractAlreadyInitialized);
-    }
+    guard::enter(&mut CasperBackend).unwrap_or_revert();
     // set hash and hash_type :
     let hash = runtime::get_named_arg::<String>(constants::ARG_HASH);
     let hash_type = runtime::get_named_arg::<String>(constants::ARG_HASH_TYPE);
-    if !check_hash_type(hash_type.as_str()) {
-        runtime::revert(Error::HashTypeNotSupported);
-    }
+    // only the owner can initiate, and only if the contract isn't already initialized
+    swap::check_initiate(&CasperBackend, hash_type.as_str()).unwrap_or_revert();
+    hashlocks::claim(&hash);
     set_key(constants::NAMED_KEY_HASH, hash);
     set_key(constants::NAMED_KEY_HASH_TYPE, hash_type);
+    // Optionally lock with the hash of the salted secret, bound to this contract and swap id. The
+    // hashlock of the bare secret is claimed too, `unlock` takes the bare secret and salts it
+    // with public data, so the secret must lock no other swap of the contract either
+    let salted = get_optional_named_arg::<bool>(constants::ARG_SALTED).unwrap_or_default();
+    let secret_hash = if salted {
+        get_optional_named_arg::<String>(constants::ARG_SECRET_HASH)
+            .unwrap_or_revert_with(Error::SecretHashRequired)
+    } else {
+        String::new()
+    };
+    if salted {
+        hashlocks::claim(&secret_hash);
+    }
+    set_key(constants::NAMED_KEY_SALTED, salted);
+    set_key(constants::NAMED_KEY_SECRET_HASH, secret_hash);
 
     // Get the recipient
     let reciver = runtime::get_named_arg::<Key>(constants::ARG_RECEIVER);
+    hooks::check_receiver(&reciver);
     set_key(constants::NAMED_KEY_RECIVER, reciver);
+    // Optionally require the receiver's signature to unlock
+    let reciver_public_key =
+        get_optional_named_arg::<PublicKey>(constants::ARG_RECEIVER_PUBLIC_KEY);
+    set_key(constants::NAMED_KEY_RECEIVER_PUBLIC_KEY, reciver_public_key);
+    // Optionally bind the swap to the terms both parties signed off-chain
+    let terms_hash =
+        get_optional_named_arg::<String>(constants::ARG_TERMS_HASH).unwrap_or_default();
+    swap::check_terms_hash(&terms_hash).unwrap_or_revert();
+    set_key(constants::NAMED_KEY_TERMS_HASH, terms_hash);
 
-    {
-        // Get timeout and set start_time and end_time
-        let timeout = runtime::get_named_arg::<u64>(constants::ARG_TIMEOUT);
-        let current_time = get_current_time();
-        set_start_time(current_time);
-        set_end_time(current_time + timeout);
-    }
+    swap::next_swap_id(&mut CasperBackend).unwrap_or_revert();
+
+    // Get timeout and set start_time and end_time
+    let timeout = runtime::get_named_arg::<u64>(constants::ARG_TIMEOUT);
+    swap::start(&mut CasperBackend, timeout).unwrap_or_revert();
 
     let type_ = get_key_val::<String>(constants::NAMED_KEY_TYPE);
     if type_ != "NFT" && type_ != "ERC-20" && type_ != "Direct" && type_ != "Custom" {
         runtime::revert(Error::TypeNotSupported);
     }
-    // for NFT and ERC20 and Custom, we need to set the other contract hash
+    // for NFT and ERC20 and Custom, we need to set the token contract, the ownership and
+    // balance checks below also fail if it doesn't exist or lacks the entry points
     if type_.as_str() != "Direct" {
-        let contract_hash = runtime::get_named_arg::<String>(constants::ARG_CONTRACT_HASH);
-        set_key(constants::NAMED_KEY_CONTRACT_HASH, contract_hash);
+        let token_contract = utils::get_token_contract_arg();
+        allowlist::check_token(&CasperBackend, &type_, &token_contract).unwrap_or_revert();
+        set_key(constants::NAMED_KEY_CONTRACT_HASH, Some(token_contract));
     }
     match type_.as_str() {
         "NFT" => {
@@ -165,62 +230,159 @@ pub extern "C" fn initiate() {
             let token_ids = runtime::get_named_arg::<Vec<TokenId>>(constants::ARG_TOKEN_IDS);
             set_key(constants::NAMED_KEY_TOKEN_IDS, token_ids.clone());
             // Check that the given token_ids are owned by our contract
-            if !nftutil::check_nfts_ownership(token_ids) {
+            if !nftutil::check_nfts_ownership(transfers::get_token_contract(), token_ids) {
                 runtime::revert(Error::NFTsNotOwnedByContract);
             }
         }
         "ERC-20" => {
             // Check if the contract has enough balance
-            let contract_own_balance = erc20util::get_own_contract_balance();
+            let contract_own_balance =
+                erc20util::get_own_contract_balance(transfers::get_token_contract());
             let amount = runtime::get_named_arg::<U256>(constants::ARG_AMOUNT);
-            if contract_own_balance < amount.as_u64() {
+            if contract_own_balance < amount {
                 runtime::revert(Error::NotEnoughBalance);
             }
+            // the payout is checked against this amount
+            set_key(constants::NAMED_KEY_AMOUNT, amount.to_u512());
         }
         "Direct" => {
+            // lock part of what the owner deposited, the rest can still be withdrawn
             let amount = runtime::get_named_arg::<U512>(constants::ARG_AMOUNT);
-            // Get the deposit purse from contract
-            let deposit_uref = utils::get_named_key_by_name(constants::NAMED_KEY_PURSE);
-            let deposit: URef = storage::read(deposit_uref)
-                .unwrap_or_revert_with(Error::PurseNotSet)
-                .unwrap_or_revert_with(Error::PurseReadError);
-            // Read the balance of the deposit purse
-            let balance = get_purse_balance(deposit);
-            // Check if the balance exists
-            if balance.is_none() {
-                runtime::revert(Error::PurseNotSet);
-            }
-            // Check if the balance is enough
-            let balance = balance.unwrap();
-            let dif = balance.checked_sub(amount);
-            if dif.is_none() {
-                runtime::revert(Error::NotEnoughBalance);
-            }
-            let dif = dif.unwrap();
-            if dif.as_u64() < 2500000000 {
-                // revert if the balance is less than 2.5 CSPR
-                runtime::revert(Error::NotEnoughBalance);
-            }
+            deposits::debit(&utils::get_caller_key(), amount);
             set_key(constants::NAMED_KEY_AMOUNT, amount);
         }
         "Custom" => {
-            //TODO
+            // Check the token exists and the contract holds some of it
+            if customutil::get_own_contract_balance(transfers::get_token_contract()).is_zero() {
+                runtime::revert(Error::NotEnoughBalance);
+            }
         }
         _ => {
             runtime::revert(Error::TypeNotSupported);
         }
     }
+    // the swap pays the rate of the time it was initiated
+    set_key(
+        constants::NAMED_KEY_FEE_BPS,
+        fee::rate(&CasperBackend, &type_).unwrap_or_revert(),
+    );
+    history::record(SwapState::Open);
+    factory::record_swap_of_instance();
+    guard::exit(&mut CasperBackend);
 }
 
 #[no_mangle]
-pub extern "C" fn get_deposit_purse() {
-    if !check_ownership() {
-        runtime::revert(Error::AccessDenied);
+pub extern "C" fn set_fee() {
+    // only the admin sets the rate of an asset type, up to fee::MAX_FEE_BPS
+    guard::enter(&mut CasperBackend).unwrap_or_revert();
+    let asset_type = runtime::get_named_arg::<String>(constants::ARG_ASSET_TYPE);
+    let fee_bps = runtime::get_named_arg::<u32>(constants::ARG_FEE_BPS);
+    fee::set_rate(&mut CasperBackend, &asset_type, fee_bps).unwrap_or_revert();
+    guard::exit(&mut CasperBackend);
+}
+
+#[no_mangle]
+pub extern "C" fn set_treasury() {
+    guard::enter(&mut CasperBackend).unwrap_or_revert();
+    let treasury = runtime::get_named_arg::<Key>(constants::ARG_TREASURY);
+    fee::set_treasury(&mut CasperBackend, treasury).unwrap_or_revert();
+    hooks::check_receiver(&treasury);
+    guard::exit(&mut CasperBackend);
+}
+
+#[no_mangle]
+pub extern "C" fn get_fees() {
+    let fees = Fees::current(&CasperBackend).unwrap_or_revert();
+    runtime::ret(CLValue::from_t(fees).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn allow_token() {
+    // only the admin vouches for token contracts, see swap_core::allowlist
+    guard::enter(&mut CasperBackend).unwrap_or_revert();
+    let token = utils::get_token_contract_arg();
+    let standard = runtime::get_named_arg::<String>(constants::ARG_STANDARD);
+    allowlist::allow(&mut CasperBackend, token, standard).unwrap_or_revert();
+    guard::exit(&mut CasperBackend);
+}
+
+#[no_mangle]
+pub extern "C" fn remove_token() {
+    guard::enter(&mut CasperBackend).unwrap_or_revert();
+    let token = utils::get_token_contract_arg();
+    allowlist::remove(&mut CasperBackend, token).unwrap_or_revert();
+    guard::exit(&mut CasperBackend);
+}
+
+#[no_mangle]
+pub extern "C" fn enforce_allowlist() {
+    guard::enter(&mut CasperBackend).unwrap_or_revert();
+    let enforced = runtime::get_named_arg::<bool>(constants::ARG_ENFORCED);
+    allowlist::enforce(&mut CasperBackend, enforced).unwrap_or_revert();
+    guard::exit(&mut CasperBackend);
+}
+
+#[no_mangle]
+pub extern "C" fn get_allowlist() {
+    let allowlist = Allowlist::current(&CasperBackend).unwrap_or_revert();
+    runtime::ret(CLValue::from_t(allowlist).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn deposit() {
+    // The caller passes a purse of its own, best a fresh one holding just `amount` (see the
+    // session crate), so the contract's purse never has to leave the contract.
+    guard::enter(&mut CasperBackend).unwrap_or_revert();
+    let purse = runtime::get_named_arg::<URef>(constants::ARG_PURSE);
+    let amount = runtime::get_named_arg::<U512>(constants::ARG_AMOUNT);
+    let depositor = utils::get_caller_key();
+    deposits::credit(&depositor, amount);
+    transfer_from_purse_to_purse(purse, utils::get_contracts_purse(), amount, None)
+        .unwrap_or_revert_with(Error::DepositFailed);
+    events::emit(&SwapEvent::Deposited { depositor, amount });
+    guard::exit(&mut CasperBackend);
+}
+
+#[no_mangle]
+pub extern "C" fn withdraw() {
+    // only what isn't locked in the current swap can be taken back
+    guard::enter(&mut CasperBackend).unwrap_or_revert();
+    let amount = runtime::get_named_arg::<U512>(constants::ARG_AMOUNT);
+    let depositor = utils::get_caller_key();
+    deposits::debit(&depositor, amount);
+    // a contract has no main purse to be paid to, it names a purse of its own
+    match (
+        get_optional_named_arg::<URef>(constants::ARG_PURSE),
+        depositor,
+    ) {
+        (Some(purse), _) => native_util::transfer_native_tokens_to_purse(purse, amount),
+        (None, Key::Account(_)) => native_util::transfer_native_tokens(depositor, amount),
+        (None, _) => runtime::revert(Error::PurseRequired),
+    }
+    events::emit(&SwapEvent::Withdrawn { depositor, amount });
+    guard::exit(&mut CasperBackend);
+}
+
+#[no_mangle]
+pub extern "C" fn set_receiver_hook() {
+    // only a contract can be called back, an account has nothing to run
+    guard::enter(&mut CasperBackend).unwrap_or_revert();
+    let enabled = runtime::get_named_arg::<bool>(constants::ARG_ENABLED);
+    let receiver = utils::get_caller_key();
+    if receiver.into_hash().is_none() {
+        runtime::revert(Error::NotAContract);
     }
-    let deposit = utils::get_contracts_purse();
-    // Next line is a bit sus!
-    deposit.access_rights().set(AccessRights::READ_ADD, true);
-    runtime::ret(CLValue::from_t(deposit).unwrap_or_revert());
+    hooks::set(&receiver, enabled);
+    guard::exit(&mut CasperBackend);
+}
+
+/// Records the swap as closed in `state` and clears it, before anything is paid out: a token
+/// contract called by the payout only ever sees a contract without a swap.
+fn close(state: SwapState) -> Payout {
+    let payout = Payout::current();
+    history::record(state);
+    utils::clear_all();
+    payout
 }
 
 #[no_mangle]
@@ -250,22 +412,43 @@ pub extern "C" fn init() {
     let caller = get_caller().to_key();
     let owner = storage::new_uref(caller);
     runtime::put_key(constants::NAMED_KEY_OWNER, owner.into());
+    // the installer, or the admin of the factory that installed it, runs the contract as a
+    // service, and is the only one who can set fees
+    let admin = runtime::get_named_arg::<Key>(constants::ARG_ADMIN);
+    runtime::put_key(constants::NAMED_KEY_ADMIN, storage::new_uref(admin).into());
+    // swaps of contracts installed by a factory are recorded in its registry
+    let factory = runtime::get_named_arg::<Option<ContractHash>>(constants::ARG_FACTORY);
+    runtime::put_key(
+        constants::NAMED_KEY_FACTORY,
+        storage::new_uref(factory).into(),
+    );
     let purse = create_purse();
     runtime::put_key(constants::NAMED_KEY_PURSE, purse.into());
+    storage::new_dictionary(constants::NAMED_KEY_SWAPS).unwrap_or_revert();
+    storage::new_dictionary(constants::NAMED_KEY_DEPOSITS).unwrap_or_revert();
+    storage::new_dictionary(constants::NAMED_KEY_HOOKS).unwrap_or_revert();
+    storage::new_dictionary(constants::NAMED_KEY_HASHLOCKS).unwrap_or_revert();
 }
 
-fn install_contract() {
-    let type_ = runtime::get_named_arg::<String>(constants::ARG_TYPE);
+/// Installs a swap contract of the given `type`, owned by the account of the deploy, and stores
+/// its package hash under `package_key` in the current context: the installer's account, or the
+/// factory.
+pub(crate) fn install_swap(
+    type_: String,
+    admin: Key,
+    factory: Option<ContractHash>,
+    package_key: &str,
+) -> (ContractHash, ContractPackageHash) {
     let entry_points = get_entrypoints();
     let named_keys = get_named_keys();
     let (contract_hash, _contract_version) = storage::new_contract(
         entry_points,
         Some(named_keys),
-        Some("atomic_swap_package_hash".to_string()),
+        Some(package_key.to_string()),
         None,
     );
     let package_hash = ContractPackageHash::new(
-        runtime::get_key("atomic_swap_package_hash")
+        runtime::get_key(package_key)
             .unwrap_or_revert()
             .into_hash()
             .unwrap_or_revert(),
@@ -277,17 +460,34 @@ fn install_contract() {
             .unwrap_or_revert();
     let _: () = runtime::call_contract(
         contract_hash,
-        "init",
+        constants::ENTRY_POINT_INIT,
         runtime_args! {
             constants::NAMED_KEY_OWN_CONTRACT_PACKAGE_HASH => package_hash,
             constants::NAMED_KEY_OWN_CONTRACT_HASH => contract_hash,
             constants::ARG_TYPE => type_,
+            constants::ARG_ADMIN => admin,
+            constants::ARG_FACTORY => factory,
         },
     );
     let mut urefs = BTreeSet::new();
     urefs.insert(constructor_access);
     storage::remove_contract_user_group_urefs(package_hash, "constructor", urefs)
         .unwrap_or_revert();
+    (contract_hash, package_hash)
+}
+
+fn install_contract() {
+    let type_ = runtime::get_named_arg::<String>(constants::ARG_TYPE);
+    if type_ == constants::FACTORY_TYPE {
+        factory::install_factory();
+        return;
+    }
+    let (contract_hash, _package_hash) = install_swap(
+        type_,
+        get_caller().to_key(),
+        None,
+        "atomic_swap_package_hash",
+    );
     runtime::put_key("atomic_swap_contract", contract_hash.into());
 }
 
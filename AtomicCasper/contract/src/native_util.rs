@@ -1,27 +1,39 @@
 use crate::{
-    constants,
-    utils::{self, get_key_val},
-    Error,
+    deposits,
+    events::{self, SwapEvent},
+    utils, Error,
 };
 use casper_contract::{
-    contract_api::{runtime, system::transfer_from_purse_to_account},
+    contract_api::{
+        runtime,
+        system::{transfer_from_purse_to_account, transfer_from_purse_to_purse},
+    },
     unwrap_or_revert::UnwrapOrRevert,
 };
-use casper_types::{Key, U512};
+use casper_types::{Key, URef, U512};
 
-pub fn transfer_native_tokens_to(reciver: Key) {
-    // transfer amount to reciver from contract's purse
-    let amount = get_key_val::<U512>(constants::NAMED_KEY_AMOUNT);
-    let reciver_account_hash = match reciver {
-        Key::Account(account_hash) => account_hash,
+/// Pays `amount` out of the contract's purse. An account gets it right away, a contract is
+/// credited in the `deposits` ledger and takes it out with `withdraw`, see swap_core::ledger.
+pub fn transfer_native_tokens(reciver: Key, amount: U512) {
+    match reciver {
+        Key::Account(account_hash) => {
+            let contract_purse = utils::get_contracts_purse();
+            transfer_from_purse_to_account(contract_purse, account_hash, amount, None)
+                .unwrap_or_revert_with(Error::NativeTransferFailed);
+        }
+        Key::Hash(_) => {
+            deposits::credit(&reciver, amount);
+            events::emit(&SwapEvent::Credited {
+                depositor: reciver,
+                amount,
+            });
+        }
         _ => runtime::revert(Error::ReciverNotAnAccount),
-    };
-    let contract_purse = utils::get_contracts_purse();
-    transfer_from_purse_to_account(contract_purse, reciver_account_hash, amount, None)
-        .unwrap_or_revert_with(Error::NativeTransferFailed);
+    }
 }
 
-pub fn transfer_native_tokens_back() {
-    let reciver = get_key_val::<Key>(constants::NAMED_KEY_OWNER);
-    transfer_native_tokens_to(reciver);
+/// Pays `amount` out of the contract's purse into `purse`, one the caller can write to.
+pub fn transfer_native_tokens_to_purse(purse: URef, amount: U512) {
+    transfer_from_purse_to_purse(utils::get_contracts_purse(), purse, amount, None)
+        .unwrap_or_revert_with(Error::NativeTransferFailed);
 }
@@ -1,25 +1,24 @@
 use alloc::vec::Vec;
-use casper_contract::{contract_api::runtime::call_contract, unwrap_or_revert::UnwrapOrRevert};
-use casper_types::{ContractHash, Key, RuntimeArgs};
+use casper_contract::unwrap_or_revert::UnwrapOrRevert;
+use casper_types::{Key, RuntimeArgs};
+use swap_core::token::{self, ReturnValue, TokenContract};
 
 use crate::{
-    constants,
-    utils::{get_contract_package_hash, get_key_val, ToKey},
+    utils::{call_token_contract, get_contract_package_hash, ToKey},
     Error, TokenId,
 };
 
-fn get_owner_of(token_id: TokenId) -> Option<Key> {
-    let contract_hash = get_key_val::<ContractHash>(constants::NAMED_KEY_CONTRACT_HASH);
+fn get_owner_of(token_contract: TokenContract, token_id: TokenId) -> Option<Key> {
     let mut runtimeargs = RuntimeArgs::new();
     runtimeargs
         .insert("token_id", token_id)
         .unwrap_or_revert_with(Error::RuntimeArgFailed);
-    call_contract::<Option<Key>>(contract_hash, "owner_of", runtimeargs)
+    call_token_contract::<Option<Key>>(token_contract, "owner_of", runtimeargs)
 }
 
-pub fn check_nfts_ownership(token_ids: Vec<TokenId>) -> bool {
+pub fn check_nfts_ownership(token_contract: TokenContract, token_ids: Vec<TokenId>) -> bool {
     for token_id in token_ids {
-        let owner = get_owner_of(token_id);
+        let owner = get_owner_of(token_contract, token_id);
         if owner.is_none() || owner.unwrap() != get_contract_package_hash().to_key() {
             return false;
         }
@@ -27,23 +26,19 @@ pub fn check_nfts_ownership(token_ids: Vec<TokenId>) -> bool {
     true
 }
 
-pub fn transfer_to(reciver: Key) {
-    let token_ids = get_key_val::<Vec<TokenId>>(constants::NAMED_KEY_TOKEN_IDS);
-    transfer_tokens(reciver, token_ids);
-}
-pub fn transfer_back() {
-    let reciver = get_key_val::<Key>(constants::NAMED_KEY_OWNER);
-    let token_ids = get_key_val::<Vec<TokenId>>(constants::NAMED_KEY_TOKEN_IDS);
-    transfer_tokens(reciver, token_ids);
-}
-fn transfer_tokens(reciver: Key, token_ids: Vec<TokenId>) {
-    let contract_hash = get_key_val::<ContractHash>(constants::NAMED_KEY_CONTRACT_HASH);
+pub fn transfer_tokens(token_contract: TokenContract, reciver: Key, token_ids: Vec<TokenId>) {
     let mut runtimeargs = RuntimeArgs::new();
     runtimeargs
         .insert("recipient", reciver)
         .unwrap_or_revert_with(Error::RuntimeArgFailed);
     runtimeargs
-        .insert("token_ids", token_ids)
+        .insert("token_ids", token_ids.clone())
         .unwrap_or_revert_with(Error::RuntimeArgFailed);
-    let _ = call_contract::<()>(contract_hash, "transfer", runtimeargs);
+    let returned = call_token_contract::<ReturnValue>(token_contract, "transfer", runtimeargs);
+    token::check_transfer_return(&returned).unwrap_or_revert();
+    // don't take the token's word for it
+    for token_id in token_ids {
+        token::check_owner_moved(get_owner_of(token_contract, token_id), reciver)
+            .unwrap_or_revert();
+    }
 }
@@ -1,59 +1,84 @@
-use alloc::string::String;
-use casper_contract::contract_api::runtime;
-use casper_types::{Key, U256, U512};
+use alloc::{string::String, vec::Vec};
+use casper_contract::{contract_api::runtime, unwrap_or_revert::UnwrapOrRevert};
+use casper_types::{Key, U512};
+use swap_core::{convert::U512ToU256, fee, token::TokenContract};
 
-use crate::{constants, erc20util, native_util, nftutil, utils::get_key_val, Error};
+use crate::{
+    constants, customutil, erc20util, native_util, nftutil, utils::get_key_val, Error, TokenId,
+};
 
-pub fn transfer_to(secret_hash: &str) {
-    let saved_hash = get_key_val::<String>(constants::NAMED_KEY_HASH);
-    if secret_hash != saved_hash.as_str() {
-        runtime::revert(Error::HashMismatch);
-    }
-    let _type = get_key_val::<String>(constants::NAMED_KEY_TYPE);
-    let _type = _type.as_str();
-    let reciver = get_key_val::<Key>(constants::NAMED_KEY_RECIVER);
-    match _type {
-        "NFT" => {
-            nftutil::transfer_to(reciver);
-        }
-        "ERC20" => {
-            erc20util::transfer_erc20_tokens_to(reciver);
-        }
-        "Direct" => {
-            native_util::transfer_native_tokens_to(reciver);
-        }
-        "Custom" => {}
-        _ => {
-            runtime::revert(Error::TypeNotFound);
-        }
-    }
+/// What the current swap locked. It is read before the swap is cleared, so the swap is closed
+/// before any other contract gets called to pay it out.
+pub struct Payout {
+    type_: String,
+    amount: U512,
+    token_ids: Vec<TokenId>,
+    token_contract: Option<TokenContract>,
+    fee_bps: u32,
 }
-pub fn transfer_back() {
-    let _type = get_key_val::<String>(constants::NAMED_KEY_TYPE);
-    let _type = _type.as_str();
-    match _type {
-        "NFT" => {
-            nftutil::transfer_back();
-        }
-        "ERC20" => {
-            erc20util::transfer_back();
+
+impl Payout {
+    pub fn current() -> Self {
+        Payout {
+            type_: get_key_val::<String>(constants::NAMED_KEY_TYPE),
+            amount: get_key_val::<U512>(constants::NAMED_KEY_AMOUNT),
+            token_ids: get_key_val::<Vec<TokenId>>(constants::NAMED_KEY_TOKEN_IDS),
+            token_contract: get_key_val::<Option<TokenContract>>(
+                constants::NAMED_KEY_CONTRACT_HASH,
+            ),
+            fee_bps: get_key_val::<u32>(constants::NAMED_KEY_FEE_BPS),
         }
-        "Direct" => {
-            native_util::transfer_native_tokens_back();
+    }
+
+    /// Pays the swap back to its owner, without a fee.
+    pub fn send(self, reciver: Key) {
+        self.pay(reciver, self.amount);
+    }
+
+    /// Pays the swap to its receiver, minus the fee, which goes to the treasury. Returns the
+    /// amount the receiver got and the fee.
+    pub fn redeem(self, reciver: Key) -> (U512, U512) {
+        let (amount, fee) = fee::split(self.amount, self.fee_bps);
+        self.pay(reciver, amount);
+        if !fee.is_zero() {
+            let treasury = get_key_val::<Option<Key>>(constants::NAMED_KEY_TREASURY)
+                .unwrap_or_revert_with(Error::TreasuryNotSet);
+            self.pay(treasury, fee);
         }
-        "Custom" => {}
-        _ => {
-            runtime::revert(Error::TypeNotFound);
+        (amount, fee)
+    }
+
+    fn pay(&self, reciver: Key, amount: U512) {
+        match self.type_.as_str() {
+            "NFT" => {
+                nftutil::transfer_tokens(self.token_contract(), reciver, self.token_ids.clone());
+            }
+            "ERC-20" => {
+                erc20util::transfer_erc20_tokens_to(
+                    self.token_contract(),
+                    reciver,
+                    amount.to_u256(),
+                );
+            }
+            "Direct" => {
+                native_util::transfer_native_tokens(reciver, amount);
+            }
+            "Custom" => {
+                customutil::transfer_custom_tokens_to(self.token_contract(), reciver);
+            }
+            _ => {
+                runtime::revert(Error::TypeNotFound);
+            }
         }
     }
-}
-pub trait U512ToU256 {
-    fn to_u256(self) -> U256;
-}
-impl U512ToU256 for U512 {
-    fn to_u256(self) -> U256 {
-        let mut result = U256::zero();
-        result.0[..4].clone_from_slice(&self.0[..4]);
-        result
+
+    fn token_contract(&self) -> TokenContract {
+        self.token_contract
+            .unwrap_or_revert_with(Error::ContractHashNotSet)
     }
 }
+
+pub fn get_token_contract() -> TokenContract {
+    get_key_val::<Option<TokenContract>>(constants::NAMED_KEY_CONTRACT_HASH)
+        .unwrap_or_revert_with(Error::ContractHashNotSet)
+}
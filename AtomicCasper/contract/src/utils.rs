@@ -1,137 +1,102 @@
-use crate::{constants, Error, TokenId};
-use alloc::{
-    string::{String, ToString},
-    vec::Vec,
-};
+use crate::{backend::CasperBackend, constants, Error, TokenId};
+use alloc::{string::ToString, vec::Vec};
 use casper_contract::{
     contract_api::{
+        self,
         runtime::{self, get_call_stack},
-        storage::{self, read},
     },
+    ext_ffi,
     unwrap_or_revert::UnwrapOrRevert,
 };
 use casper_types::{
     account::AccountHash,
-    bytesrepr::{FromBytes, ToBytes},
+    api_error,
+    bytesrepr::{self, FromBytes, ToBytes},
     system::CallStackElement,
-    CLTyped, ContractHash, ContractPackageHash, Key, URef, U512,
+    ApiError, CLTyped, ContractHash, ContractPackageHash, Key, PublicKey, RuntimeArgs, URef, U512,
 };
-pub fn generate_hash(hash_type: &str, secret: &str) -> String {
-    use sha3::Digest;
-    match hash_type {
-        "sha3-256" => {
-            let mut hasher = sha3::Sha3_256::new();
-            hasher.update(secret);
-            hex::encode(hasher.finalize())
-        }
-        "sha3-512" => {
-            let mut hasher = sha3::Sha3_512::new();
-            hasher.update(secret);
-            hex::encode(hasher.finalize())
-        }
-        "Keccak256" => {
-            let mut hasher = sha3::Keccak256::new();
-            hasher.update(secret);
-            hex::encode(hasher.finalize())
-        }
-        "Keccak512" => {
-            let mut hasher = sha3::Keccak512::new();
-            hasher.update(secret);
-            hex::encode(hasher.finalize())
-        }
-        "blake2b" => {
-            let bytes = runtime::blake2b(secret.as_bytes());
-            hex::encode(bytes)
-        }
-        _ => "".to_string(),
-    }
-}
-
-pub fn check_hash_type(hash_type: &str) -> bool {
-    if hash_type == "sha3-256"
-        || hash_type == "sha3-512"
-        || hash_type == "Keccak256"
-        || hash_type == "Keccak512"
-        || hash_type == "blake2b"
-    {
-        return true;
-    }
-    false
-}
-
+use swap_core::{backend::Storage, token::TokenContract};
 pub fn get_named_key_by_name(dict_name: &str) -> casper_types::URef {
     casper_contract::contract_api::runtime::get_key(dict_name)
         .unwrap_or_revert_with(Error::KeyNotFound)
         .into_uref()
         .unwrap_or_revert_with(Error::KeyNotFound)
 }
-pub fn get_current_time() -> u64 {
-    casper_contract::contract_api::runtime::get_blocktime().into()
-}
-pub fn _get_start_time() -> u64 {
-    let start_time = get_named_key_by_name(constants::NAMED_KEY_START_TIME);
-    read(start_time)
-        .unwrap_or_revert_with(Error::StartTimeNotSet)
-        .unwrap_or_revert_with(Error::StartTimeReadError)
-}
-pub fn set_start_time(start_time: u64) {
-    let start_time_uref = get_named_key_by_name(constants::NAMED_KEY_START_TIME);
-    storage::write(start_time_uref, start_time);
-}
-pub fn get_end_time() -> u64 {
-    let end_time = get_named_key_by_name(constants::NAMED_KEY_END_TIME);
-    read(end_time)
-        .unwrap_or_revert_with(Error::EndTimeNotSet)
-        .unwrap_or_revert_with(Error::EndTimeReadError)
-}
-pub fn set_end_time(end_time: u64) {
-    let end_time_uref = get_named_key_by_name(constants::NAMED_KEY_END_TIME);
-    storage::write(end_time_uref, end_time);
-}
-pub fn is_timed_out() -> bool {
-    get_current_time() > get_end_time()
-}
-pub fn _is_started() -> bool {
-    get_current_time() > _get_start_time()
-}
-
-pub fn check_ownership() -> bool {
-    let caller = get_caller_key();
-    let owner = get_owner();
-    if caller != owner {
-        return false;
-    }
-    true
-}
-pub fn get_owner() -> Key {
-    let owner = get_named_key_by_name(constants::NAMED_KEY_OWNER);
-    read(owner)
-        .unwrap_or_revert_with(Error::OwnerNotSet)
-        .unwrap_or_revert_with(Error::OwnerReadError)
+pub fn get_receiver_public_key() -> Option<PublicKey> {
+    get_key_val::<Option<PublicKey>>(constants::NAMED_KEY_RECEIVER_PUBLIC_KEY)
 }
-
 pub fn set_key<T>(key_name: &str, key_value: T)
 where
     T: CLTyped + ToBytes,
 {
-    if runtime::get_key(key_name).is_none() {
-        let key_uref = storage::new_uref(key_value).into();
-        runtime::put_key(key_name, key_uref);
-    } else {
-        let key_uref = get_named_key_by_name(key_name);
-        storage::write(key_uref, key_value);
-    }
+    CasperBackend.write(key_name, key_value);
 }
 pub fn get_key_val<T: FromBytes + CLTyped>(key: &str) -> T {
-    let value: T = match runtime::get_key(key) {
-        Some(Key::URef(uref)) => match storage::read(uref) {
-            Ok(Some(value)) => value,
-            Ok(None) => runtime::revert(Error::MissingValue),
-            Err(_error) => runtime::revert(Error::StorageError),
-        },
-        _ => runtime::revert(Error::MissingKey),
+    CasperBackend.read::<T>(key).unwrap_or_revert()
+}
+
+/// Same as `runtime::get_named_arg`, but returns `None` instead of reverting if the argument was
+/// not passed.
+pub fn get_optional_named_arg<T: FromBytes>(name: &str) -> Option<T> {
+    let arg_size = {
+        let mut arg_size: usize = 0;
+        let ret = unsafe {
+            ext_ffi::casper_get_named_arg_size(
+                name.as_bytes().as_ptr(),
+                name.len(),
+                &mut arg_size as *mut usize,
+            )
+        };
+        match api_error::result_from(ret) {
+            Ok(_) => arg_size,
+            Err(ApiError::MissingArgument) => return None,
+            Err(error) => runtime::revert(error),
+        }
     };
-    value
+    let arg_bytes = if arg_size > 0 {
+        let data_non_null_ptr = contract_api::alloc_bytes(arg_size);
+        let ret = unsafe {
+            ext_ffi::casper_get_named_arg(
+                name.as_bytes().as_ptr(),
+                name.len(),
+                data_non_null_ptr.as_ptr(),
+                arg_size,
+            )
+        };
+        let data = unsafe { Vec::from_raw_parts(data_non_null_ptr.as_ptr(), arg_size, arg_size) };
+        api_error::result_from(ret).unwrap_or_revert_with(Error::RuntimeArgFailed);
+        data
+    } else {
+        Vec::new()
+    };
+    Some(bytesrepr::deserialize(arg_bytes).unwrap_or_revert_with(Error::RuntimeArgFailed))
+}
+
+/// The token contract passed as `contract_hash`, or as `contract_package_hash` to follow the
+/// latest version of the package.
+pub fn get_token_contract_arg() -> TokenContract {
+    TokenContract::from_args(
+        get_optional_named_arg::<ContractHash>(constants::ARG_CONTRACT_HASH),
+        get_optional_named_arg::<ContractPackageHash>(constants::ARG_CONTRACT_PACKAGE_HASH),
+    )
+    .unwrap_or_revert()
+}
+
+/// Calls `entry_point` of the token contract, of its latest version if it was given as a
+/// package. Reverts if there is no such contract or entry point.
+pub fn call_token_contract<T: CLTyped + FromBytes>(
+    token_contract: TokenContract,
+    entry_point: &str,
+    args: RuntimeArgs,
+) -> T {
+    match token_contract {
+        TokenContract::Contract(contract_hash) => {
+            runtime::call_contract(contract_hash, entry_point, args)
+        }
+        TokenContract::Package(package_hash) => {
+            runtime::call_versioned_contract(package_hash, None, entry_point, args)
+        }
+    }
 }
 
 pub fn clear_all() {
@@ -139,21 +104,23 @@ pub fn clear_all() {
     set_key(constants::NAMED_KEY_HASH, "".to_string());
     set_key::<u64>(constants::NAMED_KEY_START_TIME, 0);
     set_key::<u64>(constants::NAMED_KEY_END_TIME, 0);
-    set_key(
-        constants::NAMED_KEY_OWNER,
-        AccountHash::new([0u8; 32]).to_key(),
-    );
+    set_key::<u64>(constants::NAMED_KEY_PROPOSED_END_TIME, 0);
     set_key(constants::NAMED_KEY_AMOUNT, U512::from(0u64));
-    set_key(constants::NAMED_KEY_CONTRACT_HASH, "".to_string());
+    set_key(constants::NAMED_KEY_CONTRACT_HASH, None::<TokenContract>);
     set_key(constants::NAMED_KEY_HASH_TYPE, "".to_string());
-    set_key(constants::NAMED_KEY_TYPE, "".to_string());
+    set_key(constants::NAMED_KEY_SALTED, false);
+    set_key(constants::NAMED_KEY_SECRET_HASH, "".to_string());
     set_key(
         constants::NAMED_KEY_RECIVER,
         Key::Account(AccountHash::new([0u8; 32])),
     );
+    set_key(constants::NAMED_KEY_RECEIVER_PUBLIC_KEY, None::<PublicKey>);
+    set_key(constants::NAMED_KEY_TERMS_HASH, "".to_string());
+    set_key(constants::NAMED_KEY_FEE_BPS, 0u32);
     let empty_vec: Vec<TokenId> = Vec::new();
     set_key(constants::NAMED_KEY_TOKEN_IDS, empty_vec);
     // Note that the purse is not cleared, as it is owned by the contract and can be used for other times.
+    // The owner and the type are kept too, so the owner can run the next swap on this contract.
 }
 
 pub fn _get_contract_hash() -> ContractHash {
@@ -183,27 +150,13 @@ impl ToKey for ContractPackageHash {
     }
 }
 
-pub fn already_initialized() -> bool {
-    let hash_uref = runtime::get_key(constants::NAMED_KEY_HASH);
-    if hash_uref.is_none() {
-        return false;
-    }
-    let hash_uref = hash_uref.unwrap_or_revert().into_uref().unwrap_or_revert();
-    let hash = storage::read::<String>(hash_uref)
-        .unwrap_or_revert()
-        .unwrap_or_revert();
-    if hash.as_str() == "" {
-        return false;
-    }
-    true
-}
-
+/// The purse itself is the `purse` named key. It must never be handed out of the contract, CSPR
+/// only comes in through `deposit`.
 pub fn get_contracts_purse() -> URef {
-    let deposit = get_named_key_by_name(constants::NAMED_KEY_PURSE);
-    let deposit: URef = storage::read(deposit)
+    runtime::get_key(constants::NAMED_KEY_PURSE)
         .unwrap_or_revert_with(Error::PurseNotSet)
-        .unwrap_or_revert_with(Error::PurseReadError);
-    deposit
+        .into_uref()
+        .unwrap_or_revert_with(Error::PurseReadError)
 }
 
 pub fn get_caller_key() -> Key {
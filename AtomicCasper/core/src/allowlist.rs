@@ -0,0 +1,134 @@
+//! The token contracts the admin vouches for.
+//!
+//! `initiate` of an NFT, ERC-20 or Custom swap takes any token contract, and the swap later calls
+//! into that contract. The admin lists the genuine token contracts with their standard, so a swap
+//! can't be initiated with a listed token of the wrong kind, and once the allowlist is enforced,
+//! not with an unlisted look-alike at all. A contract and its package are listed apart: vouching
+//! for one version says nothing about the versions a package may get later.
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    CLType, CLTyped,
+};
+
+use crate::{
+    backend::{Runtime, Storage},
+    constants, fee,
+    token::TokenContract,
+    Error,
+};
+
+/// Fungible tokens, what ERC-20 swaps lock.
+pub const CEP18: &str = "CEP-18";
+/// NFTs, what NFT swaps lock.
+pub const CEP47: &str = "CEP-47";
+pub const CEP78: &str = "CEP-78";
+
+/// What `get_allowlist` returns.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Allowlist {
+    /// If set, `initiate` rejects any token that isn't listed.
+    pub enforced: bool,
+    /// Standard of each listed token contract.
+    pub tokens: BTreeMap<TokenContract, String>,
+}
+
+impl Allowlist {
+    pub fn current<S: Storage>(storage: &S) -> Result<Self, Error> {
+        Ok(Allowlist {
+            enforced: storage.read(constants::NAMED_KEY_ALLOWLIST_ENFORCED)?,
+            tokens: storage.read(constants::NAMED_KEY_ALLOWLIST)?,
+        })
+    }
+}
+
+/// Whether a token of `standard` can be locked in a swap of `asset_type`. Custom tokens follow no
+/// standard, so Custom swaps take any listed token.
+pub fn fits(asset_type: &str, standard: &str) -> bool {
+    match asset_type {
+        "ERC-20" => standard == CEP18,
+        "NFT" => standard == CEP47 || standard == CEP78,
+        _ => true,
+    }
+}
+
+/// Lists `token` as a contract of `standard`, or changes its standard.
+pub fn allow<B: Runtime + Storage>(
+    backend: &mut B,
+    token: TokenContract,
+    standard: String,
+) -> Result<(), Error> {
+    fee::check_admin(backend)?;
+    if standard != CEP18 && standard != CEP47 && standard != CEP78 {
+        return Err(Error::UnknownTokenStandard);
+    }
+    let mut tokens = Allowlist::current(backend)?.tokens;
+    tokens.insert(token, standard);
+    backend.write(constants::NAMED_KEY_ALLOWLIST, tokens);
+    Ok(())
+}
+
+pub fn remove<B: Runtime + Storage>(backend: &mut B, token: TokenContract) -> Result<(), Error> {
+    fee::check_admin(backend)?;
+    let mut tokens = Allowlist::current(backend)?.tokens;
+    tokens.remove(&token);
+    backend.write(constants::NAMED_KEY_ALLOWLIST, tokens);
+    Ok(())
+}
+
+pub fn enforce<B: Runtime + Storage>(backend: &mut B, enforced: bool) -> Result<(), Error> {
+    fee::check_admin(backend)?;
+    backend.write(constants::NAMED_KEY_ALLOWLIST_ENFORCED, enforced);
+    Ok(())
+}
+
+/// Checks the token contract `initiate` got can be locked in a swap of `asset_type`. A listed
+/// token must be of a standard the type takes, an unlisted one is only accepted while the
+/// allowlist isn't enforced.
+pub fn check_token<S: Storage>(
+    storage: &S,
+    asset_type: &str,
+    token: &TokenContract,
+) -> Result<(), Error> {
+    let allowlist = Allowlist::current(storage)?;
+    match allowlist.tokens.get(token) {
+        Some(standard) if !fits(asset_type, standard) => Err(Error::TokenStandardMismatch),
+        Some(_) => Ok(()),
+        None if allowlist.enforced => Err(Error::TokenNotAllowed),
+        None => Ok(()),
+    }
+}
+
+/// A tuple serializes as its elements one after the other, so this is also the bytes of the
+/// allowlist.
+type Fields = (bool, BTreeMap<TokenContract, String>);
+
+impl Allowlist {
+    fn fields(&self) -> Fields {
+        (self.enforced, self.tokens.clone())
+    }
+}
+
+impl CLTyped for Allowlist {
+    fn cl_type() -> CLType {
+        Fields::cl_type()
+    }
+}
+
+impl ToBytes for Allowlist {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        self.fields().to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.fields().serialized_length()
+    }
+}
+
+impl FromBytes for Allowlist {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let ((enforced, tokens), bytes) = Fields::from_bytes(bytes)?;
+        Ok((Allowlist { enforced, tokens }, bytes))
+    }
+}
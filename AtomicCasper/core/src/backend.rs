@@ -0,0 +1,23 @@
+use casper_types::{
+    bytesrepr::{FromBytes, ToBytes},
+    CLTyped, Key,
+};
+
+use crate::Error;
+
+/// What the swap logic needs to know about the call it runs in.
+pub trait Runtime {
+    /// Block time in milliseconds since the unix epoch.
+    fn block_time(&self) -> u64;
+    /// The account, or the contract package, that called the entry point.
+    fn caller(&self) -> Key;
+}
+
+/// Typed access to the contract's named keys.
+pub trait Storage {
+    /// Fails with `MissingKey` if there is no such named key, `MissingValue` if it holds
+    /// nothing and `StorageError` if it holds a value of another type.
+    fn read<T: CLTyped + FromBytes>(&self, name: &str) -> Result<T, Error>;
+    /// Creates the named key if it doesn't exist yet.
+    fn write<T: CLTyped + ToBytes>(&mut self, name: &str, value: T);
+}
@@ -0,0 +1,149 @@
+//! The swaps one deploy of the batch session opens or redeems.
+//!
+//! A contract runs one swap at a time, so a batch spans several contracts, usually the instances
+//! of one factory. The session calls each of them in turn from the signer's account, so every
+//! call passes the same owner and receiver checks as a deploy of its own, and a single failing
+//! call reverts the whole deploy.
+use alloc::{string::String, vec::Vec};
+
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes, U32_SERIALIZED_LENGTH},
+    CLType, CLTyped, ContractHash, Key, RuntimeArgs, Signature,
+};
+
+use crate::Error;
+
+/// `initiate` of one contract, with the args a deploy of its own would pass.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Initiation {
+    pub contract_hash: ContractHash,
+    pub args: RuntimeArgs,
+}
+
+/// `unlock` of swap `swap_id` of one contract with its secret.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Redemption {
+    pub contract_hash: ContractHash,
+    pub swap_id: u64,
+    pub secret: String,
+    /// The payout and the receiver's signature of it, for swaps initiated with a receiver public
+    /// key, what `unlock` takes as `payout` and `signature`.
+    pub signed: Option<(Key, Signature)>,
+}
+
+/// A batch has to do something, an empty one is most likely a mistake of the caller.
+pub fn check_size(len: usize) -> Result<(), Error> {
+    if len == 0 {
+        return Err(Error::EmptyBatch);
+    }
+    Ok(())
+}
+
+/// A tuple serializes as its elements one after the other, so these are also the bytes of an
+/// initiation. The args go as their bytes, a `CLValue` has no CL type of its own.
+type InitiationFields = (ContractHash, Vec<u8>);
+
+impl Initiation {
+    fn fields(&self) -> Result<InitiationFields, bytesrepr::Error> {
+        Ok((self.contract_hash, self.args.to_bytes()?))
+    }
+}
+
+impl CLTyped for Initiation {
+    fn cl_type() -> CLType {
+        InitiationFields::cl_type()
+    }
+}
+
+impl ToBytes for Initiation {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        self.fields()?.to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        // the length of the args bytes, then the bytes
+        self.contract_hash.serialized_length()
+            + U32_SERIALIZED_LENGTH
+            + self.args.serialized_length()
+    }
+}
+
+impl FromBytes for Initiation {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let ((contract_hash, args), bytes) = InitiationFields::from_bytes(bytes)?;
+        let args = bytesrepr::deserialize::<RuntimeArgs>(args)?;
+        Ok((
+            Initiation {
+                contract_hash,
+                args,
+            },
+            bytes,
+        ))
+    }
+}
+
+/// The signature goes as its bytes like the args of an [`Initiation`], it has no CL type of its
+/// own.
+type RedemptionFields = ((ContractHash, u64, String), Option<(Key, Vec<u8>)>);
+
+impl Redemption {
+    fn fields(&self) -> Result<RedemptionFields, bytesrepr::Error> {
+        let signed = match &self.signed {
+            Some((payout, signature)) => Some((*payout, signature.to_bytes()?)),
+            None => None,
+        };
+        Ok((
+            (self.contract_hash, self.swap_id, self.secret.clone()),
+            signed,
+        ))
+    }
+}
+
+impl CLTyped for Redemption {
+    fn cl_type() -> CLType {
+        RedemptionFields::cl_type()
+    }
+}
+
+impl ToBytes for Redemption {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        self.fields()?.to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        let signed = match &self.signed {
+            Some((payout, signature)) => {
+                payout.serialized_length() + U32_SERIALIZED_LENGTH + signature.serialized_length()
+            }
+            None => 0,
+        };
+        self.contract_hash.serialized_length()
+            + self.swap_id.serialized_length()
+            + self.secret.serialized_length()
+            // the tag of the option
+            + 1
+            + signed
+    }
+}
+
+impl FromBytes for Redemption {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (((contract_hash, swap_id, secret), signed), bytes) =
+            RedemptionFields::from_bytes(bytes)?;
+        let signed = match signed {
+            Some((payout, signature)) => {
+                Some((payout, bytesrepr::deserialize::<Signature>(signature)?))
+            }
+            None => None,
+        };
+        Ok((
+            Redemption {
+                contract_hash,
+                swap_id,
+                secret,
+                signed,
+            },
+            bytes,
+        ))
+    }
+}
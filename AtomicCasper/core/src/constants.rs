@@ -0,0 +1,106 @@
+pub const NAMED_KEY_TOKEN_IDS: &str = "token_ids";
+pub const NAMED_KEY_HASH: &str = "hash";
+pub const NAMED_KEY_HASH_TYPE: &str = "hash_type";
+pub const NAMED_KEY_SECRET: &str = "secret";
+pub const NAMED_KEY_TYPE: &str = "type";
+pub const NAMED_KEY_OWNER: &str = "owner";
+pub const NAMED_KEY_RECIVER: &str = "reciver";
+pub const NAMED_KEY_RECEIVER_PUBLIC_KEY: &str = "receiver_public_key";
+pub const NAMED_KEY_SWAP_ID: &str = "swap_id";
+pub const NAMED_KEY_TERMS_HASH: &str = "terms_hash";
+pub const NAMED_KEY_SWAPS: &str = "swaps";
+pub const NAMED_KEY_DEPOSITS: &str = "deposits";
+pub const NAMED_KEY_HOOKS: &str = "hooks";
+pub const NAMED_KEY_HASHLOCKS: &str = "hashlocks";
+pub const NAMED_KEY_SALTED: &str = "salted";
+pub const NAMED_KEY_SECRET_HASH: &str = "secret_hash";
+pub const NAMED_KEY_LOCKED: &str = "locked";
+pub const NAMED_KEY_ADMIN: &str = "admin";
+pub const NAMED_KEY_TREASURY: &str = "treasury";
+pub const NAMED_KEY_FEES: &str = "fees";
+pub const NAMED_KEY_FEE_BPS: &str = "fee_bps";
+pub const NAMED_KEY_ALLOWLIST: &str = "allowlist";
+pub const NAMED_KEY_ALLOWLIST_ENFORCED: &str = "allowlist_enforced";
+pub const NAMED_KEY_FACTORY: &str = "factory";
+pub const NAMED_KEY_INSTANCES: &str = "instances";
+pub const NAMED_KEY_INSTANCES_BY_OWNER: &str = "instances_by_owner";
+pub const NAMED_KEY_INSTANCES_BY_HASHLOCK: &str = "instances_by_hashlock";
+pub const NAMED_KEY_INSTANCE_COUNT: &str = "instance_count";
+pub const NAMED_KEY_CONTRACT_HASH: &str = "contract_hash";
+pub const NAMED_KEY_OWN_CONTRACT_PACKAGE_HASH: &str = "own_contract_package_hash";
+pub const NAMED_KEY_AMOUNT: &str = "amount";
+pub const NAMED_KEY_START_TIME: &str = "start_time";
+pub const NAMED_KEY_END_TIME: &str = "end_time";
+pub const NAMED_KEY_PROPOSED_END_TIME: &str = "proposed_end_time";
+pub const NAMED_KEY_PURSE: &str = "purse";
+pub const NAMED_KEY_OWN_CONTRACT_HASH: &str = "own_contract_hash";
+
+pub const ARG_SECRET: &str = "secret";
+pub const ARG_CONTRACT_HASH: &str = "contract_hash";
+pub const ARG_CONTRACT_PACKAGE_HASH: &str = "contract_package_hash";
+pub const ARG_HASH: &str = "hash";
+pub const ARG_HASH_TYPE: &str = "hash_type";
+pub const ARG_SALTED: &str = "salted";
+pub const ARG_SECRET_HASH: &str = "secret_hash";
+pub const ARG_TIMEOUT: &str = "timeout";
+pub const ARG_TYPE: &str = "type";
+pub const ARG_AMOUNT: &str = "amount";
+pub const ARG_PURSE: &str = "purse";
+pub const ARG_RECEIVER: &str = "receiver";
+pub const ARG_TOKEN_IDS: &str = "token_ids";
+pub const ARG_END_TIME: &str = "end_time";
+pub const ARG_RECEIVER_PUBLIC_KEY: &str = "receiver_public_key";
+pub const ARG_PAYOUT: &str = "payout";
+pub const ARG_SIGNATURE: &str = "signature";
+pub const ARG_TERMS_HASH: &str = "terms_hash";
+pub const ARG_SWAP_ID: &str = "swap_id";
+pub const ARG_OFFSET: &str = "offset";
+pub const ARG_LIMIT: &str = "limit";
+pub const ARG_ASSET_TYPE: &str = "asset_type";
+pub const ARG_FEE_BPS: &str = "fee_bps";
+pub const ARG_TREASURY: &str = "treasury";
+pub const ARG_STANDARD: &str = "standard";
+pub const ARG_ENFORCED: &str = "enforced";
+pub const ARG_ENABLED: &str = "enabled";
+pub const ARG_ASSET: &str = "asset";
+pub const ARG_ADMIN: &str = "admin";
+pub const ARG_FACTORY: &str = "factory";
+pub const ARG_OWNER: &str = "owner";
+pub const ARG_BATCH: &str = "batch";
+pub const ARG_INITIATIONS: &str = "initiations";
+pub const ARG_REDEMPTIONS: &str = "redemptions";
+
+pub const ENTRY_POINT_INIT: &str = "init";
+pub const ENTRY_POINT_INITIATE: &str = "initiate";
+pub const ENTRY_POINT_UNLOCK: &str = "unlock";
+pub const ENTRY_POINT_REFUND: &str = "refund";
+pub const ENTRY_POINT_CANCEL: &str = "cancel";
+pub const ENTRY_POINT_PROPOSE_END_TIME: &str = "propose_end_time";
+pub const ENTRY_POINT_ACCEPT_END_TIME: &str = "accept_end_time";
+pub const ENTRY_POINT_GET_HASH: &str = "get_hash";
+pub const ENTRY_POINT_GET_SWAP: &str = "get_swap";
+pub const ENTRY_POINT_LIST_SWAPS: &str = "list_swaps";
+pub const ENTRY_POINT_DEPOSIT: &str = "deposit";
+pub const ENTRY_POINT_WITHDRAW: &str = "withdraw";
+pub const ENTRY_POINT_SET_FEE: &str = "set_fee";
+pub const ENTRY_POINT_SET_TREASURY: &str = "set_treasury";
+pub const ENTRY_POINT_ALLOW_TOKEN: &str = "allow_token";
+pub const ENTRY_POINT_REMOVE_TOKEN: &str = "remove_token";
+pub const ENTRY_POINT_ENFORCE_ALLOWLIST: &str = "enforce_allowlist";
+pub const ENTRY_POINT_GET_ALLOWLIST: &str = "get_allowlist";
+pub const ENTRY_POINT_GET_FEES: &str = "get_fees";
+pub const ENTRY_POINT_SET_RECEIVER_HOOK: &str = "set_receiver_hook";
+/// Called on a contract receiver that registered with `set_receiver_hook`, after `unlock` paid it.
+pub const ENTRY_POINT_ON_SWAP_RECEIVED: &str = "on_swap_received";
+pub const ENTRY_POINT_INIT_FACTORY: &str = "init_factory";
+pub const ENTRY_POINT_CREATE_SWAP: &str = "create_swap";
+pub const ENTRY_POINT_RECORD_SWAP: &str = "record_swap";
+pub const ENTRY_POINT_GET_INSTANCES: &str = "get_instances";
+pub const ENTRY_POINT_FIND_SWAP: &str = "find_swap";
+
+/// What the `batch` arg of the batch session runs.
+pub const BATCH_INITIATE: &str = "initiate_batch";
+pub const BATCH_UNLOCK: &str = "unlock_batch";
+
+/// The `type` that installs the factory instead of a swap contract.
+pub const FACTORY_TYPE: &str = "Factory";
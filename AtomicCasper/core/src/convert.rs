@@ -0,0 +1,23 @@
+use casper_types::{U256, U512};
+
+pub trait U512ToU256 {
+    fn to_u256(self) -> U256;
+}
+impl U512ToU256 for U512 {
+    fn to_u256(self) -> U256 {
+        let mut result = U256::zero();
+        result.0[..4].clone_from_slice(&self.0[..4]);
+        result
+    }
+}
+
+pub trait U256ToU512 {
+    fn to_u512(self) -> U512;
+}
+impl U256ToU512 for U256 {
+    fn to_u512(self) -> U512 {
+        let mut result = U512::zero();
+        result.0[..4].clone_from_slice(&self.0);
+        result
+    }
+}
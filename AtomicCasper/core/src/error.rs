@@ -0,0 +1,142 @@
+use core::convert::TryFrom;
+
+use casper_types::ApiError;
+
+/// An error enum which can be converted to a `u16` so it can be returned as an `ApiError::User`.
+#[repr(u16)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    StartTimeNotSet = 0,
+    StartTimeReadError = 1,
+    EndTimeNotSet = 2,
+    EndTimeReadError = 3,
+    HashNotSet = 4,
+    HashReadError = 5,
+    AccessDenied = 6,
+    TypeNotSupported = 7,
+    ContractHashNotSet = 8,
+    ContractHashReadError = 9,
+    NotEnoughBalance = 10,
+    PurseNotSet = 11,
+    PurseReadError = 12,
+    KeyNotFound = 13,
+    EndTimeNotReached = 14,
+    EndTimePassed = 15,
+    HashTypeNotSupported = 16,
+    MissingValue = 17,
+    MissingKey = 18,
+    HashMismatch = 19,
+    TypeNotFound = 20,
+    UnexpectedKeyVariant = 21,
+    StorageError = 22,
+    InvalidContext = 23,
+    ContractAlreadyInitialized = 24,
+    NFTsNotOwnedByContract = 25,
+    ReciverNotAnAccount = 26,
+    OwnerNotSet = 27,
+    OwnerReadError = 28,
+    NativeTransferFailed = 29,
+    RuntimeArgFailed = 30,
+    NotInitialized = 31,
+    EndTimeNotExtended = 32,
+    EndTimeNotProposed = 33,
+    EndTimeProposalMismatch = 34,
+    SignatureRequired = 35,
+    InvalidSignature = 36,
+    TimeOverflow = 37,
+    InvalidTermsHash = 38,
+    SwapNotFound = 39,
+    DepositFailed = 40,
+    InvalidAmount = 41,
+    TokenTransferRejected = 42,
+    TokenNotTransferred = 43,
+    Reentrancy = 44,
+    FeeTooHigh = 45,
+    TreasuryNotSet = 46,
+    UnknownInstance = 47,
+    EmptyBatch = 48,
+    SwapIdMismatch = 49,
+    TokenNotAllowed = 50,
+    TokenStandardMismatch = 51,
+    UnknownTokenStandard = 52,
+    PurseRequired = 53,
+    NotAContract = 54,
+    HashlockReused = 55,
+    ReceiverNotRegistered = 56,
+    SecretHashRequired = 57,
+}
+impl From<Error> for ApiError {
+    fn from(error: Error) -> Self {
+        ApiError::User(error as u16)
+    }
+}
+
+/// Turns the code of an `ApiError::User` back into the error the contract reverted with.
+impl TryFrom<u16> for Error {
+    type Error = u16;
+
+    fn try_from(code: u16) -> Result<Self, Self::Error> {
+        let error = match code {
+            0 => Error::StartTimeNotSet,
+            1 => Error::StartTimeReadError,
+            2 => Error::EndTimeNotSet,
+            3 => Error::EndTimeReadError,
+            4 => Error::HashNotSet,
+            5 => Error::HashReadError,
+            6 => Error::AccessDenied,
+            7 => Error::TypeNotSupported,
+            8 => Error::ContractHashNotSet,
+            9 => Error::ContractHashReadError,
+            10 => Error::NotEnoughBalance,
+            11 => Error::PurseNotSet,
+            12 => Error::PurseReadError,
+            13 => Error::KeyNotFound,
+            14 => Error::EndTimeNotReached,
+            15 => Error::EndTimePassed,
+            16 => Error::HashTypeNotSupported,
+            17 => Error::MissingValue,
+            18 => Error::MissingKey,
+            19 => Error::HashMismatch,
+            20 => Error::TypeNotFound,
+            21 => Error::UnexpectedKeyVariant,
+            22 => Error::StorageError,
+            23 => Error::InvalidContext,
+            24 => Error::ContractAlreadyInitialized,
+            25 => Error::NFTsNotOwnedByContract,
+            26 => Error::ReciverNotAnAccount,
+            27 => Error::OwnerNotSet,
+            28 => Error::OwnerReadError,
+            29 => Error::NativeTransferFailed,
+            30 => Error::RuntimeArgFailed,
+            31 => Error::NotInitialized,
+            32 => Error::EndTimeNotExtended,
+            33 => Error::EndTimeNotProposed,
+            34 => Error::EndTimeProposalMismatch,
+            35 => Error::SignatureRequired,
+            36 => Error::InvalidSignature,
+            37 => Error::TimeOverflow,
+            38 => Error::InvalidTermsHash,
+            39 => Error::SwapNotFound,
+            40 => Error::DepositFailed,
+            41 => Error::InvalidAmount,
+            42 => Error::TokenTransferRejected,
+            43 => Error::TokenNotTransferred,
+            44 => Error::Reentrancy,
+            45 => Error::FeeTooHigh,
+            46 => Error::TreasuryNotSet,
+            47 => Error::UnknownInstance,
+            48 => Error::EmptyBatch,
+            49 => Error::SwapIdMismatch,
+            50 => Error::TokenNotAllowed,
+            51 => Error::TokenStandardMismatch,
+            52 => Error::UnknownTokenStandard,
+            53 => Error::PurseRequired,
+            54 => Error::NotAContract,
+            55 => Error::HashlockReused,
+            56 => Error::ReceiverNotRegistered,
+            57 => Error::SecretHashRequired,
+            _ => return Err(code),
+        };
+        Ok(error)
+    }
+}
@@ -0,0 +1,126 @@
+//! The protocol fee taken from swaps on redeem.
+//!
+//! The admin sets a rate in basis points for each fungible asset type, and the treasury the fees
+//! go to. A swap keeps the rate it was initiated with, so a change only applies to later swaps.
+//! NFTs can't be split, so `NFT` and `Custom` swaps never pay a fee.
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    CLType, CLTyped, Key, U512,
+};
+
+use crate::{
+    backend::{Runtime, Storage},
+    constants, Error,
+};
+
+/// One basis point is a hundredth of a percent.
+pub const BPS_DENOMINATOR: u32 = 10_000;
+/// Highest rate the admin can set, 10%.
+pub const MAX_FEE_BPS: u32 = 1_000;
+
+/// What `get_fees` returns.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Fees {
+    pub treasury: Option<Key>,
+    /// Rate of each asset type, in basis points. Types without one pay nothing.
+    pub rates: BTreeMap<String, u32>,
+}
+
+impl Fees {
+    pub fn current<S: Storage>(storage: &S) -> Result<Self, Error> {
+        Ok(Fees {
+            treasury: storage.read(constants::NAMED_KEY_TREASURY)?,
+            rates: storage.read(constants::NAMED_KEY_FEES)?,
+        })
+    }
+}
+
+pub fn check_admin<B: Runtime + Storage>(backend: &B) -> Result<(), Error> {
+    let admin = backend.read::<Key>(constants::NAMED_KEY_ADMIN)?;
+    if backend.caller() != admin {
+        return Err(Error::AccessDenied);
+    }
+    Ok(())
+}
+
+/// Sets the rate of `asset_type`. A rate above zero needs a treasury to pay the fees to.
+pub fn set_rate<B: Runtime + Storage>(
+    backend: &mut B,
+    asset_type: &str,
+    bps: u32,
+) -> Result<(), Error> {
+    check_admin(backend)?;
+    if asset_type != "Direct" && asset_type != "ERC-20" {
+        return Err(Error::TypeNotSupported);
+    }
+    if bps > MAX_FEE_BPS {
+        return Err(Error::FeeTooHigh);
+    }
+    let mut fees = Fees::current(backend)?;
+    if bps > 0 && fees.treasury.is_none() {
+        return Err(Error::TreasuryNotSet);
+    }
+    fees.rates.insert(asset_type.to_string(), bps);
+    backend.write(constants::NAMED_KEY_FEES, fees.rates);
+    Ok(())
+}
+
+pub fn set_treasury<B: Runtime + Storage>(backend: &mut B, treasury: Key) -> Result<(), Error> {
+    check_admin(backend)?;
+    backend.write(constants::NAMED_KEY_TREASURY, Some(treasury));
+    Ok(())
+}
+
+/// The rate a swap of `asset_type` initiated now pays.
+pub fn rate<S: Storage>(storage: &S, asset_type: &str) -> Result<u32, Error> {
+    let rates = storage.read::<BTreeMap<String, u32>>(constants::NAMED_KEY_FEES)?;
+    Ok(rates.get(asset_type).copied().unwrap_or_default())
+}
+
+/// Splits `amount` into what the receiver gets and the fee, rounding the fee down.
+pub fn split(amount: U512, bps: u32) -> (U512, U512) {
+    let fee = amount
+        .checked_mul(U512::from(bps))
+        .map(|scaled| scaled / U512::from(BPS_DENOMINATOR))
+        // too large to scale first, dividing first rounds the fee down a little further
+        .unwrap_or_else(|| amount / U512::from(BPS_DENOMINATOR) * U512::from(bps));
+    (amount - fee, fee)
+}
+
+/// A tuple serializes as its elements one after the other, so this is also the bytes of the fees.
+type Fields = (Option<Key>, BTreeMap<String, u32>);
+
+impl Fees {
+    fn fields(&self) -> Fields {
+        (self.treasury, self.rates.clone())
+    }
+}
+
+impl CLTyped for Fees {
+    fn cl_type() -> CLType {
+        Fields::cl_type()
+    }
+}
+
+impl ToBytes for Fees {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        self.fields().to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.fields().serialized_length()
+    }
+}
+
+impl FromBytes for Fees {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let ((treasury, rates), bytes) = Fields::from_bytes(bytes)?;
+        Ok((Fees { treasury, rates }, bytes))
+    }
+}
@@ -0,0 +1,19 @@
+//! The contract-wide reentrancy lock.
+//!
+//! Entry points that call other contracts hold the lock for their whole run, so a token contract
+//! called during a payout can't call back into any of them, e.g. to unlock the same swap again.
+use crate::{backend::Storage, constants, Error};
+
+pub fn enter<S: Storage>(storage: &mut S) -> Result<(), Error> {
+    match storage.read::<bool>(constants::NAMED_KEY_LOCKED) {
+        Ok(true) => return Err(Error::Reentrancy),
+        Ok(false) | Err(Error::MissingKey) => {}
+        Err(error) => return Err(error),
+    }
+    storage.write(constants::NAMED_KEY_LOCKED, true);
+    Ok(())
+}
+
+pub fn exit<S: Storage>(storage: &mut S) {
+    storage.write(constants::NAMED_KEY_LOCKED, false);
+}
@@ -0,0 +1,69 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+use casper_types::ContractPackageHash;
+
+pub const HASH_TYPES: [&str; 5] = ["sha3-256", "sha3-512", "Keccak256", "Keccak512", "blake2b"];
+
+/// Hex encoded hash of `secret`, or an empty string if `hash_type` is not supported.
+pub fn generate_hash(hash_type: &str, secret: &str) -> String {
+    use sha3::Digest;
+    match hash_type {
+        "sha3-256" => {
+            let mut hasher = sha3::Sha3_256::new();
+            hasher.update(secret);
+            hex::encode(hasher.finalize())
+        }
+        "sha3-512" => {
+            let mut hasher = sha3::Sha3_512::new();
+            hasher.update(secret);
+            hex::encode(hasher.finalize())
+        }
+        "Keccak256" => {
+            let mut hasher = sha3::Keccak256::new();
+            hasher.update(secret);
+            hex::encode(hasher.finalize())
+        }
+        "Keccak512" => {
+            let mut hasher = sha3::Keccak512::new();
+            hasher.update(secret);
+            hex::encode(hasher.finalize())
+        }
+        "blake2b" => hex::encode(blake2b(secret.as_bytes())),
+        _ => "".to_string(),
+    }
+}
+
+/// What a salted swap hashes instead of the bare secret: `<secret>:<package hash hex>:<swap id>`,
+/// with the contract package and the id of the swap. Its hashlock belongs to that one swap, the
+/// preimage revealed by unlocking it matches the hashlock of no other swap.
+pub fn salted_secret(
+    secret: &str,
+    contract_package_hash: ContractPackageHash,
+    swap_id: u64,
+) -> String {
+    format!(
+        "{}:{}:{}",
+        secret,
+        hex::encode(contract_package_hash.value()),
+        swap_id
+    )
+}
+
+pub fn check_hash_type(hash_type: &str) -> bool {
+    HASH_TYPES.contains(&hash_type)
+}
+
+/// 32 byte blake2b, the same digest as `runtime::blake2b` returns inside the contract.
+pub fn blake2b(bytes: &[u8]) -> [u8; 32] {
+    use blake2::{
+        digest::{Update, VariableOutput},
+        VarBlake2b,
+    };
+    let mut result = [0u8; 32];
+    let mut hasher = VarBlake2b::new(32).expect("32 is a valid blake2b output size");
+    hasher.update(bytes);
+    hasher.finalize_variable(|hash| result.copy_from_slice(hash));
+    result
+}
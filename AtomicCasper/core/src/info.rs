@@ -0,0 +1,168 @@
+//! The record of a swap that `get_swap` and `list_swaps` return, and the contract keeps for
+//! every swap it ran in its `swaps` dictionary, keyed by swap id.
+use alloc::{string::String, vec::Vec};
+use core::ops::Range;
+
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    CLType, CLTyped, Key, U512,
+};
+
+use crate::{backend::Storage, constants, swap, token::TokenContract, Error, TokenId};
+
+/// Most swaps `list_swaps` returns in one page.
+pub const MAX_PAGE_SIZE: u64 = 50;
+
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SwapState {
+    Open = 0,
+    Redeemed = 1,
+    Refunded = 2,
+    Cancelled = 3,
+}
+
+impl SwapState {
+    fn from_u8(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(SwapState::Open),
+            1 => Some(SwapState::Redeemed),
+            2 => Some(SwapState::Refunded),
+            3 => Some(SwapState::Cancelled),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SwapInfo {
+    pub swap_id: u64,
+    pub state: SwapState,
+    /// The `type` of the contract: NFT, ERC-20, Direct or Custom.
+    pub asset_type: String,
+    /// The token contract, none for Direct swaps.
+    pub token_contract: Option<TokenContract>,
+    pub amount: U512,
+    pub token_ids: Vec<TokenId>,
+    /// Gets the asset back on refund or cancel.
+    pub owner: Key,
+    pub receiver: Key,
+    pub hash_type: String,
+    pub hashlock: String,
+    pub start_time: u64,
+    pub end_time: u64,
+    /// Empty if the swap isn't bound to signed terms.
+    pub terms_hash: String,
+    /// Whether the hashlock is over the salted secret, see [`crate::hash::salted_secret`].
+    pub salted: bool,
+}
+
+impl SwapInfo {
+    /// The swap the contract currently holds, in the given state. Read before `clear_all`
+    /// wipes it.
+    pub fn current<S: Storage>(storage: &S, state: SwapState) -> Result<Self, Error> {
+        Ok(SwapInfo {
+            swap_id: storage.read(constants::NAMED_KEY_SWAP_ID)?,
+            state,
+            asset_type: storage.read(constants::NAMED_KEY_TYPE)?,
+            token_contract: storage.read(constants::NAMED_KEY_CONTRACT_HASH)?,
+            amount: storage.read(constants::NAMED_KEY_AMOUNT)?,
+            token_ids: storage.read(constants::NAMED_KEY_TOKEN_IDS)?,
+            owner: storage.read(constants::NAMED_KEY_OWNER)?,
+            receiver: storage.read(constants::NAMED_KEY_RECIVER)?,
+            hash_type: storage.read(constants::NAMED_KEY_HASH_TYPE)?,
+            hashlock: storage.read(constants::NAMED_KEY_HASH)?,
+            start_time: storage.read(constants::NAMED_KEY_START_TIME)?,
+            end_time: storage.read(constants::NAMED_KEY_END_TIME)?,
+            terms_hash: storage.read(constants::NAMED_KEY_TERMS_HASH)?,
+            salted: swap::is_salted(storage)?,
+        })
+    }
+}
+
+/// Swap ids are given out from 1 on, so the swaps of page `offset`, `limit` out of `count` are
+/// `offset + 1` up to `offset + limit`, at most [`MAX_PAGE_SIZE`] of them.
+pub fn page(count: u64, offset: u64, limit: u64) -> Range<u64> {
+    let start = offset.saturating_add(1);
+    let end = offset
+        .saturating_add(limit.min(MAX_PAGE_SIZE))
+        .min(count)
+        .saturating_add(1);
+    start..end.max(start)
+}
+
+/// The fields of a record as nested tuples, casper has none of more than three elements. A
+/// tuple serializes as its elements one after the other, so these are also the bytes of the
+/// record: every field in declaration order, the state as its `u8`.
+type Fields = (
+    (u64, u8, String),
+    (
+        (Option<TokenContract>, U512, Vec<TokenId>),
+        (Key, Key, String),
+        (String, u64, u64),
+    ),
+    (String, bool),
+);
+
+impl SwapInfo {
+    fn fields(&self) -> Fields {
+        (
+            (self.swap_id, self.state as u8, self.asset_type.clone()),
+            (
+                (self.token_contract, self.amount, self.token_ids.clone()),
+                (self.owner, self.receiver, self.hash_type.clone()),
+                (self.hashlock.clone(), self.start_time, self.end_time),
+            ),
+            (self.terms_hash.clone(), self.salted),
+        )
+    }
+}
+
+impl CLTyped for SwapInfo {
+    fn cl_type() -> CLType {
+        Fields::cl_type()
+    }
+}
+
+impl ToBytes for SwapInfo {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        self.fields().to_bytes()
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.fields().serialized_length()
+    }
+}
+
+impl FromBytes for SwapInfo {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (fields, bytes) = Fields::from_bytes(bytes)?;
+        let (
+            (swap_id, state, asset_type),
+            (
+                (token_contract, amount, token_ids),
+                (owner, receiver, hash_type),
+                (hashlock, start_time, end_time),
+            ),
+            (terms_hash, salted),
+        ) = fields;
+        let state = SwapState::from_u8(state).ok_or(bytesrepr::Error::Formatting)?;
+        let info = SwapInfo {
+            swap_id,
+            state,
+            asset_type,
+            token_contract,
+            amount,
+            token_ids,
+            owner,
+            receiver,
+            hash_type,
+            hashlock,
+            start_time,
+            end_time,
+            terms_hash,
+            salted,
+        };
+        Ok((info, bytes))
+    }
+}
@@ -0,0 +1,38 @@
+//! Bookkeeping of the CSPR each depositor moved into the contract's purse through `deposit`.
+//!
+//! The contract keeps the balances in its `deposits` dictionary. A `Direct` swap debits the
+//! owner's balance when it is initiated, and `withdraw` pays back whatever isn't locked.
+//!
+//! CSPR can only be sent to an account, a contract has no main purse. What a `Direct` swap pays
+//! to a contract, as receiver, owner or treasury, is credited to it here instead, and the contract
+//! withdraws it into a purse of its own, or locks it in a swap of its own.
+use alloc::string::String;
+
+use casper_types::{bytesrepr::ToBytes, Key, U512};
+
+use crate::{hash, Error};
+
+/// Dictionary item key of `depositor` in the `deposits` dictionary.
+///
+/// Formatted keys are longer than the 64 bytes a dictionary item key may have, so the depositor
+/// is stored under the hex of the blake2b hash of its serialized key instead.
+pub fn item_key(depositor: &Key) -> String {
+    let bytes = depositor.to_bytes().unwrap_or_default();
+    hex::encode(hash::blake2b(&bytes))
+}
+
+/// Balance after `amount` is deposited.
+pub fn credit(balance: U512, amount: U512) -> Result<U512, Error> {
+    if amount.is_zero() {
+        return Err(Error::InvalidAmount);
+    }
+    balance.checked_add(amount).ok_or(Error::InvalidAmount)
+}
+
+/// Balance after `amount` is taken out of it, to be locked in a swap or withdrawn.
+pub fn debit(balance: U512, amount: U512) -> Result<U512, Error> {
+    if amount.is_zero() {
+        return Err(Error::InvalidAmount);
+    }
+    balance.checked_sub(amount).ok_or(Error::NotEnoughBalance)
+}
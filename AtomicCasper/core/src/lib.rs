@@ -0,0 +1,30 @@
+//! The target independent part of the AtomicSwap contract.
+//!
+//! Everything here is plain logic on top of the [`Runtime`](backend::Runtime) and
+//! [`Storage`](backend::Storage) traits, so it runs in the wasm contract through the casper
+//! backend and on the host through `mock::MockBackend`, behind the `test-util` feature, for tests.
+#![no_std]
+
+extern crate alloc;
+
+pub mod allowlist;
+pub mod backend;
+pub mod batch;
+pub mod constants;
+pub mod convert;
+mod error;
+pub mod fee;
+pub mod guard;
+pub mod hash;
+pub mod info;
+pub mod ledger;
+#[cfg(feature = "test-util")]
+pub mod mock;
+pub mod registry;
+pub mod swap;
+pub mod time;
+pub mod token;
+
+pub use error::Error;
+
+pub type TokenId = casper_types::U256;
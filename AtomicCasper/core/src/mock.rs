@@ -0,0 +1,88 @@
+//! An in-memory backend for running the swap logic on the host.
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+use casper_types::{
+    bytesrepr::{FromBytes, ToBytes},
+    CLTyped, CLValue, ContractPackageHash, Key, PublicKey, U512,
+};
+
+use crate::{
+    backend::{Runtime, Storage},
+    constants,
+    token::TokenContract,
+    Error, TokenId,
+};
+
+pub struct MockBackend {
+    pub block_time: u64,
+    pub caller: Key,
+    named_keys: BTreeMap<String, CLValue>,
+}
+
+impl MockBackend {
+    /// A freshly installed contract of the given `type`, owned by `owner`, with the same named
+    /// keys `install_contract` and `init` create.
+    pub fn install(owner: Key, contract_package_hash: ContractPackageHash, type_: &str) -> Self {
+        let mut backend = MockBackend {
+            block_time: 0,
+            caller: owner,
+            named_keys: BTreeMap::new(),
+        };
+        backend.write(constants::NAMED_KEY_HASH, "".to_string());
+        backend.write(constants::NAMED_KEY_HASH_TYPE, "".to_string());
+        backend.write(constants::NAMED_KEY_SALTED, false);
+        backend.write(constants::NAMED_KEY_SECRET_HASH, "".to_string());
+        backend.write(constants::NAMED_KEY_SECRET, "".to_string());
+        backend.write(constants::NAMED_KEY_AMOUNT, U512::from(0u64));
+        backend.write(constants::NAMED_KEY_CONTRACT_HASH, None::<TokenContract>);
+        backend.write(constants::NAMED_KEY_END_TIME, 0u64);
+        backend.write(constants::NAMED_KEY_PROPOSED_END_TIME, 0u64);
+        backend.write(constants::NAMED_KEY_START_TIME, 0u64);
+        backend.write(constants::NAMED_KEY_RECEIVER_PUBLIC_KEY, None::<PublicKey>);
+        backend.write(constants::NAMED_KEY_SWAP_ID, 0u64);
+        backend.write(constants::NAMED_KEY_TERMS_HASH, "".to_string());
+        backend.write(constants::NAMED_KEY_LOCKED, false);
+        backend.write(constants::NAMED_KEY_TREASURY, None::<Key>);
+        backend.write(constants::NAMED_KEY_FEES, BTreeMap::<String, u32>::new());
+        backend.write(constants::NAMED_KEY_FEE_BPS, 0u32);
+        backend.write(
+            constants::NAMED_KEY_ALLOWLIST,
+            BTreeMap::<TokenContract, String>::new(),
+        );
+        backend.write(constants::NAMED_KEY_ALLOWLIST_ENFORCED, false);
+        backend.write(constants::NAMED_KEY_TOKEN_IDS, Vec::<TokenId>::new());
+        backend.write(
+            constants::NAMED_KEY_OWN_CONTRACT_PACKAGE_HASH,
+            contract_package_hash,
+        );
+        backend.write(constants::NAMED_KEY_TYPE, type_.to_string());
+        backend.write(constants::NAMED_KEY_OWNER, owner);
+        backend.write(constants::NAMED_KEY_ADMIN, owner);
+        backend
+    }
+}
+
+impl Runtime for MockBackend {
+    fn block_time(&self) -> u64 {
+        self.block_time
+    }
+
+    fn caller(&self) -> Key {
+        self.caller
+    }
+}
+
+impl Storage for MockBackend {
+    fn read<T: CLTyped + FromBytes>(&self, name: &str) -> Result<T, Error> {
+        let value = self.named_keys.get(name).ok_or(Error::MissingKey)?;
+        value.clone().into_t().map_err(|_| Error::StorageError)
+    }
+
+    fn write<T: CLTyped + ToBytes>(&mut self, name: &str, value: T) {
+        let value = CLValue::from_t(value).expect("value should serialize");
+        self.named_keys.insert(name.to_string(), value);
+    }
+}
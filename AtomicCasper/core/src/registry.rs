@@ -0,0 +1,26 @@
+//! Dictionary item keys of the factory's registry of the swap contracts it installed.
+//!
+//! `instances_by_owner` lists the instances each owner created, `instances_by_hashlock` points
+//! from the hashlock of a swap to the instance it was initiated on, and `instances` holds every
+//! instance, keyed by package hash, so the factory knows which callers are its own.
+use alloc::string::String;
+
+use casper_types::{ContractPackageHash, Key};
+
+use crate::{hash, ledger};
+
+/// Item key of `owner` in `instances_by_owner`, the same encoding as the `deposits` ledger.
+pub fn owner_item_key(owner: &Key) -> String {
+    ledger::item_key(owner)
+}
+
+/// Item key of a hashlock in `instances_by_hashlock`. Hashlocks of some hash types are longer
+/// than a dictionary item key may be, so the blake2b hash of the hashlock is used.
+pub fn hashlock_item_key(hashlock: &str) -> String {
+    hex::encode(hash::blake2b(hashlock.as_bytes()))
+}
+
+/// Item key of an instance in `instances`.
+pub fn instance_item_key(package_hash: ContractPackageHash) -> String {
+    hex::encode(package_hash.value())
+}
@@ -0,0 +1,218 @@
+//! State checks of the swap entry points, in the order the contract runs them.
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use casper_types::{bytesrepr::ToBytes, crypto, ContractPackageHash, Key, PublicKey, Signature};
+
+use crate::{
+    backend::{Runtime, Storage},
+    constants, hash, time, Error,
+};
+
+pub fn already_initialized<S: Storage>(storage: &S) -> Result<bool, Error> {
+    match storage.read::<String>(constants::NAMED_KEY_HASH) {
+        Ok(hash) => Ok(!hash.is_empty()),
+        Err(Error::MissingKey) => Ok(false),
+        Err(error) => Err(error),
+    }
+}
+
+pub fn check_ownership<B: Runtime + Storage>(backend: &B) -> Result<bool, Error> {
+    let owner = backend.read::<Key>(constants::NAMED_KEY_OWNER)?;
+    Ok(backend.caller() == owner)
+}
+
+pub fn check_receiver<B: Runtime + Storage>(backend: &B) -> Result<bool, Error> {
+    let reciver = backend.read::<Key>(constants::NAMED_KEY_RECIVER)?;
+    Ok(backend.caller() == reciver)
+}
+
+pub fn is_timed_out<B: Runtime + Storage>(backend: &B) -> Result<bool, Error> {
+    let end_time = backend.read::<u64>(constants::NAMED_KEY_END_TIME)?;
+    Ok(time::is_timed_out(backend.block_time(), end_time))
+}
+
+/// Only the owner can initiate, only once, and only with a supported hash type.
+pub fn check_initiate<B: Runtime + Storage>(backend: &B, hash_type: &str) -> Result<(), Error> {
+    if !check_ownership(backend)? {
+        return Err(Error::AccessDenied);
+    }
+    if already_initialized(backend)? {
+        return Err(Error::ContractAlreadyInitialized);
+    }
+    if !hash::check_hash_type(hash_type) {
+        return Err(Error::HashTypeNotSupported);
+    }
+    Ok(())
+}
+
+/// A terms hash is empty, or the hex encoded blake2b hash of the terms signed off-chain.
+pub fn check_terms_hash(terms_hash: &str) -> Result<(), Error> {
+    let valid = terms_hash.is_empty()
+        || (terms_hash.len() == 64
+            && terms_hash
+                .bytes()
+                .all(|byte| matches!(byte, b'0'..=b'9' | b'a'..=b'f')));
+    if !valid {
+        return Err(Error::InvalidTermsHash);
+    }
+    Ok(())
+}
+
+/// Sets `start_time` to now and `end_time` to `timeout` milliseconds later.
+pub fn start<B: Runtime + Storage>(backend: &mut B, timeout: u64) -> Result<(), Error> {
+    let current_time = backend.block_time();
+    let end_time = time::end_time(current_time, timeout)?;
+    backend.write(constants::NAMED_KEY_START_TIME, current_time);
+    backend.write(constants::NAMED_KEY_END_TIME, end_time);
+    Ok(())
+}
+
+/// Every initiation gets a new swap id.
+pub fn next_swap_id<S: Storage>(storage: &mut S) -> Result<u64, Error> {
+    let swap_id = storage.read::<u64>(constants::NAMED_KEY_SWAP_ID)? + 1;
+    storage.write(constants::NAMED_KEY_SWAP_ID, swap_id);
+    Ok(swap_id)
+}
+
+/// A caller naming the swap it means, like the batch session does, must name the current one, so
+/// a reused contract never pays out a later swap by mistake.
+pub fn check_swap_id<S: Storage>(storage: &S, swap_id: u64) -> Result<(), Error> {
+    if storage.read::<u64>(constants::NAMED_KEY_SWAP_ID)? != swap_id {
+        return Err(Error::SwapIdMismatch);
+    }
+    Ok(())
+}
+
+/// Anyone but the owner can unlock, before the timeout, with the secret of the stored hash.
+pub fn check_unlock<B: Runtime + Storage>(backend: &B, secret: &str) -> Result<(), Error> {
+    if check_ownership(backend)? {
+        return Err(Error::AccessDenied);
+    }
+    if is_timed_out(backend)? {
+        return Err(Error::EndTimePassed);
+    }
+    let hash_type = backend.read::<String>(constants::NAMED_KEY_HASH_TYPE)?;
+    let saved_hash = backend.read::<String>(constants::NAMED_KEY_HASH)?;
+    let preimage = if is_salted(backend)? {
+        // the bare secret must be the one of the hashlock `initiate` claimed along with the
+        // salted one, so it locks no other swap of the contract
+        let secret_hash = backend.read::<String>(constants::NAMED_KEY_SECRET_HASH)?;
+        if hash::generate_hash(hash_type.as_str(), secret) != secret_hash {
+            return Err(Error::HashMismatch);
+        }
+        hash::salted_secret(
+            secret,
+            backend.read::<ContractPackageHash>(constants::NAMED_KEY_OWN_CONTRACT_PACKAGE_HASH)?,
+            backend.read::<u64>(constants::NAMED_KEY_SWAP_ID)?,
+        )
+    } else {
+        secret.to_string()
+    };
+    if hash::generate_hash(hash_type.as_str(), &preimage) != saved_hash {
+        return Err(Error::HashMismatch);
+    }
+    Ok(())
+}
+
+/// Whether the hashlock of the current swap is over its salted secret, see
+/// [`hash::salted_secret`].
+pub fn is_salted<S: Storage>(storage: &S) -> Result<bool, Error> {
+    match storage.read::<bool>(constants::NAMED_KEY_SALTED) {
+        Err(Error::MissingKey) => Ok(false),
+        salted => salted,
+    }
+}
+
+/// Only the owner can take the funds back, once the swap timed out.
+pub fn check_refund<B: Runtime + Storage>(backend: &B) -> Result<(), Error> {
+    if !already_initialized(backend)? {
+        return Err(Error::NotInitialized);
+    }
+    if !check_ownership(backend)? {
+        return Err(Error::AccessDenied);
+    }
+    if !is_timed_out(backend)? {
+        return Err(Error::EndTimeNotReached);
+    }
+    Ok(())
+}
+
+/// Only the receiver can agree to release the funds back to the owner before the timeout.
+pub fn check_cancel<B: Runtime + Storage>(backend: &B) -> Result<(), Error> {
+    if !already_initialized(backend)? {
+        return Err(Error::NotInitialized);
+    }
+    if !check_receiver(backend)? {
+        return Err(Error::AccessDenied);
+    }
+    Ok(())
+}
+
+/// The owner asks for a later deadline, which is stored until the receiver accepts it.
+pub fn propose_end_time<B: Runtime + Storage>(backend: &mut B, end_time: u64) -> Result<(), Error> {
+    if !already_initialized(backend)? {
+        return Err(Error::NotInitialized);
+    }
+    if !check_ownership(backend)? {
+        return Err(Error::AccessDenied);
+    }
+    let current_end_time = backend.read::<u64>(constants::NAMED_KEY_END_TIME)?;
+    time::check_extension(backend.block_time(), current_end_time, end_time)?;
+    backend.write(constants::NAMED_KEY_PROPOSED_END_TIME, end_time);
+    Ok(())
+}
+
+/// The receiver accepts the proposed deadline. It passes the value it agrees to, so the proposal
+/// can't be changed under its feet. Returns the previous deadline.
+pub fn accept_end_time<B: Runtime + Storage>(backend: &mut B, end_time: u64) -> Result<u64, Error> {
+    if !already_initialized(backend)? {
+        return Err(Error::NotInitialized);
+    }
+    if !check_receiver(backend)? {
+        return Err(Error::AccessDenied);
+    }
+    let old_end_time = backend.read::<u64>(constants::NAMED_KEY_END_TIME)?;
+    if time::is_timed_out(backend.block_time(), old_end_time) {
+        return Err(Error::EndTimePassed);
+    }
+    let proposed_end_time = backend.read::<u64>(constants::NAMED_KEY_PROPOSED_END_TIME)?;
+    if proposed_end_time == 0 {
+        return Err(Error::EndTimeNotProposed);
+    }
+    if end_time != proposed_end_time {
+        return Err(Error::EndTimeProposalMismatch);
+    }
+    backend.write(constants::NAMED_KEY_END_TIME, end_time);
+    backend.write(constants::NAMED_KEY_PROPOSED_END_TIME, 0u64);
+    Ok(old_end_time)
+}
+
+/// The bytes the receiver signs to authorise an unlock: the contract package hash, the swap id,
+/// the secret and the key that gets the payout.
+pub fn redemption_message(
+    contract_package_hash: ContractPackageHash,
+    swap_id: u64,
+    secret: &str,
+    payout: Key,
+) -> Vec<u8> {
+    (contract_package_hash, swap_id, secret.to_string(), payout)
+        .to_bytes()
+        .unwrap_or_default()
+}
+
+/// Checks the receiver's signature over the redemption message of the current swap.
+pub fn verify_redemption<S: Storage>(
+    storage: &S,
+    public_key: &PublicKey,
+    secret: &str,
+    payout: Key,
+    signature: &Signature,
+) -> Result<(), Error> {
+    let contract_package_hash =
+        storage.read::<ContractPackageHash>(constants::NAMED_KEY_OWN_CONTRACT_PACKAGE_HASH)?;
+    let swap_id = storage.read::<u64>(constants::NAMED_KEY_SWAP_ID)?;
+    let message = redemption_message(contract_package_hash, swap_id, secret, payout);
+    crypto::verify(message, signature, public_key).map_err(|_| Error::InvalidSignature)
+}
@@ -0,0 +1,22 @@
+use crate::Error;
+
+/// The deadline of a swap started at `start_time` that stays open for `timeout` milliseconds.
+pub fn end_time(start_time: u64, timeout: u64) -> Result<u64, Error> {
+    start_time.checked_add(timeout).ok_or(Error::TimeOverflow)
+}
+
+pub fn is_timed_out(current_time: u64, end_time: u64) -> bool {
+    current_time > end_time
+}
+
+/// The deadline can only be pushed back, and only while the swap is still running, so the
+/// receiver never ends up with less time than it agreed to.
+pub fn check_extension(current_time: u64, end_time: u64, new_end_time: u64) -> Result<(), Error> {
+    if is_timed_out(current_time, end_time) {
+        return Err(Error::EndTimePassed);
+    }
+    if new_end_time <= end_time {
+        return Err(Error::EndTimeNotExtended);
+    }
+    Ok(())
+}
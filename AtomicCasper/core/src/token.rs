@@ -0,0 +1,171 @@
+//! Checks on the calls the contract makes to token contracts.
+//!
+//! Token contracts don't agree on what `transfer` returns: the reference ERC-20 and CEP-47
+//! contracts return nothing and revert on failure, others return a `Result` or a `bool`. The
+//! contract takes the raw return value, rejects anything but success, and then checks the tokens
+//! actually moved, since a token may also report success and do nothing.
+//!
+//! A swap refers to its token contract either by contract hash, which pins one version, or by
+//! package hash, so every call goes to the latest version of the package.
+use alloc::vec::Vec;
+
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
+    CLType, CLTyped, ContractHash, ContractPackageHash, Key, U256,
+};
+
+use crate::Error;
+
+const CONTRACT_TAG: u8 = 0;
+const PACKAGE_TAG: u8 = 1;
+
+/// The token contract of a swap, what `initiate` takes as its `contract_hash` or
+/// `contract_package_hash` argument.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TokenContract {
+    /// This version of the contract.
+    Contract(ContractHash),
+    /// Whatever version of the package is the latest at the time of the call.
+    Package(ContractPackageHash),
+}
+
+impl TokenContract {
+    /// The token contract of the `contract_hash` and `contract_package_hash` arguments, exactly
+    /// one of which must be passed.
+    pub fn from_args(
+        contract_hash: Option<ContractHash>,
+        contract_package_hash: Option<ContractPackageHash>,
+    ) -> Result<Self, Error> {
+        match (contract_hash, contract_package_hash) {
+            (Some(contract_hash), None) => Ok(TokenContract::Contract(contract_hash)),
+            (None, Some(package_hash)) => Ok(TokenContract::Package(package_hash)),
+            _ => Err(Error::ContractHashNotSet),
+        }
+    }
+
+    /// The address of the contract or package in global state.
+    pub fn value(&self) -> [u8; 32] {
+        match self {
+            TokenContract::Contract(contract_hash) => contract_hash.value(),
+            TokenContract::Package(package_hash) => package_hash.value(),
+        }
+    }
+}
+
+/// The entry points a token contract must expose to be locked in a swap of `asset_type`: the
+/// ones the contract calls at `initiate` and on payout.
+pub fn entry_points(asset_type: &str) -> &'static [&'static str] {
+    match asset_type {
+        "NFT" => &["owner_of", "transfer"],
+        "ERC-20" | "Custom" => &["balance_of", "transfer"],
+        _ => &[],
+    }
+}
+
+/// The entry points of [`entry_points`] that `exposed` lacks.
+pub fn missing_entry_points<S: AsRef<str>>(asset_type: &str, exposed: &[S]) -> Vec<&'static str> {
+    entry_points(asset_type)
+        .iter()
+        .copied()
+        .filter(|name| !exposed.iter().any(|exposed| exposed.as_ref() == *name))
+        .collect()
+}
+
+/// The serialized value a contract call returned, whatever its type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReturnValue(pub Vec<u8>);
+
+impl CLTyped for ReturnValue {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+impl FromBytes for ReturnValue {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        Ok((ReturnValue(bytes.to_vec()), &[]))
+    }
+}
+
+/// Accepts what a successful `transfer` returns: nothing, `Ok(())` of a `Result` or `true`.
+/// Both `Ok` and `true` serialize to a single `1`.
+pub fn check_transfer_return(returned: &ReturnValue) -> Result<(), Error> {
+    match returned.0.as_slice() {
+        [] | [1] => Ok(()),
+        _ => Err(Error::TokenTransferRejected),
+    }
+}
+
+/// Checks `amount` left `sender` and reached `recipient`, from their balances before and after a
+/// fungible token transfer.
+pub fn check_balances_moved(
+    sender: (U256, U256),
+    recipient: (U256, U256),
+    amount: U256,
+) -> Result<(), Error> {
+    let (sender_before, sender_after) = sender;
+    let (recipient_before, recipient_after) = recipient;
+    if sender_before.checked_sub(sender_after) != Some(amount)
+        || recipient_after.checked_sub(recipient_before) != Some(amount)
+    {
+        return Err(Error::TokenNotTransferred);
+    }
+    Ok(())
+}
+
+/// Checks an NFT belongs to `recipient` after it was transferred.
+pub fn check_owner_moved(owner: Option<Key>, recipient: Key) -> Result<(), Error> {
+    if owner != Some(recipient) {
+        return Err(Error::TokenNotTransferred);
+    }
+    Ok(())
+}
+
+/// The tag, then the 32 bytes of the hash.
+impl CLTyped for TokenContract {
+    fn cl_type() -> CLType {
+        <(u8, [u8; 32])>::cl_type()
+    }
+}
+
+impl ToBytes for TokenContract {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut bytes = bytesrepr::allocate_buffer(self)?;
+        match self {
+            TokenContract::Contract(contract_hash) => {
+                bytes.push(CONTRACT_TAG);
+                bytes.extend(contract_hash.to_bytes()?);
+            }
+            TokenContract::Package(package_hash) => {
+                bytes.push(PACKAGE_TAG);
+                bytes.extend(package_hash.to_bytes()?);
+            }
+        }
+        Ok(bytes)
+    }
+
+    fn serialized_length(&self) -> usize {
+        U8_SERIALIZED_LENGTH
+            + match self {
+                TokenContract::Contract(contract_hash) => contract_hash.serialized_length(),
+                TokenContract::Package(package_hash) => package_hash.serialized_length(),
+            }
+    }
+}
+
+impl FromBytes for TokenContract {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, bytes) = u8::from_bytes(bytes)?;
+        match tag {
+            CONTRACT_TAG => {
+                let (contract_hash, bytes) = ContractHash::from_bytes(bytes)?;
+                Ok((TokenContract::Contract(contract_hash), bytes))
+            }
+            PACKAGE_TAG => {
+                let (package_hash, bytes) = ContractPackageHash::from_bytes(bytes)?;
+                Ok((TokenContract::Package(package_hash), bytes))
+            }
+            _ => Err(bytesrepr::Error::Formatting),
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use casper_types::{account::AccountHash, CLType, CLValue, ContractHash, ContractPackageHash, Key};
+use swap_core::{
+    allowlist::{self, Allowlist, CEP18, CEP47, CEP78},
+    mock::MockBackend,
+    token::TokenContract,
+    Error,
+};
+
+fn admin() -> Key {
+    Key::Account(AccountHash::new([1u8; 32]))
+}
+
+fn token() -> TokenContract {
+    TokenContract::Contract(ContractHash::new([5u8; 32]))
+}
+
+fn backend() -> MockBackend {
+    MockBackend::install(admin(), ContractPackageHash::new([9u8; 32]), "NFT")
+}
+
+#[test]
+fn only_admin_manages_the_allowlist() {
+    let mut backend = backend();
+    backend.caller = Key::Account(AccountHash::new([2u8; 32]));
+    assert_eq!(
+        allowlist::allow(&mut backend, token(), CEP47.to_string()),
+        Err(Error::AccessDenied)
+    );
+    assert_eq!(
+        allowlist::remove(&mut backend, token()),
+        Err(Error::AccessDenied)
+    );
+    assert_eq!(
+        allowlist::enforce(&mut backend, true),
+        Err(Error::AccessDenied)
+    );
+
+    backend.caller = admin();
+    assert_eq!(
+        allowlist::allow(&mut backend, token(), "ERC-721".to_string()),
+        Err(Error::UnknownTokenStandard)
+    );
+    allowlist::allow(&mut backend, token(), CEP78.to_string()).unwrap();
+    allowlist::enforce(&mut backend, true).unwrap();
+    let current = Allowlist::current(&backend).unwrap();
+    assert!(current.enforced);
+    assert_eq!(
+        current.tokens.get(&token()).map(String::as_str),
+        Some(CEP78)
+    );
+    allowlist::remove(&mut backend, token()).unwrap();
+    assert!(Allowlist::current(&backend).unwrap().tokens.is_empty());
+}
+
+#[test]
+fn allowlists_are_typed_cl_values() {
+    let mut backend = backend();
+    allowlist::allow(&mut backend, token(), CEP18.to_string()).unwrap();
+    let current = Allowlist::current(&backend).unwrap();
+    let cl_value = CLValue::from_t(current.clone()).unwrap();
+    assert_eq!(
+        cl_value.cl_type(),
+        &CLType::Tuple2([
+            Box::new(CLType::Bool),
+            Box::new(CLType::Map {
+                key: Box::new(CLType::Tuple2([
+                    Box::new(CLType::U8),
+                    Box::new(CLType::ByteArray(32)),
+                ])),
+                value: Box::new(CLType::String),
+            }),
+        ])
+    );
+    assert_eq!(cl_value.into_t::<Allowlist>().unwrap(), current);
+}
+
+#[test]
+fn checks_tokens_of_swaps() {
+    let mut backend = backend();
+    let look_alike = TokenContract::Contract(ContractHash::new([6u8; 32]));
+    // nothing is checked until tokens are listed or the allowlist is enforced
+    assert_eq!(allowlist::check_token(&backend, "NFT", &look_alike), Ok(()));
+
+    allowlist::allow(&mut backend, token(), CEP47.to_string()).unwrap();
+    assert_eq!(allowlist::check_token(&backend, "NFT", &token()), Ok(()));
+    assert_eq!(allowlist::check_token(&backend, "Custom", &token()), Ok(()));
+    assert_eq!(
+        allowlist::check_token(&backend, "ERC-20", &token()),
+        Err(Error::TokenStandardMismatch)
+    );
+    assert_eq!(allowlist::check_token(&backend, "NFT", &look_alike), Ok(()));
+
+    allowlist::enforce(&mut backend, true).unwrap();
+    assert_eq!(
+        allowlist::check_token(&backend, "NFT", &look_alike),
+        Err(Error::TokenNotAllowed)
+    );
+    // listing a version doesn't list its package
+    let package = TokenContract::Package(ContractPackageHash::new([5u8; 32]));
+    assert_eq!(
+        allowlist::check_token(&backend, "NFT", &package),
+        Err(Error::TokenNotAllowed)
+    );
+    allowlist::allow(&mut backend, look_alike, CEP18.to_string()).unwrap();
+    assert_eq!(
+        allowlist::check_token(&backend, "ERC-20", &look_alike),
+        Ok(())
+    );
+}
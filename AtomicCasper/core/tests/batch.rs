@@ -0,0 +1,92 @@
+use casper_types::{
+    account::AccountHash,
+    bytesrepr::{self, ToBytes},
+    runtime_args, CLType, CLTyped, ContractHash, ContractPackageHash, Key, RuntimeArgs, Signature,
+};
+use swap_core::{
+    batch::{self, Initiation, Redemption},
+    constants,
+    mock::MockBackend,
+    swap, Error,
+};
+
+#[test]
+fn round_trips_batches() {
+    let initiations = vec![Initiation {
+        contract_hash: ContractHash::new([1u8; 32]),
+        args: runtime_args! {
+            constants::ARG_HASH => "abcd".to_string(),
+            constants::ARG_TIMEOUT => 60_000u64,
+        },
+    }];
+    let bytes = bytesrepr::serialize(&initiations).unwrap();
+    assert_eq!(bytes.len(), initiations.serialized_length());
+    assert_eq!(
+        bytesrepr::deserialize::<Vec<Initiation>>(bytes),
+        Ok(initiations)
+    );
+
+    let redemptions = vec![
+        Redemption {
+            contract_hash: ContractHash::new([2u8; 32]),
+            swap_id: 3,
+            secret: "ac3b9e1f".to_string(),
+            signed: None,
+        },
+        Redemption {
+            contract_hash: ContractHash::new([3u8; 32]),
+            swap_id: 1,
+            secret: "5d20c4a7".to_string(),
+            signed: Some((
+                Key::Account(AccountHash::new([4u8; 32])),
+                Signature::ed25519([0u8; 64]).unwrap(),
+            )),
+        },
+    ];
+    let bytes = bytesrepr::serialize(&redemptions).unwrap();
+    assert_eq!(bytes.len(), redemptions.serialized_length());
+    assert_eq!(
+        bytesrepr::deserialize::<Vec<Redemption>>(bytes),
+        Ok(redemptions)
+    );
+}
+
+#[test]
+fn batches_are_typed_cl_values() {
+    assert_eq!(
+        Initiation::cl_type(),
+        CLType::Tuple2([
+            Box::new(CLType::ByteArray(32)),
+            Box::new(CLType::List(Box::new(CLType::U8))),
+        ])
+    );
+    assert_eq!(
+        Redemption::cl_type(),
+        CLType::Tuple2([
+            Box::new(CLType::Tuple3([
+                Box::new(CLType::ByteArray(32)),
+                Box::new(CLType::U64),
+                Box::new(CLType::String),
+            ])),
+            Box::new(CLType::Option(Box::new(CLType::Tuple2([
+                Box::new(CLType::Key),
+                Box::new(CLType::List(Box::new(CLType::U8))),
+            ])))),
+        ])
+    );
+}
+
+#[test]
+fn rejects_empty_batches_and_other_swaps() {
+    assert_eq!(batch::check_size(0), Err(Error::EmptyBatch));
+    assert_eq!(batch::check_size(1), Ok(()));
+
+    let mut backend = MockBackend::install(
+        Key::Account(AccountHash::new([1u8; 32])),
+        ContractPackageHash::new([9u8; 32]),
+        "Custom",
+    );
+    swap::next_swap_id(&mut backend).unwrap();
+    assert_eq!(swap::check_swap_id(&backend, 1), Ok(()));
+    assert_eq!(swap::check_swap_id(&backend, 2), Err(Error::SwapIdMismatch));
+}
@@ -0,0 +1,97 @@
+use casper_types::{account::AccountHash, CLType, CLValue, ContractPackageHash, Key, U512};
+use swap_core::{
+    fee::{self, Fees, MAX_FEE_BPS},
+    mock::MockBackend,
+    Error,
+};
+
+fn admin() -> Key {
+    Key::Account(AccountHash::new([1u8; 32]))
+}
+
+fn treasury() -> Key {
+    Key::Account(AccountHash::new([7u8; 32]))
+}
+
+fn backend() -> MockBackend {
+    MockBackend::install(admin(), ContractPackageHash::new([9u8; 32]), "Direct")
+}
+
+#[test]
+fn only_admin_configures_fees() {
+    let mut backend = backend();
+    backend.caller = Key::Account(AccountHash::new([2u8; 32]));
+    assert_eq!(
+        fee::set_treasury(&mut backend, treasury()),
+        Err(Error::AccessDenied)
+    );
+    assert_eq!(
+        fee::set_rate(&mut backend, "Direct", 10),
+        Err(Error::AccessDenied)
+    );
+}
+
+#[test]
+fn sets_rates_per_asset_type() {
+    let mut backend = backend();
+    assert_eq!(
+        fee::set_rate(&mut backend, "Direct", 10),
+        Err(Error::TreasuryNotSet)
+    );
+    fee::set_treasury(&mut backend, treasury()).unwrap();
+    fee::set_rate(&mut backend, "Direct", 25).unwrap();
+    fee::set_rate(&mut backend, "ERC-20", MAX_FEE_BPS).unwrap();
+    assert_eq!(
+        fee::set_rate(&mut backend, "ERC-20", MAX_FEE_BPS + 1),
+        Err(Error::FeeTooHigh)
+    );
+    assert_eq!(
+        fee::set_rate(&mut backend, "NFT", 10),
+        Err(Error::TypeNotSupported)
+    );
+    assert_eq!(fee::rate(&backend, "Direct"), Ok(25));
+    assert_eq!(fee::rate(&backend, "ERC-20"), Ok(MAX_FEE_BPS));
+    assert_eq!(fee::rate(&backend, "NFT"), Ok(0));
+    let fees = Fees::current(&backend).unwrap();
+    assert_eq!(fees.treasury, Some(treasury()));
+    assert_eq!(fees.rates.len(), 2);
+}
+
+#[test]
+fn fees_are_typed_cl_values() {
+    let mut backend = backend();
+    fee::set_treasury(&mut backend, treasury()).unwrap();
+    fee::set_rate(&mut backend, "Direct", 25).unwrap();
+    let fees = Fees::current(&backend).unwrap();
+    let cl_value = CLValue::from_t(fees.clone()).unwrap();
+    assert_eq!(
+        cl_value.cl_type(),
+        &CLType::Tuple2([
+            Box::new(CLType::Option(Box::new(CLType::Key))),
+            Box::new(CLType::Map {
+                key: Box::new(CLType::String),
+                value: Box::new(CLType::U32),
+            }),
+        ])
+    );
+    assert_eq!(cl_value.into_t::<Fees>().unwrap(), fees);
+}
+
+#[test]
+fn splits_amounts() {
+    assert_eq!(
+        fee::split(U512::from(10_000u64), 25),
+        (U512::from(9_975u64), U512::from(25u64))
+    );
+    // the fee is rounded down
+    assert_eq!(
+        fee::split(U512::from(399u64), 25),
+        (U512::from(399u64), U512::zero())
+    );
+    assert_eq!(
+        fee::split(U512::from(1_000u64), 0),
+        (U512::from(1_000u64), U512::zero())
+    );
+    let (payout, fee) = fee::split(U512::MAX, MAX_FEE_BPS);
+    assert_eq!(payout + fee, U512::MAX);
+}
@@ -0,0 +1,16 @@
+use casper_types::{account::AccountHash, ContractPackageHash, Key};
+use swap_core::{guard, mock::MockBackend, Error};
+
+fn backend() -> MockBackend {
+    let owner = Key::Account(AccountHash::new([1u8; 32]));
+    MockBackend::install(owner, ContractPackageHash::new([9u8; 32]), "NFT")
+}
+
+#[test]
+fn rejects_reentry() {
+    let mut backend = backend();
+    assert_eq!(guard::enter(&mut backend), Ok(()));
+    assert_eq!(guard::enter(&mut backend), Err(Error::Reentrancy));
+    guard::exit(&mut backend);
+    assert_eq!(guard::enter(&mut backend), Ok(()));
+}
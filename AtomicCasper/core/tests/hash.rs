@@ -0,0 +1,46 @@
+use casper_types::ContractPackageHash;
+use swap_core::hash::{check_hash_type, generate_hash, salted_secret, HASH_TYPES};
+
+#[test]
+fn known_digests() {
+    assert_eq!(
+        generate_hash("sha3-256", "abc"),
+        "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532"
+    );
+    assert_eq!(
+        generate_hash("Keccak256", ""),
+        "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+    );
+    assert_eq!(
+        generate_hash("blake2b", ""),
+        "0e5751c026e543b2e8ab2eb06099daa1d1e5df47778f7787faab45cdf12fe3a8"
+    );
+}
+
+#[test]
+fn digest_lengths() {
+    assert_eq!(generate_hash("sha3-512", "secret").len(), 128);
+    assert_eq!(generate_hash("Keccak512", "secret").len(), 128);
+}
+
+#[test]
+fn unsupported_hash_type() {
+    assert!(HASH_TYPES
+        .iter()
+        .all(|hash_type| check_hash_type(hash_type)));
+    assert!(!check_hash_type("sha256"));
+    assert_eq!(generate_hash("sha256", "secret"), "");
+}
+
+#[test]
+fn salted_secrets() {
+    let package = ContractPackageHash::new([0xab; 32]);
+    assert_eq!(
+        salted_secret("secret", package, 7),
+        format!("secret:{}:7", "ab".repeat(32))
+    );
+    assert_ne!(
+        salted_secret("secret", package, 7),
+        salted_secret("secret", package, 8)
+    );
+}
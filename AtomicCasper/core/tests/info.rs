@@ -0,0 +1,107 @@
+use casper_types::{
+    account::AccountHash,
+    bytesrepr::{self, ToBytes},
+    CLType, CLValue, ContractHash, ContractPackageHash, Key, U512,
+};
+use swap_core::{
+    backend::Storage,
+    constants,
+    hash::generate_hash,
+    info::{page, SwapInfo, SwapState, MAX_PAGE_SIZE},
+    mock::MockBackend,
+    swap,
+    token::TokenContract,
+};
+
+fn owner() -> Key {
+    Key::Account(AccountHash::new([1u8; 32]))
+}
+
+fn reciver() -> Key {
+    Key::Account(AccountHash::new([2u8; 32]))
+}
+
+#[test]
+fn reads_current_swap() {
+    let mut backend = MockBackend::install(owner(), ContractPackageHash::new([9u8; 32]), "Direct");
+    backend.block_time = 1_000;
+    backend.write(
+        constants::NAMED_KEY_HASH,
+        generate_hash("sha3-256", "secret"),
+    );
+    backend.write(constants::NAMED_KEY_HASH_TYPE, "sha3-256".to_string());
+    backend.write(constants::NAMED_KEY_RECIVER, reciver());
+    backend.write(constants::NAMED_KEY_AMOUNT, U512::from(42u64));
+    backend.write(constants::NAMED_KEY_SALTED, true);
+    swap::next_swap_id(&mut backend).unwrap();
+    swap::start(&mut backend, 60_000).unwrap();
+
+    let info = SwapInfo::current(&backend, SwapState::Open).unwrap();
+    assert_eq!(
+        info,
+        SwapInfo {
+            swap_id: 1,
+            state: SwapState::Open,
+            asset_type: "Direct".to_string(),
+            token_contract: None,
+            amount: U512::from(42u64),
+            token_ids: Vec::new(),
+            owner: owner(),
+            receiver: reciver(),
+            hash_type: "sha3-256".to_string(),
+            hashlock: generate_hash("sha3-256", "secret"),
+            start_time: 1_000,
+            end_time: 61_000,
+            terms_hash: String::new(),
+            salted: true,
+        }
+    );
+
+    let bytes = info.to_bytes().unwrap();
+    assert_eq!(bytes.len(), info.serialized_length());
+    assert_eq!(bytesrepr::deserialize::<SwapInfo>(bytes).unwrap(), info);
+}
+
+#[test]
+fn records_are_typed_cl_values() {
+    let mut info = SwapInfo::current(
+        &MockBackend::install(owner(), ContractPackageHash::new([9u8; 32]), "Custom"),
+        SwapState::Open,
+    )
+    .unwrap();
+    info.token_contract = Some(TokenContract::Contract(ContractHash::new([7u8; 32])));
+    let cl_value = CLValue::from_t(info.clone()).unwrap();
+    assert!(matches!(cl_value.cl_type(), CLType::Tuple3(_)));
+    assert_eq!(cl_value.into_t::<SwapInfo>().unwrap(), info);
+
+    let token_contract = CLValue::from_t(info.token_contract).unwrap();
+    assert_eq!(
+        token_contract.cl_type(),
+        &CLType::Option(Box::new(CLType::Tuple2([
+            Box::new(CLType::U8),
+            Box::new(CLType::ByteArray(32)),
+        ])))
+    );
+}
+
+#[test]
+fn rejects_unknown_state() {
+    let backend = MockBackend::install(owner(), ContractPackageHash::new([9u8; 32]), "NFT");
+    let mut bytes = SwapInfo::current(&backend, SwapState::Refunded)
+        .unwrap()
+        .to_bytes()
+        .unwrap();
+    bytes[8] = 4;
+    assert!(bytesrepr::deserialize::<SwapInfo>(bytes).is_err());
+}
+
+#[test]
+fn pages() {
+    assert_eq!(page(0, 0, 10), 1..1);
+    assert_eq!(page(3, 0, 10), 1..4);
+    assert_eq!(page(30, 10, 10), 11..21);
+    assert_eq!(page(15, 10, 10), 11..16);
+    assert_eq!(page(5, 10, 10), 11..11);
+    assert_eq!(page(500, 0, 1_000).count() as u64, MAX_PAGE_SIZE);
+    assert!(page(u64::MAX, u64::MAX, u64::MAX).next().is_none());
+}
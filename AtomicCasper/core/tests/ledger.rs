@@ -0,0 +1,40 @@
+use casper_types::{account::AccountHash, ContractPackageHash, Key, U512};
+use swap_core::{ledger, Error};
+
+#[test]
+fn credits_and_debits() {
+    let balance = ledger::credit(U512::zero(), U512::from(10u64)).unwrap();
+    let balance = ledger::credit(balance, U512::from(5u64)).unwrap();
+    assert_eq!(balance, U512::from(15u64));
+    assert_eq!(ledger::debit(balance, U512::from(15u64)), Ok(U512::zero()));
+    assert_eq!(
+        ledger::debit(balance, U512::from(16u64)),
+        Err(Error::NotEnoughBalance)
+    );
+}
+
+#[test]
+fn rejects_zero_and_overflowing_amounts() {
+    assert_eq!(
+        ledger::credit(U512::one(), U512::zero()),
+        Err(Error::InvalidAmount)
+    );
+    assert_eq!(
+        ledger::debit(U512::one(), U512::zero()),
+        Err(Error::InvalidAmount)
+    );
+    assert_eq!(
+        ledger::credit(U512::MAX, U512::one()),
+        Err(Error::InvalidAmount)
+    );
+}
+
+#[test]
+fn item_keys_fit_dictionaries() {
+    let account = Key::Account(AccountHash::new([1u8; 32]));
+    let package = Key::from(ContractPackageHash::new([1u8; 32]));
+    let account_key = ledger::item_key(&account);
+    assert_eq!(account_key.len(), 64);
+    assert_ne!(account_key, ledger::item_key(&package));
+    assert_eq!(account_key, ledger::item_key(&account));
+}
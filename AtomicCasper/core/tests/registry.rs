@@ -0,0 +1,16 @@
+use casper_types::{account::AccountHash, ContractPackageHash, Key};
+use swap_core::{hash::generate_hash, ledger, registry};
+
+#[test]
+fn item_keys_fit_dictionaries() {
+    let owner = Key::Account(AccountHash::new([1u8; 32]));
+    assert_eq!(registry::owner_item_key(&owner), ledger::item_key(&owner));
+    for hash_type in ["sha3-256", "sha3-512"] {
+        let hashlock = generate_hash(hash_type, "secret");
+        let item_key = registry::hashlock_item_key(&hashlock);
+        assert_eq!(item_key.len(), 64);
+        assert_ne!(item_key, hashlock);
+    }
+    let package = ContractPackageHash::new([0xab; 32]);
+    assert_eq!(registry::instance_item_key(package), "ab".repeat(32));
+}
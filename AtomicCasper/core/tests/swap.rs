@@ -0,0 +1,245 @@
+use casper_types::{account::AccountHash, crypto, ContractPackageHash, Key, PublicKey, SecretKey};
+use swap_core::{
+    backend::Storage,
+    constants,
+    hash::{generate_hash, salted_secret},
+    mock::MockBackend,
+    swap, Error,
+};
+
+const SECRET: &str = "my secret";
+const TIMEOUT: u64 = 60_000;
+
+fn owner() -> Key {
+    Key::Account(AccountHash::new([1u8; 32]))
+}
+
+fn reciver() -> Key {
+    Key::Account(AccountHash::new([2u8; 32]))
+}
+
+fn stranger() -> Key {
+    Key::Account(AccountHash::new([3u8; 32]))
+}
+
+/// A `Direct` swap initiated by `owner` at block time 1000, the way `initiate` does it.
+fn initiated() -> MockBackend {
+    let mut backend = MockBackend::install(owner(), ContractPackageHash::new([9u8; 32]), "Direct");
+    backend.block_time = 1_000;
+    swap::check_initiate(&backend, "sha3-256").unwrap();
+    backend.write(constants::NAMED_KEY_HASH, generate_hash("sha3-256", SECRET));
+    backend.write(constants::NAMED_KEY_HASH_TYPE, "sha3-256".to_string());
+    backend.write(constants::NAMED_KEY_RECIVER, reciver());
+    swap::next_swap_id(&mut backend).unwrap();
+    swap::start(&mut backend, TIMEOUT).unwrap();
+    backend
+}
+
+#[test]
+fn initiate_checks() {
+    let mut backend = MockBackend::install(owner(), ContractPackageHash::new([9u8; 32]), "NFT");
+    assert_eq!(
+        swap::check_initiate(&backend, "md5"),
+        Err(Error::HashTypeNotSupported)
+    );
+    backend.caller = stranger();
+    assert_eq!(
+        swap::check_initiate(&backend, "sha3-256"),
+        Err(Error::AccessDenied)
+    );
+
+    let backend = initiated();
+    assert_eq!(
+        swap::check_initiate(&backend, "sha3-256"),
+        Err(Error::ContractAlreadyInitialized)
+    );
+    assert_eq!(
+        backend.read::<u64>(constants::NAMED_KEY_END_TIME),
+        Ok(61_000)
+    );
+    assert_eq!(backend.read::<u64>(constants::NAMED_KEY_SWAP_ID), Ok(1));
+}
+
+#[test]
+fn timeout_overflow() {
+    let mut backend = initiated();
+    backend.block_time = u64::MAX;
+    assert_eq!(swap::start(&mut backend, 1), Err(Error::TimeOverflow));
+}
+
+#[test]
+fn terms_hash_format() {
+    assert!(swap::check_terms_hash("").is_ok());
+    assert!(swap::check_terms_hash(&"ab".repeat(32)).is_ok());
+    for invalid in ["ab".repeat(31), "AB".repeat(32), "zz".repeat(32)] {
+        assert_eq!(
+            swap::check_terms_hash(&invalid),
+            Err(Error::InvalidTermsHash)
+        );
+    }
+}
+
+#[test]
+fn unlock_checks() {
+    let mut backend = initiated();
+    backend.caller = stranger();
+    assert_eq!(
+        swap::check_unlock(&backend, "wrong secret"),
+        Err(Error::HashMismatch)
+    );
+    assert_eq!(swap::check_unlock(&backend, SECRET), Ok(()));
+
+    backend.caller = owner();
+    assert_eq!(
+        swap::check_unlock(&backend, SECRET),
+        Err(Error::AccessDenied)
+    );
+
+    backend.caller = reciver();
+    backend.block_time = 61_001;
+    assert_eq!(
+        swap::check_unlock(&backend, SECRET),
+        Err(Error::EndTimePassed)
+    );
+}
+
+#[test]
+fn salted_unlock() {
+    let mut backend = initiated();
+    let salted = salted_secret(SECRET, ContractPackageHash::new([9u8; 32]), 1);
+    backend.write(
+        constants::NAMED_KEY_HASH,
+        generate_hash("sha3-256", &salted),
+    );
+    backend.write(constants::NAMED_KEY_SALTED, true);
+    backend.caller = stranger();
+    // the hashlock of the bare secret must be the one initiate claimed
+    backend.write(
+        constants::NAMED_KEY_SECRET_HASH,
+        generate_hash("sha3-256", "5d20c4a7"),
+    );
+    assert_eq!(
+        swap::check_unlock(&backend, SECRET),
+        Err(Error::HashMismatch)
+    );
+    backend.write(
+        constants::NAMED_KEY_SECRET_HASH,
+        generate_hash("sha3-256", SECRET),
+    );
+    assert_eq!(swap::check_unlock(&backend, SECRET), Ok(()));
+
+    // the hashlock opens no other swap, not even a later one of the same contract
+    backend.write(constants::NAMED_KEY_SWAP_ID, 2u64);
+    assert_eq!(
+        swap::check_unlock(&backend, SECRET),
+        Err(Error::HashMismatch)
+    );
+    // and a salted swap isn't opened by the hashlock of the bare secret
+    backend.write(constants::NAMED_KEY_HASH, generate_hash("sha3-256", SECRET));
+    assert_eq!(
+        swap::check_unlock(&backend, SECRET),
+        Err(Error::HashMismatch)
+    );
+}
+
+#[test]
+fn refund_checks() {
+    let mut backend = initiated();
+    assert_eq!(swap::check_refund(&backend), Err(Error::EndTimeNotReached));
+    backend.block_time = 61_001;
+    assert_eq!(swap::check_refund(&backend), Ok(()));
+    backend.caller = reciver();
+    assert_eq!(swap::check_refund(&backend), Err(Error::AccessDenied));
+
+    // a finished swap leaves the owner in place and the end time at 0, which reads as timed out
+    backend.caller = owner();
+    backend.write(constants::NAMED_KEY_HASH, String::new());
+    backend.write(constants::NAMED_KEY_END_TIME, 0u64);
+    assert_eq!(swap::check_refund(&backend), Err(Error::NotInitialized));
+}
+
+#[test]
+fn cancel_checks() {
+    let mut backend = MockBackend::install(owner(), ContractPackageHash::new([9u8; 32]), "NFT");
+    assert_eq!(swap::check_cancel(&backend), Err(Error::NotInitialized));
+
+    backend = initiated();
+    assert_eq!(swap::check_cancel(&backend), Err(Error::AccessDenied));
+    backend.caller = reciver();
+    assert_eq!(swap::check_cancel(&backend), Ok(()));
+}
+
+#[test]
+fn end_time_extension() {
+    let mut backend = initiated();
+    assert_eq!(
+        swap::propose_end_time(&mut backend, 61_000),
+        Err(Error::EndTimeNotExtended)
+    );
+    backend.caller = reciver();
+    assert_eq!(
+        swap::accept_end_time(&mut backend, 90_000),
+        Err(Error::EndTimeNotProposed)
+    );
+    assert_eq!(
+        swap::propose_end_time(&mut backend, 90_000),
+        Err(Error::AccessDenied)
+    );
+
+    backend.caller = owner();
+    swap::propose_end_time(&mut backend, 90_000).unwrap();
+    assert_eq!(
+        swap::accept_end_time(&mut backend, 90_000),
+        Err(Error::AccessDenied)
+    );
+    backend.caller = reciver();
+    assert_eq!(
+        swap::accept_end_time(&mut backend, 120_000),
+        Err(Error::EndTimeProposalMismatch)
+    );
+    assert_eq!(swap::accept_end_time(&mut backend, 90_000), Ok(61_000));
+    assert_eq!(
+        backend.read::<u64>(constants::NAMED_KEY_END_TIME),
+        Ok(90_000)
+    );
+    assert_eq!(
+        backend.read::<u64>(constants::NAMED_KEY_PROPOSED_END_TIME),
+        Ok(0)
+    );
+}
+
+#[test]
+fn proposal_after_timeout() {
+    let mut backend = initiated();
+    swap::propose_end_time(&mut backend, 90_000).unwrap();
+    backend.block_time = 61_001;
+    assert_eq!(
+        swap::propose_end_time(&mut backend, 100_000),
+        Err(Error::EndTimePassed)
+    );
+    backend.caller = reciver();
+    assert_eq!(
+        swap::accept_end_time(&mut backend, 90_000),
+        Err(Error::EndTimePassed)
+    );
+}
+
+#[test]
+fn signed_redemption() {
+    let backend = initiated();
+    let secret_key = SecretKey::ed25519_from_bytes([7u8; 32]).unwrap();
+    let public_key = PublicKey::from(&secret_key);
+    let payout = stranger();
+    let message = swap::redemption_message(ContractPackageHash::new([9u8; 32]), 1, SECRET, payout);
+    let signature = crypto::sign(message, &secret_key, &public_key);
+
+    assert_eq!(
+        swap::verify_redemption(&backend, &public_key, SECRET, payout, &signature),
+        Ok(())
+    );
+    // the signature doesn't cover another payout key
+    assert_eq!(
+        swap::verify_redemption(&backend, &public_key, SECRET, reciver(), &signature),
+        Err(Error::InvalidSignature)
+    );
+}
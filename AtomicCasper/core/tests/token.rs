@@ -0,0 +1,124 @@
+use casper_types::{
+    account::AccountHash,
+    bytesrepr::{self, ToBytes},
+    ContractHash, ContractPackageHash, Key, U256,
+};
+use swap_core::{
+    token::{
+        check_balances_moved, check_owner_moved, check_transfer_return, missing_entry_points,
+        ReturnValue, TokenContract,
+    },
+    Error,
+};
+
+fn returned<T: ToBytes>(value: T) -> ReturnValue {
+    bytesrepr::deserialize(value.to_bytes().unwrap()).unwrap()
+}
+
+#[test]
+fn accepts_successful_returns() {
+    assert_eq!(check_transfer_return(&returned(())), Ok(()));
+    assert_eq!(check_transfer_return(&returned(true)), Ok(()));
+    assert_eq!(check_transfer_return(&returned(Ok::<(), u32>(()))), Ok(()));
+}
+
+#[test]
+fn rejects_failed_returns() {
+    assert_eq!(
+        check_transfer_return(&returned(false)),
+        Err(Error::TokenTransferRejected)
+    );
+    assert_eq!(
+        check_transfer_return(&returned(Err::<(), u32>(3))),
+        Err(Error::TokenTransferRejected)
+    );
+    assert_eq!(
+        check_transfer_return(&returned(7u64)),
+        Err(Error::TokenTransferRejected)
+    );
+}
+
+#[test]
+fn checks_balances_moved() {
+    let amount = U256::from(5u64);
+    let moved = check_balances_moved(
+        (U256::from(10u64), U256::from(5u64)),
+        (U256::zero(), amount),
+        amount,
+    );
+    assert_eq!(moved, Ok(()));
+    let untouched = check_balances_moved(
+        (U256::from(10u64), U256::from(10u64)),
+        (U256::zero(), U256::zero()),
+        amount,
+    );
+    assert_eq!(untouched, Err(Error::TokenNotTransferred));
+    let short = check_balances_moved(
+        (U256::from(10u64), U256::from(5u64)),
+        (U256::zero(), U256::from(4u64)),
+        amount,
+    );
+    assert_eq!(short, Err(Error::TokenNotTransferred));
+}
+
+#[test]
+fn checks_owner_moved() {
+    let recipient = Key::Account(AccountHash::new([2u8; 32]));
+    let contract = Key::Hash([9u8; 32]);
+    assert_eq!(check_owner_moved(Some(recipient), recipient), Ok(()));
+    assert_eq!(
+        check_owner_moved(Some(contract), recipient),
+        Err(Error::TokenNotTransferred)
+    );
+    assert_eq!(
+        check_owner_moved(None, recipient),
+        Err(Error::TokenNotTransferred)
+    );
+}
+
+#[test]
+fn takes_either_a_contract_or_a_package() {
+    let contract_hash = ContractHash::new([0x2f; 32]);
+    let package_hash = ContractPackageHash::new([0x3f; 32]);
+    assert_eq!(
+        TokenContract::from_args(Some(contract_hash), None),
+        Ok(TokenContract::Contract(contract_hash))
+    );
+    assert_eq!(
+        TokenContract::from_args(None, Some(package_hash)),
+        Ok(TokenContract::Package(package_hash))
+    );
+    assert_eq!(
+        TokenContract::from_args(None, None),
+        Err(Error::ContractHashNotSet)
+    );
+    assert_eq!(
+        TokenContract::from_args(Some(contract_hash), Some(package_hash)),
+        Err(Error::ContractHashNotSet)
+    );
+}
+
+#[test]
+fn token_contracts_round_trip() {
+    for token in [
+        TokenContract::Contract(ContractHash::new([1u8; 32])),
+        TokenContract::Package(ContractPackageHash::new([1u8; 32])),
+    ] {
+        let bytes = token.to_bytes().unwrap();
+        assert_eq!(bytes.len(), token.serialized_length());
+        assert_eq!(bytesrepr::deserialize::<TokenContract>(bytes), Ok(token));
+    }
+}
+
+#[test]
+fn finds_missing_entry_points() {
+    let cep47 = ["name", "owner_of", "transfer", "mint"];
+    assert!(missing_entry_points("NFT", &cep47).is_empty());
+    assert_eq!(missing_entry_points("ERC-20", &cep47), vec!["balance_of"]);
+    assert_eq!(
+        missing_entry_points("NFT", &["transfer".to_string()]),
+        vec!["owner_of"]
+    );
+    assert_eq!(missing_entry_points("Custom", &cep47), vec!["balance_of"]);
+    assert!(missing_entry_points::<&str>("Direct", &[]).is_empty());
+}
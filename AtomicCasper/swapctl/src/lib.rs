@@ -0,0 +1,1086 @@
+//! `swapctl` builds and signs every deploy of the AtomicSwap lifecycle, so operators never have
+//! to assemble session arguments by hand. Deploys are only written out as JSON, they can be sent
+//! later, from another machine, with `casper-client send-deploy --input <file>`.
+mod parse;
+
+use std::{
+    ffi::OsString,
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, Context, Result};
+use casper_types::{runtime_args, ContractHash, RuntimeArgs, SecretKey};
+use clap::{Args, Parser, Subcommand};
+use swap_client::{
+    args::Asset,
+    constants,
+    derivation::{recover, DerivationContext, OnChainSwap, Seed},
+    generate_secret, hashlock, load_secret_key,
+    rpc::{NodeClient, Transport},
+    Deploy, DeployBuilder, Initiate, SignedTerms, SwapContext, SwapInfo, TokenContract, Unlock,
+    Vault, HASH_TYPES,
+};
+
+#[derive(Parser)]
+#[clap(name = "swapctl", about = "Casper AtomicSwap lifecycle tool")]
+struct Cli {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Generate a new random secret
+    Secret {
+        /// Also print the hashlock of the secret for this hash type
+        #[clap(long)]
+        hash_type: Option<String>,
+        /// Store the secret in this encrypted vault (created if missing) and only print its
+        /// hashlock. The password is read from SWAPCTL_VAULT_PASSWORD, or prompted for.
+        #[clap(long, requires = "hash_type")]
+        vault: Option<PathBuf>,
+        /// The chain of the other leg of the swap, recorded in the vault
+        #[clap(long, default_value = "")]
+        remote_chain: String,
+        /// Deadline of the counterparty's lock in milliseconds, recorded in the vault
+        #[clap(long)]
+        remote_end_time: Option<u64>,
+        #[clap(flatten)]
+        derivation: DerivationOpts,
+    },
+    /// Show the swap contracts the factory installed for an owner
+    Instances {
+        /// e.g. http://<node address>:7777/rpc
+        #[clap(long)]
+        node_address: String,
+        #[clap(long)]
+        factory_hash: String,
+        /// e.g. account-hash-...
+        #[clap(long)]
+        owner: String,
+    },
+    /// Show the swap contract of the factory a hashlock was initiated on
+    FindSwap {
+        /// e.g. http://<node address>:7777/rpc
+        #[clap(long)]
+        node_address: String,
+        #[clap(long)]
+        factory_hash: String,
+        #[clap(long)]
+        hashlock: String,
+    },
+    /// Generate a new master seed for deterministic secrets
+    Seed {
+        /// The file to write the hex encoded seed to, it is never overwritten
+        #[clap(long, short)]
+        output: PathBuf,
+    },
+    /// Find the secrets of our swaps by re-deriving them from the master seed
+    Recover {
+        #[clap(long)]
+        seed: PathBuf,
+        /// e.g. http://<node address>:7777/rpc
+        #[clap(long)]
+        node_address: String,
+        #[clap(long, default_value = "casper-test")]
+        chain_name: String,
+        /// The swap contracts to look at, can be repeated
+        #[clap(long = "contract-hash", required = true)]
+        contract_hashes: Vec<String>,
+        /// How many indexes to try for every swap
+        #[clap(long, default_value = "1000")]
+        gap_limit: u64,
+    },
+    /// Show the swaps a contract ran, or one of them, as JSON
+    Swaps {
+        /// e.g. http://<node address>:7777/rpc
+        #[clap(long)]
+        node_address: String,
+        #[clap(long)]
+        contract_hash: String,
+        #[clap(long)]
+        swap_id: Option<u64>,
+        #[clap(long, default_value = "0")]
+        offset: u64,
+        #[clap(long, default_value = "20")]
+        limit: u64,
+    },
+    /// Show the treasury and fee rates of a contract, as JSON
+    Fees {
+        /// e.g. http://<node address>:7777/rpc
+        #[clap(long)]
+        node_address: String,
+        #[clap(long)]
+        contract_hash: String,
+    },
+    /// Show the token contracts the admin listed, and whether only they are accepted, as JSON
+    Allowlist {
+        /// e.g. http://<node address>:7777/rpc
+        #[clap(long)]
+        node_address: String,
+        #[clap(long)]
+        contract_hash: String,
+    },
+    /// Print the hashlock of a secret, for one or every supported hash type
+    Hashlock {
+        #[clap(long)]
+        secret: String,
+        #[clap(long)]
+        hash_type: Option<String>,
+    },
+    /// Sign swap terms as one of the parties. The file holds `{"terms": {...}}`, or terms the
+    /// other party already signed, and is updated in place
+    SignTerms {
+        #[clap(long)]
+        terms: PathBuf,
+        /// PEM file of the party's key
+        #[clap(long)]
+        secret_key: PathBuf,
+    },
+    /// Check that both parties signed the terms and print their hash. With --contract-hash, also
+    /// check that the swap the contract holds is the given leg of the terms
+    VerifyTerms {
+        #[clap(long)]
+        terms: PathBuf,
+        #[clap(long, requires_all = &["node_address", "leg"])]
+        contract_hash: Option<String>,
+        /// The leg the contract locks: initiator or participant
+        #[clap(long)]
+        leg: Option<String>,
+        #[clap(long)]
+        node_address: Option<String>,
+    },
+    /// Install the contract for one swap type, or the factory that installs them
+    Install {
+        #[clap(flatten)]
+        deploy: DeployOpts,
+        /// NFT, ERC-20, Direct, Custom or Factory
+        #[clap(long = "type")]
+        swap_type: String,
+        #[clap(long, default_value = "deploy/contract.wasm")]
+        wasm: PathBuf,
+    },
+    /// Install a swap contract of one type through the factory
+    CreateSwap {
+        #[clap(flatten)]
+        deploy: DeployOpts,
+        #[clap(long)]
+        factory_hash: String,
+        /// NFT, ERC-20, Direct or Custom
+        #[clap(long = "type")]
+        swap_type: String,
+    },
+    /// Move the asset into the swap contract before initiating it
+    Fund {
+        #[clap(flatten)]
+        deploy: DeployOpts,
+        #[clap(flatten)]
+        asset: AssetOpts,
+        /// Package hash of the swap contract, which receives ERC-20 tokens and NFTs
+        #[clap(long)]
+        contract_package_hash: Option<String>,
+        /// Hash of the swap contract, which CSPR of Direct swaps is deposited into
+        #[clap(long)]
+        contract_hash: Option<String>,
+        /// Session that deposits CSPR from a purse of its own
+        #[clap(long, default_value = "deploy/session.wasm")]
+        session: PathBuf,
+    },
+    /// Lock the asset for the receiver behind a hashlock
+    Initiate {
+        #[clap(flatten)]
+        deploy: DeployOpts,
+        #[clap(long)]
+        contract_hash: String,
+        #[clap(flatten)]
+        asset: AssetOpts,
+        #[clap(long)]
+        receiver: String,
+        /// How long the swap stays open, in milliseconds
+        #[clap(long)]
+        timeout: u64,
+        /// Required unless --terms is given
+        #[clap(long)]
+        hash_type: Option<String>,
+        /// The hashlock, if the secret is kept elsewhere
+        #[clap(long, conflicts_with = "secret")]
+        hash: Option<String>,
+        #[clap(long)]
+        secret: Option<String>,
+        /// Require the receiver's signature to unlock
+        #[clap(long)]
+        receiver_public_key: Option<String>,
+        /// Bind the swap to these signed terms, their hashlock is used if none is given
+        #[clap(long)]
+        terms: Option<PathBuf>,
+        /// Check on this node that the token contract exists and has the entry points the swap
+        /// calls, e.g. http://<node address>:7777/rpc
+        #[clap(long)]
+        node_address: Option<String>,
+        /// Lock with the hashlock of the secret salted with the contract package and swap id, so
+        /// it opens no other swap. --secret needs --node-address to read them, --hash must
+        /// already be salted and needs --secret-hash
+        #[clap(long)]
+        salted: bool,
+        /// With --salted --hash: the hashlock of the bare secret, which the contract claims too
+        #[clap(long, requires_all = &["salted", "hash"])]
+        secret_hash: Option<String>,
+    },
+    /// Initiate swaps on several contracts in one deploy, all or nothing. The file holds a JSON
+    /// array of swaps, each with the `contract_hash`, `type`, `token_contract`, `amount` and
+    /// `token_ids` of its asset, `receiver`, `timeout`, `hash_type` and `hash`
+    InitiateBatch {
+        #[clap(flatten)]
+        deploy: DeployOpts,
+        #[clap(long)]
+        swaps: PathBuf,
+        #[clap(long, default_value = "deploy/batch.wasm")]
+        session: PathBuf,
+    },
+    /// Claim several swaps in one deploy, all or nothing
+    UnlockBatch {
+        #[clap(flatten)]
+        deploy: DeployOpts,
+        /// <contract hash>:<swap id>:<secret>, and :<payout>:<signature> for signed swaps, can be
+        /// repeated
+        #[clap(long = "swap", required = true)]
+        swaps: Vec<String>,
+        #[clap(long, default_value = "deploy/batch.wasm")]
+        session: PathBuf,
+    },
+    /// Claim the asset with the secret
+    Unlock {
+        #[clap(flatten)]
+        deploy: DeployOpts,
+        #[clap(long)]
+        contract_hash: String,
+        #[clap(long)]
+        secret: String,
+        /// For signed swaps: the key that gets the payout
+        #[clap(long, requires = "signature")]
+        payout: Option<String>,
+        /// For signed swaps: the receiver's signature, hex encoded
+        #[clap(long, requires = "payout")]
+        signature: Option<String>,
+        /// Before revealing the secret, check that the swap is the participant's leg of these
+        /// signed terms
+        #[clap(long, requires = "node_address")]
+        terms: Option<PathBuf>,
+        #[clap(long)]
+        node_address: Option<String>,
+    },
+    /// Get the asset back after the timeout
+    Refund {
+        #[clap(flatten)]
+        deploy: DeployOpts,
+        #[clap(long)]
+        contract_hash: String,
+    },
+    /// Take back deposited CSPR that isn't locked in a swap
+    Withdraw {
+        #[clap(flatten)]
+        deploy: DeployOpts,
+        #[clap(long)]
+        contract_hash: String,
+        /// In motes
+        #[clap(long)]
+        amount: String,
+    },
+    /// Set the fee of one asset type, as the admin
+    SetFee {
+        #[clap(flatten)]
+        deploy: DeployOpts,
+        #[clap(long)]
+        contract_hash: String,
+        /// Direct or ERC-20
+        #[clap(long = "type")]
+        asset_type: String,
+        /// In basis points, at most 1000
+        #[clap(long)]
+        fee_bps: u32,
+    },
+    /// Set the key fees are paid to, as the admin
+    SetTreasury {
+        #[clap(flatten)]
+        deploy: DeployOpts,
+        #[clap(long)]
+        contract_hash: String,
+        #[clap(long)]
+        treasury: String,
+    },
+    /// List a token contract and its standard, as the admin
+    AllowToken {
+        #[clap(flatten)]
+        deploy: DeployOpts,
+        #[clap(long)]
+        contract_hash: String,
+        /// A package hash lists the package, calls to whatever version is the latest
+        #[clap(long)]
+        token_contract: String,
+        /// CEP-18, CEP-47 or CEP-78
+        #[clap(long)]
+        standard: String,
+    },
+    /// Take a token contract off the allowlist, as the admin
+    RemoveToken {
+        #[clap(flatten)]
+        deploy: DeployOpts,
+        #[clap(long)]
+        contract_hash: String,
+        #[clap(long)]
+        token_contract: String,
+    },
+    /// Only accept listed token contracts in new swaps, as the admin
+    EnforceAllowlist {
+        #[clap(flatten)]
+        deploy: DeployOpts,
+        #[clap(long)]
+        contract_hash: String,
+        /// Accept unlisted token contracts again
+        #[clap(long)]
+        off: bool,
+    },
+    /// Release the asset back to the owner before the timeout, as the receiver
+    Cancel {
+        #[clap(flatten)]
+        deploy: DeployOpts,
+        #[clap(long)]
+        contract_hash: String,
+    },
+}
+
+#[derive(Args)]
+struct DerivationOpts {
+    /// Derive the secret from the master seed in this file instead of generating a random one
+    #[clap(long, requires_all = &["index", "counterparty"])]
+    seed: Option<PathBuf>,
+    /// Index of the swap with this counterparty
+    #[clap(long)]
+    index: Option<u64>,
+    /// The receiver of the swap, e.g. account-hash-...
+    #[clap(long)]
+    counterparty: Option<String>,
+    #[clap(long, default_value = "casper-test")]
+    chain_name: String,
+}
+
+impl DerivationOpts {
+    fn secret(&self) -> Result<String> {
+        let seed_path = match &self.seed {
+            Some(seed_path) => seed_path,
+            None => return Ok(generate_secret()),
+        };
+        let seed = read_seed(seed_path)?;
+        let context = DerivationContext {
+            counterparty: self.counterparty.clone().unwrap_or_default(),
+            chain: self.chain_name.clone(),
+        };
+        Ok(seed.derive_secret(self.index.unwrap_or_default(), &context))
+    }
+}
+
+#[derive(Args)]
+struct DeployOpts {
+    /// casper or casper-test
+    #[clap(long, default_value = "casper-test")]
+    chain_name: String,
+    /// In motes
+    #[clap(long)]
+    payment_amount: String,
+    /// PEM file of the key that signs the deploy
+    #[clap(long)]
+    secret_key: PathBuf,
+    /// Write the deploy to this file instead of stdout
+    #[clap(long, short)]
+    output: Option<PathBuf>,
+}
+
+impl DeployOpts {
+    fn builder(&self) -> Result<(DeployBuilder, SecretKey)> {
+        let payment_amount = parse::u512(&self.payment_amount)?;
+        let secret_key = load_secret_key(&self.secret_key)?;
+        Ok((
+            DeployBuilder::new(&self.chain_name, payment_amount),
+            secret_key,
+        ))
+    }
+}
+
+#[derive(Args)]
+struct AssetOpts {
+    /// NFT, ERC-20, Direct or Custom
+    #[clap(long = "type")]
+    swap_type: String,
+    /// Hash of the token contract, for NFT, ERC-20 and Custom swaps. A package hash
+    /// (`contract-package-...`) makes the swap call its latest version
+    #[clap(long)]
+    token_contract: Option<String>,
+    /// Motes for Direct swaps, tokens for ERC-20 swaps
+    #[clap(long)]
+    amount: Option<String>,
+    /// For NFT swaps, can be repeated
+    #[clap(long = "token-id")]
+    token_ids: Vec<String>,
+}
+
+impl AssetOpts {
+    fn asset(&self) -> Result<Asset> {
+        let token_contract = || {
+            let token_contract = self
+                .token_contract
+                .as_ref()
+                .ok_or_else(|| anyhow!("--token-contract is required for {}", self.swap_type))?;
+            parse::token_contract(token_contract)
+        };
+        let amount = || {
+            self.amount
+                .clone()
+                .ok_or_else(|| anyhow!("--amount is required for {}", self.swap_type))
+        };
+        let asset = match self.swap_type.as_str() {
+            "NFT" => Asset::Nft {
+                token_contract: token_contract()?,
+                token_ids: self
+                    .token_ids
+                    .iter()
+                    .map(|token_id| parse::u256(token_id))
+                    .collect::<Result<_>>()?,
+            },
+            "ERC-20" => Asset::Erc20 {
+                token_contract: token_contract()?,
+                amount: parse::u256(&amount()?)?,
+            },
+            "Direct" => Asset::Direct {
+                amount: parse::u512(&amount()?)?,
+            },
+            "Custom" => Asset::Custom {
+                token_contract: token_contract()?,
+            },
+            other => return Err(anyhow!("unsupported type {}", other)),
+        };
+        Ok(asset)
+    }
+}
+
+/// Runs the command line `args`, the program name first. Nodes are reached through the transport
+/// `connect` returns for a `--node-address`, and what the command prints goes to `out`.
+pub fn run<A, T, C>(args: A, connect: C, out: &mut dyn Write) -> Result<()>
+where
+    A: IntoIterator,
+    A::Item: Into<OsString> + Clone,
+    T: Transport,
+    C: Fn(&str) -> T,
+{
+    let cli = Cli::parse_from(args);
+    match cli.command {
+        Command::Secret {
+            hash_type,
+            vault: Some(vault),
+            remote_chain,
+            remote_end_time,
+            derivation,
+        } => {
+            let hash_type = hash_type.ok_or_else(|| anyhow!("--hash-type is required"))?;
+            let secret = derivation.secret()?;
+            let password = vault_password()?;
+            let mut vault = if vault.exists() {
+                Vault::open(&vault, &password)?
+            } else {
+                Vault::create(&vault, &password)?
+            };
+            let context = SwapContext {
+                local_chain: derivation.chain_name,
+                remote_chain,
+                remote_end_time,
+                ..Default::default()
+            };
+            let record = vault.insert(&secret, &hash_type, context)?;
+            writeln!(out, "{}: {}", hash_type, record.hashlock)?;
+        }
+        Command::Secret {
+            hash_type,
+            vault: None,
+            derivation,
+            ..
+        } => {
+            let secret = derivation.secret()?;
+            writeln!(out, "secret: {}", secret)?;
+            if let Some(hash_type) = hash_type {
+                writeln!(out, "{}: {}", hash_type, hashlock(&hash_type, &secret)?)?;
+            }
+        }
+        Command::Instances {
+            node_address,
+            factory_hash,
+            owner,
+        } => {
+            let node = NodeClient::new(connect(&node_address));
+            let instances =
+                node.instances(parse::contract_hash(&factory_hash)?, parse::key(&owner)?)?;
+            for instance in instances {
+                writeln!(out, "{}", instance)?;
+            }
+        }
+        Command::FindSwap {
+            node_address,
+            factory_hash,
+            hashlock,
+        } => {
+            let node = NodeClient::new(connect(&node_address));
+            writeln!(
+                out,
+                "{}",
+                node.find_swap(parse::contract_hash(&factory_hash)?, &hashlock)?
+            )?;
+        }
+        Command::Seed { output } => {
+            let mut file = fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&output)
+                .with_context(|| format!("failed to create {}", output.display()))?;
+            writeln!(file, "{}", Seed::generate().to_hex())?;
+            file.sync_all()?;
+            eprintln!("seed written to {}, back it up", output.display());
+        }
+        Command::Recover {
+            seed,
+            node_address,
+            chain_name,
+            contract_hashes,
+            gap_limit,
+        } => {
+            let seed = read_seed(&seed)?;
+            let node = NodeClient::new(connect(&node_address));
+            let mut swaps = Vec::new();
+            for contract_hash in contract_hashes {
+                let parsed = parse::contract_hash(&contract_hash)?;
+                let history = node.history(parsed)?;
+                if history.is_empty() {
+                    eprintln!("{}: no swaps", contract_hash);
+                }
+                // salted swaps hash the secret bound to the package of the contract
+                let (contract_package_hash, _next_swap_id) = node.salt(parsed)?;
+                swaps.extend(history.iter().map(|info| {
+                    OnChainSwap::from_info(&contract_hash, contract_package_hash, info, &chain_name)
+                }));
+            }
+            let recovered = recover(&seed, &swaps, gap_limit);
+            for secret in &recovered {
+                writeln!(
+                    out,
+                    "{} swap {} index {} secret {}",
+                    secret.swap.contract_hash, secret.swap.swap_id, secret.index, secret.secret
+                )?;
+            }
+            eprintln!("recovered {} of {} swaps", recovered.len(), swaps.len());
+        }
+        Command::Swaps {
+            node_address,
+            contract_hash,
+            swap_id,
+            offset,
+            limit,
+        } => {
+            let node = NodeClient::new(connect(&node_address));
+            let contract_hash = parse::contract_hash(&contract_hash)?;
+            let swaps = match swap_id {
+                Some(swap_id) => vec![node.swap(contract_hash, swap_id)?],
+                None => node.swaps(contract_hash, offset, limit)?,
+            };
+            for swap in &swaps {
+                writeln!(out, "{}", serde_json::to_string_pretty(&swap_json(swap))?)?;
+            }
+        }
+        Command::Fees {
+            node_address,
+            contract_hash,
+        } => {
+            let node = NodeClient::new(connect(&node_address));
+            let fees = node.fees(parse::contract_hash(&contract_hash)?)?;
+            let fees = serde_json::json!({
+                "treasury": fees.treasury.map(|treasury| treasury.to_formatted_string()),
+                "rates": fees.rates,
+            });
+            writeln!(out, "{}", serde_json::to_string_pretty(&fees)?)?;
+        }
+        Command::Allowlist {
+            node_address,
+            contract_hash,
+        } => {
+            let node = NodeClient::new(connect(&node_address));
+            let allowlist = node.allowlist(parse::contract_hash(&contract_hash)?)?;
+            let tokens: serde_json::Map<_, _> = allowlist
+                .tokens
+                .iter()
+                .map(|(token, standard)| (format_token_contract(token), standard.clone().into()))
+                .collect();
+            let allowlist = serde_json::json!({
+                "enforced": allowlist.enforced,
+                "tokens": tokens,
+            });
+            writeln!(out, "{}", serde_json::to_string_pretty(&allowlist)?)?;
+        }
+        Command::Hashlock { secret, hash_type } => match hash_type {
+            Some(hash_type) => writeln!(out, "{}", hashlock(&hash_type, &secret)?)?,
+            None => {
+                for hash_type in HASH_TYPES.iter() {
+                    writeln!(out, "{}: {}", hash_type, hashlock(hash_type, &secret)?)?;
+                }
+            }
+        },
+        Command::SignTerms { terms, secret_key } => {
+            let mut signed = read_terms(&terms)?;
+            signed.sign(&load_secret_key(&secret_key)?)?;
+            fs::write(&terms, serde_json::to_string_pretty(&signed)?)?;
+            match signed.verify() {
+                Ok(hash) => writeln!(out, "signed by both parties, terms hash {}", hash)?,
+                Err(_) => eprintln!("signed, waiting for the other party"),
+            }
+        }
+        Command::VerifyTerms {
+            terms,
+            contract_hash,
+            leg,
+            node_address,
+        } => {
+            let signed = read_terms(&terms)?;
+            let hash = signed.verify()?;
+            if let (Some(contract_hash), Some(leg), Some(node_address)) =
+                (contract_hash, leg, node_address)
+            {
+                let leg = match leg.as_str() {
+                    "initiator" => &signed.terms.initiator,
+                    "participant" => &signed.terms.participant,
+                    _ => return Err(anyhow!("--leg is initiator or participant, not {}", leg)),
+                };
+                NodeClient::new(connect(&node_address)).check_terms(
+                    parse::contract_hash(&contract_hash)?,
+                    &signed.terms,
+                    leg,
+                )?;
+            }
+            writeln!(out, "{}", hash)?;
+        }
+        Command::Install {
+            deploy,
+            swap_type,
+            wasm,
+        } => {
+            let (builder, secret_key) = deploy.builder()?;
+            let wasm_bytes =
+                fs::read(&wasm).with_context(|| format!("failed to read {}", wasm.display()))?;
+            write_deploy(
+                &deploy,
+                &builder.install(wasm_bytes, &swap_type, &secret_key),
+                out,
+            )?;
+        }
+        Command::CreateSwap {
+            deploy,
+            factory_hash,
+            swap_type,
+        } => {
+            let (builder, secret_key) = deploy.builder()?;
+            let factory_hash = parse::contract_hash(&factory_hash)?;
+            write_deploy(
+                &deploy,
+                &builder.create_swap(factory_hash, &swap_type, &secret_key),
+                out,
+            )?;
+        }
+        Command::Fund {
+            deploy,
+            asset,
+            contract_package_hash,
+            contract_hash,
+            session,
+        } => {
+            let (builder, secret_key) = deploy.builder()?;
+            let fund = match asset.asset()? {
+                Asset::Direct { amount } => {
+                    let contract_hash = contract_hash
+                        .ok_or_else(|| anyhow!("--contract-hash is required for Direct"))?;
+                    let session_bytes = fs::read(&session)
+                        .with_context(|| format!("failed to read {}", session.display()))?;
+                    builder.deposit(
+                        session_bytes,
+                        parse::contract_hash(&contract_hash)?,
+                        amount,
+                        &secret_key,
+                    )
+                }
+                Asset::Custom { .. } => {
+                    return Err(anyhow!(
+                        "Custom swaps are funded through their own contract"
+                    ))
+                }
+                token => {
+                    let recipient = contract_package_hash.ok_or_else(|| {
+                        anyhow!(
+                            "--contract-package-hash is required for {}",
+                            token.swap_type()
+                        )
+                    })?;
+                    let (token_contract, args) = token_transfer(&token, &recipient)?;
+                    builder.call_token(token_contract, "transfer", args, &secret_key)
+                }
+            };
+            write_deploy(&deploy, &fund, out)?;
+        }
+        Command::Initiate {
+            deploy,
+            contract_hash,
+            asset,
+            receiver,
+            timeout,
+            hash_type,
+            hash,
+            secret,
+            receiver_public_key,
+            terms,
+            node_address,
+            salted,
+            secret_hash,
+        } => {
+            let (builder, secret_key) = deploy.builder()?;
+            let contract_hash = parse::contract_hash(&contract_hash)?;
+            let asset = asset.asset()?;
+            let node = node_address.map(|node_address| NodeClient::new(connect(&node_address)));
+            if let (Some(node), Some(token_contract)) = (&node, asset.token_contract()) {
+                node.check_token_contract(token_contract, asset.swap_type())?;
+            }
+            let mut initiate = Initiate::new(asset, parse::key(&receiver)?, timeout);
+            let hash_type = || {
+                hash_type
+                    .clone()
+                    .ok_or_else(|| anyhow!("--hash-type is required"))
+            };
+            initiate = match (hash, secret) {
+                (Some(hash), _) if salted => {
+                    let secret_hash = secret_hash
+                        .ok_or_else(|| anyhow!("--salted --hash needs --secret-hash"))?;
+                    initiate
+                        .with_hashlock(&hash_type()?, &hash)
+                        .salted(&secret_hash)
+                }
+                (Some(hash), _) => initiate.with_hashlock(&hash_type()?, &hash),
+                (None, Some(secret)) if salted => {
+                    let node = node
+                        .as_ref()
+                        .ok_or_else(|| anyhow!("--salted --secret needs --node-address"))?;
+                    let (contract_package_hash, swap_id) = node.salt(contract_hash)?;
+                    initiate.with_salted_secret(
+                        &hash_type()?,
+                        &secret,
+                        contract_package_hash,
+                        swap_id,
+                    )?
+                }
+                (None, Some(secret)) => initiate.with_secret(&hash_type()?, &secret)?,
+                (None, None) if salted => return Err(anyhow!("--salted needs --hash or --secret")),
+                (None, None) if terms.is_some() => initiate,
+                (None, None) => return Err(anyhow!("either --hash or --secret is required")),
+            };
+            if let Some(terms) = terms {
+                let terms = read_terms(&terms)?;
+                terms.verify()?;
+                initiate = initiate.with_terms(&terms.terms);
+            }
+            if let Some(public_key) = receiver_public_key {
+                initiate = initiate.with_receiver_public_key(parse::public_key(&public_key)?);
+            }
+            write_deploy(
+                &deploy,
+                &builder.initiate(contract_hash, &initiate, &secret_key)?,
+                out,
+            )?;
+        }
+        Command::InitiateBatch {
+            deploy,
+            swaps,
+            session,
+        } => {
+            let (builder, secret_key) = deploy.builder()?;
+            let swaps = read_batch(&swaps)?;
+            let wasm = fs::read(&session)
+                .with_context(|| format!("failed to read {}", session.display()))?;
+            write_deploy(
+                &deploy,
+                &builder.initiate_batch(wasm, &swaps, &secret_key)?,
+                out,
+            )?;
+        }
+        Command::UnlockBatch {
+            deploy,
+            swaps,
+            session,
+        } => {
+            let (builder, secret_key) = deploy.builder()?;
+            let redemptions = swaps
+                .iter()
+                .map(|swap| parse::redemption(swap))
+                .collect::<Result<_>>()?;
+            let wasm = fs::read(&session)
+                .with_context(|| format!("failed to read {}", session.display()))?;
+            write_deploy(
+                &deploy,
+                &builder.unlock_batch(wasm, redemptions, &secret_key),
+                out,
+            )?;
+        }
+        Command::Unlock {
+            deploy,
+            contract_hash,
+            secret,
+            payout,
+            signature,
+            terms,
+            node_address,
+        } => {
+            let (builder, secret_key) = deploy.builder()?;
+            let mut unlock = Unlock::new(&secret);
+            if let (Some(payout), Some(signature)) = (payout, signature) {
+                unlock = unlock.signed(parse::key(&payout)?, parse::signature(&signature)?);
+            }
+            let contract_hash = parse::contract_hash(&contract_hash)?;
+            if let (Some(terms), Some(node_address)) = (terms, node_address) {
+                let signed = read_terms(&terms)?;
+                signed.verify()?;
+                NodeClient::new(connect(&node_address)).check_terms(
+                    contract_hash,
+                    &signed.terms,
+                    &signed.terms.participant,
+                )?;
+            }
+            write_deploy(
+                &deploy,
+                &builder.unlock(contract_hash, &unlock, &secret_key)?,
+                out,
+            )?;
+        }
+        Command::Refund {
+            deploy,
+            contract_hash,
+        } => {
+            let (builder, secret_key) = deploy.builder()?;
+            let contract_hash = parse::contract_hash(&contract_hash)?;
+            write_deploy(&deploy, &builder.refund(contract_hash, &secret_key), out)?;
+        }
+        Command::Withdraw {
+            deploy,
+            contract_hash,
+            amount,
+        } => {
+            let (builder, secret_key) = deploy.builder()?;
+            let contract_hash = parse::contract_hash(&contract_hash)?;
+            write_deploy(
+                &deploy,
+                &builder.withdraw(contract_hash, parse::u512(&amount)?, &secret_key),
+                out,
+            )?;
+        }
+        Command::SetFee {
+            deploy,
+            contract_hash,
+            asset_type,
+            fee_bps,
+        } => {
+            let (builder, secret_key) = deploy.builder()?;
+            let contract_hash = parse::contract_hash(&contract_hash)?;
+            write_deploy(
+                &deploy,
+                &builder.set_fee(contract_hash, &asset_type, fee_bps, &secret_key),
+                out,
+            )?;
+        }
+        Command::SetTreasury {
+            deploy,
+            contract_hash,
+            treasury,
+        } => {
+            let (builder, secret_key) = deploy.builder()?;
+            let contract_hash = parse::contract_hash(&contract_hash)?;
+            write_deploy(
+                &deploy,
+                &builder.set_treasury(contract_hash, parse::key(&treasury)?, &secret_key),
+                out,
+            )?;
+        }
+        Command::AllowToken {
+            deploy,
+            contract_hash,
+            token_contract,
+            standard,
+        } => {
+            let (builder, secret_key) = deploy.builder()?;
+            let contract_hash = parse::contract_hash(&contract_hash)?;
+            let token_contract = parse::token_contract(&token_contract)?;
+            write_deploy(
+                &deploy,
+                &builder.allow_token(contract_hash, token_contract, &standard, &secret_key),
+                out,
+            )?;
+        }
+        Command::RemoveToken {
+            deploy,
+            contract_hash,
+            token_contract,
+        } => {
+            let (builder, secret_key) = deploy.builder()?;
+            let contract_hash = parse::contract_hash(&contract_hash)?;
+            let token_contract = parse::token_contract(&token_contract)?;
+            write_deploy(
+                &deploy,
+                &builder.remove_token(contract_hash, token_contract, &secret_key),
+                out,
+            )?;
+        }
+        Command::EnforceAllowlist {
+            deploy,
+            contract_hash,
+            off,
+        } => {
+            let (builder, secret_key) = deploy.builder()?;
+            let contract_hash = parse::contract_hash(&contract_hash)?;
+            write_deploy(
+                &deploy,
+                &builder.enforce_allowlist(contract_hash, !off, &secret_key),
+                out,
+            )?;
+        }
+        Command::Cancel {
+            deploy,
+            contract_hash,
+        } => {
+            let (builder, secret_key) = deploy.builder()?;
+            let contract_hash = parse::contract_hash(&contract_hash)?;
+            write_deploy(&deploy, &builder.cancel(contract_hash, &secret_key), out)?;
+        }
+    }
+    Ok(())
+}
+
+fn read_seed(path: &Path) -> Result<Seed> {
+    let seed =
+        fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
+    Ok(Seed::from_hex(&seed)?)
+}
+
+fn swap_json(swap: &SwapInfo) -> serde_json::Value {
+    serde_json::json!({
+        "swap_id": swap.swap_id,
+        "state": format!("{:?}", swap.state),
+        "type": swap.asset_type,
+        "token_contract": swap.token_contract.as_ref().map(format_token_contract),
+        "amount": swap.amount.to_string(),
+        "token_ids": swap.token_ids.iter().map(ToString::to_string).collect::<Vec<_>>(),
+        "owner": swap.owner.to_formatted_string(),
+        "receiver": swap.receiver.to_formatted_string(),
+        "hash_type": swap.hash_type,
+        "hashlock": swap.hashlock,
+        "start_time": swap.start_time,
+        "end_time": swap.end_time,
+        "terms_hash": swap.terms_hash,
+        "salted": swap.salted,
+    })
+}
+
+/// The inverse of `parse::token_contract`.
+fn format_token_contract(token_contract: &TokenContract) -> String {
+    match token_contract {
+        TokenContract::Contract(contract_hash) => contract_hash.to_formatted_string(),
+        TokenContract::Package(package_hash) => package_hash.to_formatted_string(),
+    }
+}
+
+fn read_terms(path: &Path) -> Result<SignedTerms> {
+    let json = fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+    Ok(serde_json::from_slice(&json)?)
+}
+
+/// The swaps of an `initiate-batch` file.
+fn read_batch(path: &Path) -> Result<Vec<(ContractHash, Initiate)>> {
+    let json = fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+    let swaps: Vec<serde_json::Value> = serde_json::from_slice(&json)?;
+    swaps
+        .iter()
+        .map(|swap| {
+            let field = |name: &str| {
+                swap[name]
+                    .as_str()
+                    .map(str::to_string)
+                    .ok_or_else(|| anyhow!("a swap has no {}", name))
+            };
+            let asset = AssetOpts {
+                swap_type: field("type")?,
+                token_contract: field("token_contract").ok(),
+                amount: field("amount").ok(),
+                token_ids: swap["token_ids"]
+                    .as_array()
+                    .map(|ids| {
+                        ids.iter()
+                            .filter_map(|id| id.as_str().map(str::to_string))
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+            };
+            let timeout = swap["timeout"]
+                .as_u64()
+                .ok_or_else(|| anyhow!("a swap has no timeout"))?;
+            let initiate = Initiate::new(asset.asset()?, parse::key(&field("receiver")?)?, timeout)
+                .with_hashlock(&field("hash_type")?, &field("hash")?);
+            Ok((parse::contract_hash(&field("contract_hash")?)?, initiate))
+        })
+        .collect()
+}
+
+fn vault_password() -> Result<String> {
+    match std::env::var("SWAPCTL_VAULT_PASSWORD") {
+        Ok(password) => Ok(password),
+        Err(_) => Ok(rpassword::prompt_password_stderr("Vault password: ")?),
+    }
+}
+
+/// The `transfer` call that moves ERC-20 tokens or NFTs to the swap contract. Tokens must be
+/// transferred, not approved, before `initiate`.
+fn token_transfer(asset: &Asset, recipient: &str) -> Result<(TokenContract, RuntimeArgs)> {
+    let recipient = parse::key(recipient)?;
+    match asset {
+        Asset::Erc20 {
+            token_contract,
+            amount,
+        } => Ok((
+            *token_contract,
+            runtime_args! {
+                "recipient" => recipient,
+                constants::ARG_AMOUNT => *amount,
+            },
+        )),
+        Asset::Nft {
+            token_contract,
+            token_ids,
+        } => Ok((
+            *token_contract,
+            runtime_args! {
+                "recipient" => recipient,
+                constants::ARG_TOKEN_IDS => token_ids.clone(),
+            },
+        )),
+        _ => Err(anyhow!("{} is not a token swap", asset.swap_type())),
+    }
+}
+
+fn write_deploy(opts: &DeployOpts, deploy: &Deploy, out: &mut dyn Write) -> Result<()> {
+    let json = serde_json::to_string_pretty(deploy)?;
+    match &opts.output {
+        Some(path) => {
+            fs::write(path, json).with_context(|| format!("failed to write {}", path.display()))?;
+            eprintln!("deploy written to {}", path.display());
+        }
+        None => writeln!(out, "{}", json)?,
+    }
+    Ok(())
+}
@@ -0,0 +1,8 @@
+use std::io;
+
+use anyhow::Result;
+use swap_client::rpc::HttpTransport;
+
+fn main() -> Result<()> {
+    swapctl::run(std::env::args_os(), HttpTransport::new, &mut io::stdout())
+}
@@ -0,0 +1,89 @@
+//! Parsing of the formatted strings casper tools print.
+use anyhow::{anyhow, Context, Result};
+use casper_types::{
+    AsymmetricType, ContractHash, ContractPackageHash, Key, PublicKey, Signature, U256, U512,
+};
+use swap_client::{Redemption, TokenContract};
+
+/// Accepts `contract-<hex>`, `hash-<hex>` or plain hex.
+pub fn contract_hash(value: &str) -> Result<ContractHash> {
+    if let Ok(contract_hash) = ContractHash::from_formatted_str(value) {
+        return Ok(contract_hash);
+    }
+    let hex = value.strip_prefix("hash-").unwrap_or(value);
+    let bytes = hex_32(hex).with_context(|| format!("invalid contract hash {}", value))?;
+    Ok(ContractHash::new(bytes))
+}
+
+/// A package as `contract-package-wasm<hex>` or `contract-package-<hex>`, calls then go to its
+/// latest version, otherwise a contract hash.
+pub fn token_contract(value: &str) -> Result<TokenContract> {
+    if let Ok(package_hash) = ContractPackageHash::from_formatted_str(value) {
+        return Ok(TokenContract::Package(package_hash));
+    }
+    if let Some(hex) = value.strip_prefix("contract-package-") {
+        let bytes = hex_32(hex).with_context(|| format!("invalid package hash {}", value))?;
+        return Ok(TokenContract::Package(ContractPackageHash::new(bytes)));
+    }
+    Ok(TokenContract::Contract(contract_hash(value)?))
+}
+
+/// Accepts any formatted key, e.g. `account-hash-<hex>` or `hash-<hex>`.
+pub fn key(value: &str) -> Result<Key> {
+    Key::from_formatted_str(value).map_err(|error| anyhow!("invalid key {}: {:?}", value, error))
+}
+
+pub fn public_key(value: &str) -> Result<PublicKey> {
+    PublicKey::from_hex(value).map_err(|error| anyhow!("invalid public key {}: {:?}", value, error))
+}
+
+pub fn signature(value: &str) -> Result<Signature> {
+    Signature::from_hex(value).map_err(|error| anyhow!("invalid signature {}: {:?}", value, error))
+}
+
+pub fn u512(value: &str) -> Result<U512> {
+    U512::from_dec_str(value).map_err(|error| anyhow!("invalid amount {}: {:?}", value, error))
+}
+
+pub fn u256(value: &str) -> Result<U256> {
+    U256::from_dec_str(value).map_err(|error| anyhow!("invalid amount {}: {:?}", value, error))
+}
+
+/// `<contract hash>:<swap id>:<secret>`, a swap of an `unlock-batch`, followed by
+/// `:<payout>:<signature>` for signed swaps.
+pub fn redemption(value: &str) -> Result<Redemption> {
+    let parts: Vec<&str> = value.splitn(5, ':').collect();
+    let (contract, swap_id, secret, signed) = match parts.as_slice() {
+        [contract, swap_id, secret] => (contract, swap_id, secret, None),
+        [contract, swap_id, secret, payout, signature] => (
+            contract,
+            swap_id,
+            secret,
+            Some((key(payout)?, self::signature(signature)?)),
+        ),
+        _ => {
+            return Err(anyhow!(
+                "expected <contract hash>:<swap id>:<secret>[:<payout>:<signature>], got {}",
+                value
+            ))
+        }
+    };
+    Ok(Redemption {
+        contract_hash: contract_hash(contract)?,
+        swap_id: swap_id
+            .parse()
+            .with_context(|| format!("invalid swap id {}", swap_id))?,
+        secret: secret.to_string(),
+        signed,
+    })
+}
+
+fn hex_32(value: &str) -> Result<[u8; 32]> {
+    let bytes = hex::decode(value)?;
+    let mut result = [0u8; 32];
+    if bytes.len() != result.len() {
+        return Err(anyhow!("expected 32 bytes, got {}", bytes.len()));
+    }
+    result.copy_from_slice(&bytes);
+    Ok(result)
+}
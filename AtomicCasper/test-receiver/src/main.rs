@@ -0,0 +1,167 @@
+//! A vault contract for the engine tests of the receiver hook of the swap contract.
+//!
+//! `register` asks a swap contract to call it back with `set_receiver_hook`. Every
+//! `on_swap_received` is appended to the `received` named key, and CSPR of a `Direct` swap,
+//! which the swap contract credits to contract receivers, is withdrawn into the vault's own purse
+//! right away. Without a hook, `withdraw` takes it out.
+#![no_std]
+#![no_main]
+
+#[cfg(not(target_arch = "wasm32"))]
+compile_error!("target arch should be wasm32: compile with '--target wasm32-unknown-unknown'");
+
+extern crate alloc;
+
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use casper_contract::{
+    contract_api::{runtime, storage, system},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    contracts::NamedKeys, runtime_args, CLType, CLTyped, ContractHash, EntryPoint,
+    EntryPointAccess, EntryPointType, EntryPoints, Key, Parameter, RuntimeArgs, U512,
+};
+
+const NAMED_KEY_SWAP_CONTRACT: &str = "swap_contract";
+const NAMED_KEY_RECEIVED: &str = "received";
+const NAMED_KEY_PURSE: &str = "purse";
+
+const ARG_SWAP_CONTRACT: &str = "swap_contract";
+const ARG_ENABLED: &str = "enabled";
+const ARG_SWAP_ID: &str = "swap_id";
+const ARG_ASSET: &str = "asset";
+const ARG_AMOUNT: &str = "amount";
+const ARG_PURSE: &str = "purse";
+
+/// Swap id, asset type and amount of every `on_swap_received`.
+type Received = Vec<(u64, String, U512)>;
+
+fn read<T: CLTyped + casper_types::bytesrepr::FromBytes>(name: &str) -> T {
+    let uref = runtime::get_key(name)
+        .unwrap_or_revert()
+        .into_uref()
+        .unwrap_or_revert();
+    storage::read(uref).unwrap_or_revert().unwrap_or_revert()
+}
+
+fn write<T: CLTyped + casper_types::bytesrepr::ToBytes>(name: &str, value: T) {
+    let uref = runtime::get_key(name)
+        .unwrap_or_revert()
+        .into_uref()
+        .unwrap_or_revert();
+    storage::write(uref, value);
+}
+
+fn swap_contract() -> ContractHash {
+    let swap_contract = read::<Option<Key>>(NAMED_KEY_SWAP_CONTRACT)
+        .unwrap_or_revert()
+        .into_hash()
+        .unwrap_or_revert();
+    ContractHash::new(swap_contract)
+}
+
+#[no_mangle]
+pub extern "C" fn register() {
+    let swap_contract = runtime::get_named_arg::<Key>(ARG_SWAP_CONTRACT);
+    let enabled = runtime::get_named_arg::<bool>(ARG_ENABLED);
+    write(NAMED_KEY_SWAP_CONTRACT, Some(swap_contract));
+    // the purse is created in the context of the contract, so it can hand it to `withdraw`
+    if runtime::get_key(NAMED_KEY_PURSE).is_none() {
+        runtime::put_key(NAMED_KEY_PURSE, system::create_purse().into());
+    }
+    runtime::call_contract::<()>(
+        swap_contract(),
+        "set_receiver_hook",
+        runtime_args! { ARG_ENABLED => enabled },
+    );
+}
+
+#[no_mangle]
+pub extern "C" fn on_swap_received() {
+    let swap_id = runtime::get_named_arg::<u64>(ARG_SWAP_ID);
+    let asset = runtime::get_named_arg::<String>(ARG_ASSET);
+    let amount = runtime::get_named_arg::<U512>(ARG_AMOUNT);
+    let mut received = read::<Received>(NAMED_KEY_RECEIVED);
+    received.push((swap_id, asset.clone(), amount));
+    write(NAMED_KEY_RECEIVED, received);
+    if asset == "Direct" {
+        withdraw_into_purse(amount);
+    }
+}
+
+/// Takes CSPR the swap contract credited the vault out, without a hook.
+#[no_mangle]
+pub extern "C" fn withdraw() {
+    withdraw_into_purse(runtime::get_named_arg::<U512>(ARG_AMOUNT));
+}
+
+fn withdraw_into_purse(amount: U512) {
+    let purse = runtime::get_key(NAMED_KEY_PURSE)
+        .unwrap_or_revert()
+        .into_uref()
+        .unwrap_or_revert();
+    runtime::call_contract::<()>(
+        swap_contract(),
+        "withdraw",
+        runtime_args! {
+            ARG_AMOUNT => amount,
+            ARG_PURSE => purse,
+        },
+    );
+}
+
+fn entry_point(name: &str, params: Vec<Parameter>) -> EntryPoint {
+    EntryPoint::new(
+        name,
+        params,
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let mut entry_points = EntryPoints::new();
+    entry_points.add_entry_point(entry_point(
+        "register",
+        vec![
+            Parameter::new(ARG_SWAP_CONTRACT, CLType::Key),
+            Parameter::new(ARG_ENABLED, CLType::Bool),
+        ],
+    ));
+    entry_points.add_entry_point(entry_point(
+        "on_swap_received",
+        vec![
+            Parameter::new(ARG_SWAP_ID, CLType::U64),
+            Parameter::new(ARG_ASSET, CLType::String),
+            Parameter::new(ARG_AMOUNT, CLType::U512),
+        ],
+    ));
+
+    entry_points.add_entry_point(entry_point(
+        "withdraw",
+        vec![Parameter::new(ARG_AMOUNT, CLType::U512)],
+    ));
+
+    let mut named_keys = NamedKeys::new();
+    named_keys.insert(
+        NAMED_KEY_SWAP_CONTRACT.to_string(),
+        storage::new_uref(None::<Key>).into(),
+    );
+    named_keys.insert(
+        NAMED_KEY_RECEIVED.to_string(),
+        storage::new_uref(Received::new()).into(),
+    );
+    let (contract_hash, _version) = storage::new_contract(
+        entry_points,
+        Some(named_keys),
+        Some("test_receiver_package".to_string()),
+        None,
+    );
+    runtime::put_key("test_receiver", contract_hash.into());
+}
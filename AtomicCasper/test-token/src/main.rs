@@ -0,0 +1,289 @@
+//! A misbehaving token contract for the engine tests of the swap contract.
+//!
+//! Installed with the `NFT` type it speaks just enough CEP-47 for the swap (`owner_of` and
+//! `transfer` of `token_ids`), with the `ERC-20` type just enough ERC-20 (`balance_of` and
+//! `transfer` of an `amount` from the caller), and with the `Custom` type it keeps balances like
+//! ERC-20 but `transfer` only takes the `recipient` and moves the caller's whole balance. It is
+//! installed as `custom_token` then, `test_token` otherwise, so an account can hold both.
+//! `configure` picks how `transfer` behaves:
+//! - `honest` moves the tokens,
+//! - `noop` returns success without moving anything,
+//! - `reject` returns `Err` of a `Result`,
+//! - `reenter` moves the tokens, then calls `unlock` of the swap contract again with the secret.
+#![no_std]
+#![no_main]
+
+#[cfg(not(target_arch = "wasm32"))]
+compile_error!("target arch should be wasm32: compile with '--target wasm32-unknown-unknown'");
+
+extern crate alloc;
+
+use alloc::{
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use casper_contract::{
+    contract_api::{runtime, storage},
+    unwrap_or_revert::UnwrapOrRevert,
+};
+use casper_types::{
+    contracts::NamedKeys, runtime_args, system::CallStackElement, ApiError, CLType, CLTyped,
+    CLValue, ContractHash, EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, Key,
+    Parameter, RuntimeArgs, U256,
+};
+
+const NAMED_KEY_STANDARD: &str = "standard";
+const NAMED_KEY_OWNERS: &str = "owners";
+const NAMED_KEY_BALANCES: &str = "balances";
+const NAMED_KEY_MODE: &str = "mode";
+const NAMED_KEY_SWAP_CONTRACT: &str = "swap_contract";
+const NAMED_KEY_SECRET: &str = "secret";
+
+const ARG_RECIPIENT: &str = "recipient";
+const ARG_TOKEN_ID: &str = "token_id";
+const ARG_TOKEN_IDS: &str = "token_ids";
+const ARG_AMOUNT: &str = "amount";
+const ARG_ADDRESS: &str = "address";
+const ARG_TYPE: &str = "type";
+const ARG_MODE: &str = "mode";
+const ARG_SWAP_CONTRACT: &str = "swap_contract";
+const ARG_SECRET: &str = "secret";
+
+type Owners = BTreeMap<U256, Key>;
+type Balances = BTreeMap<Key, U256>;
+
+fn read<T: CLTyped + casper_types::bytesrepr::FromBytes>(name: &str) -> T {
+    let uref = runtime::get_key(name)
+        .unwrap_or_revert()
+        .into_uref()
+        .unwrap_or_revert();
+    storage::read(uref).unwrap_or_revert().unwrap_or_revert()
+}
+
+fn write<T: CLTyped + casper_types::bytesrepr::ToBytes>(name: &str, value: T) {
+    let uref = runtime::get_key(name)
+        .unwrap_or_revert()
+        .into_uref()
+        .unwrap_or_revert();
+    storage::write(uref, value);
+}
+
+fn is_fungible() -> bool {
+    read::<String>(NAMED_KEY_STANDARD) != "NFT"
+}
+
+fn balance(address: Key) -> U256 {
+    read::<Balances>(NAMED_KEY_BALANCES)
+        .get(&address)
+        .copied()
+        .unwrap_or_default()
+}
+
+/// The contract or account that called this one, keyed like the swap contract keys itself.
+fn caller() -> Key {
+    let call_stack = runtime::get_call_stack();
+    match call_stack.get(call_stack.len() - 2).unwrap_or_revert() {
+        CallStackElement::StoredContract {
+            contract_package_hash,
+            ..
+        } => Key::from(*contract_package_hash),
+        CallStackElement::Session { account_hash }
+        | CallStackElement::StoredSession { account_hash, .. } => Key::Account(*account_hash),
+    }
+}
+
+fn move_tokens(recipient: Key, token_ids: Vec<U256>) {
+    let mut owners = read::<Owners>(NAMED_KEY_OWNERS);
+    for token_id in token_ids {
+        owners.insert(token_id, recipient);
+    }
+    write(NAMED_KEY_OWNERS, owners);
+}
+
+fn move_amount(sender: Option<Key>, recipient: Key, amount: U256) {
+    let mut balances = read::<Balances>(NAMED_KEY_BALANCES);
+    if let Some(sender) = sender {
+        let balance = balances.get(&sender).copied().unwrap_or_default();
+        let balance = balance
+            .checked_sub(amount)
+            .unwrap_or_revert_with(ApiError::User(1));
+        balances.insert(sender, balance);
+    }
+    let balance = balances.get(&recipient).copied().unwrap_or_default();
+    balances.insert(recipient, balance + amount);
+    write(NAMED_KEY_BALANCES, balances);
+}
+
+/// Moves what `transfer` moves out of the caller to `recipient`: what the args name, or the
+/// caller's whole balance with the `Custom` type.
+fn move_transferred(recipient: Key) {
+    let sender = caller();
+    if read::<String>(NAMED_KEY_STANDARD) == "Custom" {
+        move_amount(Some(sender), recipient, balance(sender));
+    } else {
+        move_args(Some(sender), recipient);
+    }
+}
+
+/// Moves what the `mint` or `transfer` args name to `recipient`, out of `sender` if any.
+fn move_args(sender: Option<Key>, recipient: Key) {
+    if is_fungible() {
+        move_amount(
+            sender,
+            recipient,
+            runtime::get_named_arg::<U256>(ARG_AMOUNT),
+        );
+    } else {
+        move_tokens(
+            recipient,
+            runtime::get_named_arg::<Vec<U256>>(ARG_TOKEN_IDS),
+        );
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn mint() {
+    let recipient = runtime::get_named_arg::<Key>(ARG_RECIPIENT);
+    move_args(None, recipient);
+}
+
+#[no_mangle]
+pub extern "C" fn balance_of() {
+    let address = runtime::get_named_arg::<Key>(ARG_ADDRESS);
+    runtime::ret(CLValue::from_t(balance(address)).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn owner_of() {
+    let token_id = runtime::get_named_arg::<U256>(ARG_TOKEN_ID);
+    let owner = read::<Owners>(NAMED_KEY_OWNERS).get(&token_id).copied();
+    runtime::ret(CLValue::from_t(owner).unwrap_or_revert());
+}
+
+#[no_mangle]
+pub extern "C" fn configure() {
+    write(NAMED_KEY_MODE, runtime::get_named_arg::<String>(ARG_MODE));
+    write(
+        NAMED_KEY_SWAP_CONTRACT,
+        runtime::get_named_arg::<Option<Key>>(ARG_SWAP_CONTRACT),
+    );
+    write(
+        NAMED_KEY_SECRET,
+        runtime::get_named_arg::<String>(ARG_SECRET),
+    );
+}
+
+#[no_mangle]
+pub extern "C" fn transfer() {
+    let recipient = runtime::get_named_arg::<Key>(ARG_RECIPIENT);
+    match read::<String>(NAMED_KEY_MODE).as_str() {
+        "noop" => {}
+        "reject" => runtime::ret(CLValue::from_t(Err::<(), u32>(1)).unwrap_or_revert()),
+        "reenter" => {
+            move_transferred(recipient);
+            let swap_contract = read::<Option<Key>>(NAMED_KEY_SWAP_CONTRACT)
+                .unwrap_or_revert()
+                .into_hash()
+                .unwrap_or_revert();
+            let secret = read::<String>(NAMED_KEY_SECRET);
+            runtime::call_contract::<()>(
+                ContractHash::new(swap_contract),
+                "unlock",
+                runtime_args! { "secret" => secret },
+            );
+        }
+        _ => move_transferred(recipient),
+    }
+}
+
+fn entry_point(name: &str, params: Vec<Parameter>, ret: CLType) -> EntryPoint {
+    EntryPoint::new(
+        name,
+        params,
+        ret,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    )
+}
+
+#[no_mangle]
+pub extern "C" fn call() {
+    let standard = runtime::get_named_arg::<String>(ARG_TYPE);
+    let name = if standard == "Custom" {
+        "custom_token"
+    } else {
+        "test_token"
+    };
+    // what `mint` and `transfer` move
+    let moved = || match standard.as_str() {
+        "NFT" => Parameter::new(ARG_TOKEN_IDS, Vec::<U256>::cl_type()),
+        "ERC-20" | "Custom" => Parameter::new(ARG_AMOUNT, CLType::U256),
+        _ => runtime::revert(ApiError::InvalidArgument),
+    };
+    let mut transfer_params = vec![Parameter::new(ARG_RECIPIENT, CLType::Key)];
+    if standard != "Custom" {
+        transfer_params.push(moved());
+    }
+    let mut entry_points = EntryPoints::new();
+    entry_points.add_entry_point(entry_point(
+        "mint",
+        vec![Parameter::new(ARG_RECIPIENT, CLType::Key), moved()],
+        CLType::Unit,
+    ));
+    entry_points.add_entry_point(entry_point(
+        "owner_of",
+        vec![Parameter::new(ARG_TOKEN_ID, CLType::U256)],
+        Option::<Key>::cl_type(),
+    ));
+    entry_points.add_entry_point(entry_point(
+        "balance_of",
+        vec![Parameter::new(ARG_ADDRESS, CLType::Key)],
+        CLType::U256,
+    ));
+    entry_points.add_entry_point(entry_point(
+        "configure",
+        vec![
+            Parameter::new(ARG_MODE, CLType::String),
+            Parameter::new(ARG_SWAP_CONTRACT, Option::<Key>::cl_type()),
+            Parameter::new(ARG_SECRET, CLType::String),
+        ],
+        CLType::Unit,
+    ));
+    entry_points.add_entry_point(entry_point("transfer", transfer_params, CLType::Any));
+
+    let mut named_keys = NamedKeys::new();
+    named_keys.insert(
+        NAMED_KEY_STANDARD.to_string(),
+        storage::new_uref(standard.clone()).into(),
+    );
+    named_keys.insert(
+        NAMED_KEY_OWNERS.to_string(),
+        storage::new_uref(Owners::new()).into(),
+    );
+    named_keys.insert(
+        NAMED_KEY_BALANCES.to_string(),
+        storage::new_uref(Balances::new()).into(),
+    );
+    named_keys.insert(
+        NAMED_KEY_MODE.to_string(),
+        storage::new_uref("honest".to_string()).into(),
+    );
+    named_keys.insert(
+        NAMED_KEY_SWAP_CONTRACT.to_string(),
+        storage::new_uref(None::<Key>).into(),
+    );
+    named_keys.insert(
+        NAMED_KEY_SECRET.to_string(),
+        storage::new_uref(String::new()).into(),
+    );
+    let (contract_hash, _version) = storage::new_contract(
+        entry_points,
+        Some(named_keys),
+        Some(format!("{}_package", name)),
+        None,
+    );
+    runtime::put_key(name, contract_hash.into());
+}
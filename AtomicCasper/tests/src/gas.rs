@@ -0,0 +1,115 @@
+//! Gas each entry point costs on the in-memory engine, checked against the baseline in
+//! `tests/gas.json`.
+//!
+//! The baseline is committed, so a change in cost shows up in review. A measurement more than
+//! `threshold_percent` above its baseline is a regression; one the baseline doesn't have yet is
+//! only reported. `UPDATE_GAS_BASELINE=1` rewrites the baseline with the latest measurements.
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+use serde_json::{json, Value};
+
+/// Version of the layout of the baseline file.
+pub const FORMAT_VERSION: u64 = 1;
+
+/// Threshold of a new baseline, in percent.
+pub const DEFAULT_THRESHOLD_PERCENT: u64 = 5;
+
+/// Gas of the deploys of one run, by name.
+pub type Costs = BTreeMap<String, u64>;
+
+/// The committed costs and how far above them a measurement may go.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Baseline {
+    pub threshold_percent: u64,
+    pub costs: Costs,
+}
+
+impl Default for Baseline {
+    fn default() -> Self {
+        Baseline {
+            threshold_percent: DEFAULT_THRESHOLD_PERCENT,
+            costs: Costs::new(),
+        }
+    }
+}
+
+impl Baseline {
+    pub fn path() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("gas.json")
+    }
+
+    /// The baseline in `tests/gas.json`, or an empty one if there is none yet.
+    pub fn load() -> Self {
+        let path = Self::path();
+        let json = match fs::read_to_string(&path) {
+            Ok(json) => json,
+            Err(_) => return Baseline::default(),
+        };
+        let value: Value = serde_json::from_str(&json)
+            .unwrap_or_else(|error| panic!("{}: {}", path.display(), error));
+        let version = value["version"].as_u64();
+        assert_eq!(
+            version,
+            Some(FORMAT_VERSION),
+            "{}: unsupported version",
+            path.display()
+        );
+        let costs = value["costs"]
+            .as_object()
+            .map(|costs| {
+                costs
+                    .iter()
+                    .filter_map(|(name, cost)| Some((name.clone(), cost.as_u64()?)))
+                    .collect()
+            })
+            .unwrap_or_default();
+        Baseline {
+            threshold_percent: value["threshold_percent"]
+                .as_u64()
+                .unwrap_or(DEFAULT_THRESHOLD_PERCENT),
+            costs,
+        }
+    }
+
+    pub fn save(&self) {
+        let value = json!({
+            "version": FORMAT_VERSION,
+            "threshold_percent": self.threshold_percent,
+            "costs": self.costs,
+        });
+        let json = serde_json::to_string_pretty(&value).unwrap();
+        fs::write(Self::path(), json + "\n").unwrap();
+    }
+
+    /// The measurements of `costs` above the threshold, as readable lines.
+    pub fn regressions(&self, costs: &Costs) -> Vec<String> {
+        costs
+            .iter()
+            .filter_map(|(name, &cost)| {
+                let baseline = *self.costs.get(name)?;
+                let limit = baseline + baseline * self.threshold_percent / 100;
+                if cost > limit {
+                    Some(format!(
+                        "{}: {} gas, {} before (+{:.1}%)",
+                        name,
+                        cost,
+                        baseline,
+                        (cost - baseline) as f64 * 100.0 / baseline as f64
+                    ))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+}
+
+/// Gas the deploy of an execution result used. Panics unless it succeeded, a failed deploy
+/// measures nothing useful.
+pub fn cost(result: &Value) -> u64 {
+    let cost = result
+        .pointer("/Success/cost")
+        .and_then(Value::as_str)
+        .unwrap_or_else(|| panic!("{}", result));
+    cost.parse().unwrap()
+}
@@ -0,0 +1,168 @@
+//! Shared setup of the tests that run the contract: installing it on a [`MockNode`], sending
+//! deploys and checking how they ended.
+use casper_types::{
+    runtime_args, ContractHash, Key, PublicKey, RuntimeArgs, SecretKey, U256, U512,
+};
+use serde_json::Value;
+use swap_client::{
+    args::Asset, parse_execution_error, rpc::NodeClient, ContractError, Deploy, DeployBuilder,
+    Initiate, TokenContract,
+};
+
+use crate::{contract_wasm, test_receiver_wasm, test_token_wasm, MockNode};
+
+/// Network of the deploys the helpers build.
+pub const CHAIN_NAME: &str = "casper-test";
+/// Secret of the swaps [`initiate`] opens, hashed with sha3-256.
+pub const SECRET: &str = "ac3b9e1f";
+/// How long the swaps [`initiate`] opens stay open, in milliseconds.
+pub const TIMEOUT: u64 = 60_000;
+
+/// A contract installed on a fresh [`MockNode`] with two funded accounts: `owner`, which installed
+/// it, and `receiver`.
+pub struct Swap {
+    pub node: MockNode,
+    pub owner: SecretKey,
+    pub receiver: SecretKey,
+    pub contract_hash: ContractHash,
+}
+
+pub fn builder() -> DeployBuilder {
+    DeployBuilder::new(CHAIN_NAME, U512::from(200_000_000_000u64))
+}
+
+/// Puts `deploy` on the node and returns its execution result, `Success` or `Failure`.
+pub fn send(node: &MockNode, deploy: &Deploy) -> Value {
+    let client = NodeClient::new(node);
+    let deploy_hash = client.put_deploy(deploy).unwrap();
+    let result = client.deploy(&deploy_hash).unwrap();
+    result["execution_results"][0]["result"].clone()
+}
+
+pub fn assert_success(result: &Value) {
+    assert!(result.get("Success").is_some(), "{}", result);
+}
+
+/// Asserts the deploy reverted with `error` of the contract.
+pub fn assert_reverted(result: &Value, error: ContractError) {
+    let message = result
+        .pointer("/Failure/error_message")
+        .and_then(Value::as_str)
+        .unwrap_or_else(|| panic!("{}", result));
+    assert_eq!(parse_execution_error(message), Some(error), "{}", message);
+}
+
+/// A `Custom` swap contract, the type the tests lock [`custom`] tokens in.
+pub fn install() -> Swap {
+    install_as("Custom")
+}
+
+pub fn install_as(swap_type: &str) -> Swap {
+    let owner = SecretKey::ed25519_from_bytes([1u8; 32]).unwrap();
+    let receiver = SecretKey::ed25519_from_bytes([2u8; 32]).unwrap();
+    let node = MockNode::new(
+        CHAIN_NAME,
+        &[PublicKey::from(&owner), PublicKey::from(&receiver)],
+    );
+    let result = send(
+        &node,
+        &builder().install(contract_wasm(), swap_type, &owner),
+    );
+    assert_success(&result);
+    let contract_hash = match node.named_key(&PublicKey::from(&owner), "atomic_swap_contract") {
+        Some(Key::Hash(hash)) => ContractHash::new(hash),
+        other => panic!("no contract installed: {:?}", other),
+    };
+    Swap {
+        node,
+        owner,
+        receiver,
+        contract_hash,
+    }
+}
+
+/// What the `Custom` swaps of the tests lock: the `Custom` test token, which the owner installs on
+/// first use, with a unit of it minted to the contract for the swap to pay out.
+pub fn custom(swap: &Swap) -> Asset {
+    custom_of(&swap.node, &swap.owner, swap.contract_hash)
+}
+
+/// [`custom`] for any swap contract `owner` initiates on `node`.
+pub fn custom_of(node: &MockNode, owner: &SecretKey, contract_hash: ContractHash) -> Asset {
+    let public_key = PublicKey::from(owner);
+    if node.named_key(&public_key, "custom_token").is_none() {
+        let install = builder().install(test_token_wasm(), "Custom", owner);
+        assert_success(&send(node, &install));
+    }
+    let token = match node.named_key(&public_key, "custom_token") {
+        Some(Key::Hash(hash)) => ContractHash::new(hash),
+        other => panic!("no token installed: {:?}", other),
+    };
+    let (package, _swap_id) = NodeClient::new(node).salt(contract_hash).unwrap();
+    let mint = builder().call(
+        token,
+        "mint",
+        runtime_args! {
+            "recipient" => Key::from(package),
+            "amount" => U256::one(),
+        },
+        owner,
+    );
+    assert_success(&send(node, &mint));
+    Asset::Custom {
+        token_contract: TokenContract::Contract(token),
+    }
+}
+
+/// A vault of the `test-receiver` crate, installed by the owner, with its package, the key swaps
+/// pay it at.
+pub fn install_vault(swap: &Swap) -> (ContractHash, Key) {
+    let owner = PublicKey::from(&swap.owner);
+    let install = builder().install(test_receiver_wasm(), "Custom", &swap.owner);
+    assert_success(&send(&swap.node, &install));
+    let vault = match swap.node.named_key(&owner, "test_receiver") {
+        Some(Key::Hash(hash)) => ContractHash::new(hash),
+        other => panic!("no vault installed: {:?}", other),
+    };
+    let package = swap
+        .node
+        .named_key(&owner, "test_receiver_package")
+        .unwrap();
+    (vault, package)
+}
+
+/// Has `vault` call `set_receiver_hook` of the swap contract, which it needs to be paid.
+pub fn register_vault(swap: &Swap, vault: ContractHash, enabled: bool) {
+    let register = builder().call(
+        vault,
+        "register",
+        runtime_args! {
+            "swap_contract" => Key::from(swap.contract_hash),
+            "enabled" => enabled,
+        },
+        &swap.owner,
+    );
+    assert_success(&send(&swap.node, &register));
+}
+
+/// Opens a `Custom` swap for the receiver, locked with [`SECRET`].
+pub fn initiate(swap: &Swap) {
+    assert_success(&initiate_with(swap, custom(swap)));
+}
+
+pub fn initiate_with(swap: &Swap, asset: Asset) -> Value {
+    initiate_with_secret(swap, asset, SECRET)
+}
+
+/// Opens a swap for the receiver locked with another secret than [`SECRET`], a contract takes
+/// each hashlock once.
+pub fn initiate_with_secret(swap: &Swap, asset: Asset, secret: &str) -> Value {
+    let receiver = Key::Account(PublicKey::from(&swap.receiver).to_account_hash());
+    let initiate = Initiate::new(asset, receiver, TIMEOUT)
+        .with_secret("sha3-256", secret)
+        .unwrap();
+    let deploy = builder()
+        .initiate(swap.contract_hash, &initiate, &swap.owner)
+        .unwrap();
+    send(&swap.node, &deploy)
+}
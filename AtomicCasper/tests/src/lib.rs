@@ -0,0 +1,48 @@
+//! Runs the real `contract.wasm` off-chain.
+//!
+//! `make test` builds the contract, the deposit and batch sessions, the test token and the test
+//! receiver and copies them to `tests/wasm`, where [`contract_wasm`], [`session_wasm`],
+//! [`batch_wasm`], [`test_token_wasm`] and [`test_receiver_wasm`] read them from. [`MockNode`] executes deploys with the in-memory execution
+//! engine and answers the node JSON-RPC methods the client, `swapctl` and the daemons use, so they
+//! can be tested end to end without a network.
+pub mod gas;
+pub mod harness;
+pub mod node;
+
+pub use node::MockNode;
+
+use std::{fs, path::PathBuf};
+
+/// The contract built by `make test`.
+pub fn contract_wasm() -> Vec<u8> {
+    read_wasm("contract.wasm")
+}
+
+/// The deposit session built by `make test`.
+pub fn session_wasm() -> Vec<u8> {
+    read_wasm("session.wasm")
+}
+
+/// The batch session built by `make test`.
+pub fn batch_wasm() -> Vec<u8> {
+    read_wasm("batch.wasm")
+}
+
+/// The misbehaving token contract of the `test-token` crate, built by `make test`.
+pub fn test_token_wasm() -> Vec<u8> {
+    read_wasm("test_token.wasm")
+}
+
+/// The vault of the `test-receiver` crate, which registers a receiver hook, built by `make test`.
+pub fn test_receiver_wasm() -> Vec<u8> {
+    read_wasm("test_receiver.wasm")
+}
+
+fn read_wasm(name: &str) -> Vec<u8> {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("wasm")
+        .join(name);
+    fs::read(&path).unwrap_or_else(|error| {
+        panic!("{}: {}, run `make test` to build it", path.display(), error)
+    })
+}
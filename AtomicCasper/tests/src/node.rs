@@ -0,0 +1,393 @@
+//! An in-process node: every deploy it receives is executed right away, in a block of its own.
+use std::{cell::RefCell, collections::BTreeMap, convert::TryFrom};
+
+use casper_engine_test_support::{
+    utils::create_run_genesis_request, ExecuteRequestBuilder, InMemoryWasmTestBuilder,
+    DEFAULT_ACCOUNTS,
+};
+use casper_execution_engine::{
+    core::engine_state::{deploy_item::DeployItem, genesis::GenesisAccount},
+    shared::{motes::Motes, newtypes::Blake2bHash},
+};
+use casper_node::types::{json_compatibility, Deploy, Timestamp};
+use casper_types::{account::AccountHash, Key, PublicKey, StoredValue, URef, U512};
+use serde_json::{json, Value};
+use swap_client::{rpc::Transport, Error};
+
+/// Motes every account given to [`MockNode::new`] starts with.
+pub const INITIAL_BALANCE: u64 = 1_000_000_000_000_000;
+
+/// Time of the genesis block, in milliseconds since the unix epoch.
+pub const GENESIS_TIME: u64 = 1_640_995_200_000;
+
+struct Block {
+    hash: String,
+    height: u64,
+    timestamp: u64,
+    state_root: Blake2bHash,
+    deploy_hashes: Vec<String>,
+}
+
+impl Block {
+    fn new(
+        height: u64,
+        timestamp: u64,
+        state_root: Blake2bHash,
+        deploy_hashes: Vec<String>,
+    ) -> Self {
+        let mut bytes = height.to_le_bytes().to_vec();
+        bytes.extend_from_slice(&state_root.value());
+        Block {
+            hash: hex::encode(swap_core::hash::blake2b(&bytes)),
+            height,
+            timestamp,
+            state_root,
+            deploy_hashes,
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "hash": self.hash,
+            "header": {
+                "height": self.height,
+                "timestamp": Timestamp::from(self.timestamp).to_string(),
+                "state_root_hash": hex::encode(self.state_root.value()),
+            },
+            "body": {
+                "deploy_hashes": self.deploy_hashes,
+                "transfer_hashes": [],
+            },
+        })
+    }
+}
+
+struct Executed {
+    deploy: Value,
+    block_hash: String,
+    result: Value,
+}
+
+struct Inner {
+    builder: InMemoryWasmTestBuilder,
+    chain_name: String,
+    block_time: u64,
+    block_interval: u64,
+    blocks: Vec<Block>,
+    deploys: BTreeMap<String, Executed>,
+}
+
+/// Answers the subset of the node JSON-RPC the swap tools use: `account_put_deploy`,
+/// `info_get_deploy`, `chain_get_block`, `chain_get_state_root_hash`, `query_global_state` and
+/// `state_get_dictionary_item`. Deploy signatures are not checked.
+///
+/// Use it as the [`Transport`] of a `NodeClient`, or of any adapter built on one.
+pub struct MockNode {
+    inner: RefCell<Inner>,
+}
+
+fn rpc_error<E: ToString>(error: E) -> Error {
+    Error::Rpc(error.to_string())
+}
+
+impl MockNode {
+    /// Starts a network named `chain_name` where each of `accounts` holds [`INITIAL_BALANCE`].
+    pub fn new(chain_name: &str, accounts: &[PublicKey]) -> Self {
+        let mut genesis_accounts = DEFAULT_ACCOUNTS.clone();
+        genesis_accounts.extend(accounts.iter().map(|public_key| {
+            GenesisAccount::account(
+                public_key.clone(),
+                Motes::new(U512::from(INITIAL_BALANCE)),
+                None,
+            )
+        }));
+        let mut builder = InMemoryWasmTestBuilder::default();
+        builder.run_genesis(&create_run_genesis_request(genesis_accounts));
+        let genesis = Block::new(0, GENESIS_TIME, builder.get_post_state_hash(), Vec::new());
+        MockNode {
+            inner: RefCell::new(Inner {
+                builder,
+                chain_name: chain_name.to_string(),
+                block_time: GENESIS_TIME,
+                block_interval: 1_000,
+                blocks: vec![genesis],
+                deploys: BTreeMap::new(),
+            }),
+        }
+    }
+
+    /// Milliseconds between two blocks, 1000 by default.
+    pub fn with_block_interval(self, block_interval: u64) -> Self {
+        self.inner.borrow_mut().block_interval = block_interval;
+        self
+    }
+
+    /// Time of the latest block.
+    pub fn block_time(&self) -> u64 {
+        self.inner.borrow().block_time
+    }
+
+    /// Moves the clock forward, e.g. past the end time of a swap. The next block is
+    /// `milliseconds` plus the block interval after the latest one.
+    pub fn advance_time(&self, milliseconds: u64) {
+        self.inner.borrow_mut().block_time += milliseconds;
+    }
+
+    pub fn height(&self) -> u64 {
+        self.inner.borrow().blocks.len() as u64 - 1
+    }
+
+    /// A named key of an account, e.g. the `atomic_swap_contract` put there by the installer.
+    pub fn named_key(&self, account: &PublicKey, name: &str) -> Option<Key> {
+        let inner = self.inner.borrow();
+        let account = inner.builder.get_account(account.to_account_hash())?;
+        account.named_keys().get(name).copied()
+    }
+
+    /// Motes in the main purse of an account, zero if it doesn't exist.
+    pub fn balance(&self, account: AccountHash) -> U512 {
+        let inner = self.inner.borrow();
+        match inner.builder.get_account(account) {
+            Some(account) => inner.builder.get_purse_balance(account.main_purse()),
+            None => U512::zero(),
+        }
+    }
+
+    fn put_deploy(&self, params: &Value) -> Result<Value, Error> {
+        let deploy: Deploy = serde_json::from_value(params["deploy"].clone()).map_err(rpc_error)?;
+        let mut inner = self.inner.borrow_mut();
+        if deploy.header().chain_name() != inner.chain_name {
+            return Err(Error::Rpc(format!(
+                "deploy is for chain {}, not {}",
+                deploy.header().chain_name(),
+                inner.chain_name
+            )));
+        }
+        let deploy_hash = serde_json::to_value(deploy.id()).map_err(rpc_error)?;
+        let deploy_hash = deploy_hash.as_str().unwrap_or_default().to_string();
+        if inner.deploys.contains_key(&deploy_hash) {
+            return Err(Error::Rpc(format!("duplicate deploy {}", deploy_hash)));
+        }
+        let json = serde_json::to_value(&deploy).map_err(rpc_error)?;
+
+        inner.block_time += inner.block_interval;
+        let request = ExecuteRequestBuilder::from_deploy_item(DeployItem::from(deploy))
+            .with_block_time(inner.block_time)
+            .build();
+        inner.builder.exec(request).commit();
+        let result = inner
+            .builder
+            .get_last_exec_results()
+            .and_then(|results| results.first().cloned())
+            .ok_or_else(|| Error::Rpc("the deploy was not executed".to_string()))?;
+        let mut outcome = json!({
+            "effect": { "operations": [], "transforms": [] },
+            "transfers": [],
+            "cost": result.cost().value().to_string(),
+        });
+        let result = match result.as_error() {
+            Some(error) => {
+                outcome["error_message"] = json!(error.to_string());
+                json!({ "Failure": outcome })
+            }
+            None => json!({ "Success": outcome }),
+        };
+
+        let height = inner.blocks.len() as u64;
+        let state_root = inner.builder.get_post_state_hash();
+        let block = Block::new(
+            height,
+            inner.block_time,
+            state_root,
+            vec![deploy_hash.clone()],
+        );
+        inner.deploys.insert(
+            deploy_hash.clone(),
+            Executed {
+                deploy: json,
+                block_hash: block.hash.clone(),
+                result,
+            },
+        );
+        inner.blocks.push(block);
+        Ok(json!({ "deploy_hash": deploy_hash }))
+    }
+
+    fn get_deploy(&self, params: &Value) -> Result<Value, Error> {
+        let deploy_hash = params["deploy_hash"].as_str().unwrap_or_default();
+        let inner = self.inner.borrow();
+        let executed = inner
+            .deploys
+            .get(deploy_hash)
+            .ok_or_else(|| Error::Rpc(format!("no such deploy {}", deploy_hash)))?;
+        Ok(json!({
+            "deploy": executed.deploy,
+            "execution_results": [{
+                "block_hash": executed.block_hash,
+                "result": executed.result,
+            }],
+        }))
+    }
+
+    fn get_block(&self, params: &Value) -> Result<Value, Error> {
+        let inner = self.inner.borrow();
+        let block = match params.get("block_identifier") {
+            None => inner.blocks.last(),
+            Some(identifier) => {
+                if let Some(height) = identifier.get("Height").and_then(Value::as_u64) {
+                    inner.blocks.get(height as usize)
+                } else {
+                    let hash = identifier.get("Hash").and_then(Value::as_str);
+                    inner
+                        .blocks
+                        .iter()
+                        .find(|block| Some(block.hash.as_str()) == hash)
+                }
+            }
+        };
+        let block = block.ok_or_else(|| Error::Rpc("no such block".to_string()))?;
+        Ok(json!({ "block": block.to_json() }))
+    }
+
+    /// The state root of a `StateRootHash` or `BlockHash` identifier, or the latest one.
+    fn state_root(&self, identifier: Option<&Value>) -> Result<Blake2bHash, Error> {
+        let inner = self.inner.borrow();
+        let block = match identifier {
+            None => inner.blocks.last(),
+            Some(identifier) => match identifier.get("BlockHash").and_then(Value::as_str) {
+                Some(block_hash) => inner.blocks.iter().find(|block| block.hash == block_hash),
+                None => {
+                    let state_root_hash = identifier
+                        .get("StateRootHash")
+                        .and_then(Value::as_str)
+                        .or_else(|| identifier.as_str());
+                    inner.blocks.iter().find(|block| {
+                        Some(hex::encode(block.state_root.value()).as_str()) == state_root_hash
+                    })
+                }
+            },
+        };
+        block
+            .map(|block| block.state_root)
+            .ok_or_else(|| Error::Rpc("unknown state root hash".to_string()))
+    }
+
+    fn read(
+        &self,
+        state_root: Blake2bHash,
+        key: Key,
+        path: &[String],
+    ) -> Result<StoredValue, Error> {
+        self.inner
+            .borrow()
+            .builder
+            .query(Some(state_root), key, path)
+            .map_err(Error::Rpc)
+    }
+
+    fn query_global_state(&self, params: &Value) -> Result<Value, Error> {
+        let state_root = self.state_root(params.get("state_identifier"))?;
+        let key = parse_key(&params["key"])?;
+        let path: Vec<String> = params
+            .get("path")
+            .and_then(Value::as_array)
+            .map(|path| {
+                path.iter()
+                    .filter_map(Value::as_str)
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+        let stored_value = self.read(state_root, key, &path)?;
+        Ok(json!({
+            "block_header": null,
+            "stored_value": stored_value_json(&stored_value)?,
+            "merkle_proof": "",
+        }))
+    }
+
+    fn get_dictionary_item(&self, params: &Value) -> Result<Value, Error> {
+        let state_root = self.state_root(params.get("state_root_hash"))?;
+        let identifier = &params["dictionary_identifier"];
+        let key = if let Some(dictionary) = identifier.get("Dictionary") {
+            parse_key(dictionary)?
+        } else {
+            let (seed_uref, item) = if let Some(uref) = identifier.get("URef") {
+                let seed_uref =
+                    URef::from_formatted_str(uref["seed_uref"].as_str().unwrap_or_default())
+                        .map_err(|error| Error::Rpc(format!("invalid seed uref: {:?}", error)))?;
+                (seed_uref, &uref["dictionary_item_key"])
+            } else {
+                let named_key = identifier
+                    .get("ContractNamedKey")
+                    .or_else(|| identifier.get("AccountNamedKey"))
+                    .ok_or_else(|| Error::Rpc("unsupported dictionary identifier".to_string()))?;
+                let base = parse_key(&named_key["key"])?;
+                let name = named_key["dictionary_name"].as_str().unwrap_or_default();
+                let named_keys = match self.read(state_root, base, &[])? {
+                    StoredValue::Contract(contract) => contract.named_keys().clone(),
+                    StoredValue::Account(account) => account.named_keys().clone(),
+                    _ => {
+                        return Err(Error::Rpc(format!(
+                            "{} has no named keys",
+                            named_key["key"]
+                        )))
+                    }
+                };
+                let seed_uref = named_keys
+                    .get(name)
+                    .and_then(Key::as_uref)
+                    .copied()
+                    .ok_or_else(|| Error::Rpc(format!("no dictionary named {}", name)))?;
+                (seed_uref, &named_key["dictionary_item_key"])
+            };
+            let item = item
+                .as_str()
+                .ok_or_else(|| Error::Rpc("no dictionary item key".to_string()))?;
+            Key::dictionary(seed_uref, item.as_bytes())
+        };
+        let stored_value = self.read(state_root, key, &[])?;
+        Ok(json!({
+            "dictionary_key": key.to_formatted_string(),
+            "stored_value": stored_value_json(&stored_value)?,
+            "merkle_proof": "",
+        }))
+    }
+}
+
+fn parse_key(value: &Value) -> Result<Key, Error> {
+    let key = value
+        .as_str()
+        .ok_or_else(|| Error::Rpc("no key".to_string()))?;
+    Key::from_formatted_str(key)
+        .map_err(|error| Error::Rpc(format!("invalid key {}: {:?}", key, error)))
+}
+
+fn stored_value_json(stored_value: &StoredValue) -> Result<Value, Error> {
+    let stored_value = json_compatibility::StoredValue::try_from(stored_value)
+        .map_err(|error| Error::Rpc(format!("{:?}", error)))?;
+    serde_json::to_value(stored_value).map_err(rpc_error)
+}
+
+impl Transport for MockNode {
+    fn call(&self, method: &str, params: Value) -> Result<Value, Error> {
+        match method {
+            "account_put_deploy" => self.put_deploy(&params),
+            "info_get_deploy" => self.get_deploy(&params),
+            "chain_get_block" => self.get_block(&params),
+            "chain_get_state_root_hash" => {
+                let state_root = self.state_root(None)?;
+                Ok(json!({ "state_root_hash": hex::encode(state_root.value()) }))
+            }
+            "query_global_state" => self.query_global_state(&params),
+            "state_get_dictionary_item" => self.get_dictionary_item(&params),
+            other => Err(Error::Rpc(format!("method {} not supported", other))),
+        }
+    }
+}
+
+impl Transport for &MockNode {
+    fn call(&self, method: &str, params: Value) -> Result<Value, Error> {
+        (**self).call(method, params)
+    }
+}
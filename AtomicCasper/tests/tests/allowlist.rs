@@ -0,0 +1,71 @@
+use casper_types::{runtime_args, ContractHash, Key, PublicKey, RuntimeArgs, U256};
+use swap_client::{args::Asset, rpc::NodeClient, ContractError, TokenContract};
+use swap_tests::{
+    harness::{assert_reverted, assert_success, builder, initiate_with, install_as, send},
+    test_token_wasm,
+};
+
+#[test]
+fn only_takes_listed_tokens_once_enforced() {
+    let swap = install_as("NFT");
+    let owner = PublicKey::from(&swap.owner);
+    assert_success(&send(
+        &swap.node,
+        &builder().install(test_token_wasm(), "NFT", &swap.owner),
+    ));
+    let token = match swap.node.named_key(&owner, "test_token") {
+        Some(Key::Hash(hash)) => ContractHash::new(hash),
+        other => panic!("no token installed: {:?}", other),
+    };
+    let token_contract = TokenContract::Contract(token);
+    let package = swap
+        .node
+        .named_key(&owner, "atomic_swap_package_hash")
+        .unwrap();
+    let mint = builder().call(
+        token,
+        "mint",
+        runtime_args! {
+            "recipient" => package,
+            "token_ids" => vec![U256::one()],
+        },
+        &swap.owner,
+    );
+    assert_success(&send(&swap.node, &mint));
+    let initiate = || {
+        let asset = Asset::Nft {
+            token_contract,
+            token_ids: vec![U256::one()],
+        };
+        initiate_with(&swap, asset)
+    };
+    let allow = |standard: &str, secret_key| {
+        let deploy =
+            builder().allow_token(swap.contract_hash, token_contract, standard, secret_key);
+        send(&swap.node, &deploy)
+    };
+
+    assert_reverted(
+        &allow("CEP-47", &swap.receiver),
+        ContractError::AccessDenied,
+    );
+    assert_reverted(
+        &allow("ERC-721", &swap.owner),
+        ContractError::UnknownTokenStandard,
+    );
+    let enforce = builder().enforce_allowlist(swap.contract_hash, true, &swap.owner);
+    assert_success(&send(&swap.node, &enforce));
+    assert_reverted(&initiate(), ContractError::TokenNotAllowed);
+    assert_success(&allow("CEP-18", &swap.owner));
+    assert_reverted(&initiate(), ContractError::TokenStandardMismatch);
+    assert_success(&allow("CEP-47", &swap.owner));
+    let allowlist = NodeClient::new(&swap.node)
+        .allowlist(swap.contract_hash)
+        .unwrap();
+    assert!(allowlist.enforced);
+    assert_eq!(
+        allowlist.tokens.get(&token_contract).map(String::as_str),
+        Some("CEP-47")
+    );
+    assert_success(&initiate());
+}
@@ -0,0 +1,182 @@
+use casper_types::{ContractHash, ContractPackageHash, Key, PublicKey, SecretKey};
+use swap_client::{
+    args::sign_redemption, query::SwapField, rpc::NodeClient, ContractError, Initiate, Redemption,
+    SwapState,
+};
+use swap_tests::{
+    batch_wasm, contract_wasm,
+    harness::{assert_reverted, assert_success, builder, custom_of, send, CHAIN_NAME, TIMEOUT},
+    MockNode,
+};
+
+const SECRETS: [&str; 2] = ["ac3b9e1f", "5d20c4a7"];
+
+struct Batch {
+    node: MockNode,
+    owner: SecretKey,
+    receiver: SecretKey,
+    contract_hashes: Vec<ContractHash>,
+}
+
+/// Two `Custom` swap contracts of the owner, installed by a factory.
+fn setup() -> Batch {
+    let owner = SecretKey::ed25519_from_bytes([1u8; 32]).unwrap();
+    let receiver = SecretKey::ed25519_from_bytes([2u8; 32]).unwrap();
+    let node = MockNode::new(
+        CHAIN_NAME,
+        &[PublicKey::from(&owner), PublicKey::from(&receiver)],
+    );
+    assert_success(&send(
+        &node,
+        &builder().install(contract_wasm(), "Factory", &owner),
+    ));
+    let factory_hash = match node.named_key(&PublicKey::from(&owner), "atomic_swap_factory") {
+        Some(Key::Hash(hash)) => ContractHash::new(hash),
+        other => panic!("no factory installed: {:?}", other),
+    };
+    for _ in 0..SECRETS.len() {
+        let create_swap = builder().create_swap(factory_hash, "Custom", &owner);
+        assert_success(&send(&node, &create_swap));
+    }
+    let owner_key = Key::Account(PublicKey::from(&owner).to_account_hash());
+    let contract_hashes = NodeClient::new(&node)
+        .instances(factory_hash, owner_key)
+        .unwrap();
+    Batch {
+        node,
+        owner,
+        receiver,
+        contract_hashes,
+    }
+}
+
+fn initiate(batch: &Batch, contract_hash: ContractHash, secret: &str) -> (ContractHash, Initiate) {
+    let receiver = Key::Account(PublicKey::from(&batch.receiver).to_account_hash());
+    let asset = custom_of(&batch.node, &batch.owner, contract_hash);
+    let initiate = Initiate::new(asset, receiver, TIMEOUT)
+        .with_secret("sha3-256", secret)
+        .unwrap();
+    (contract_hash, initiate)
+}
+
+fn states(batch: &Batch) -> Vec<Vec<SwapState>> {
+    let client = NodeClient::new(&batch.node);
+    batch
+        .contract_hashes
+        .iter()
+        .map(|contract_hash| {
+            let swaps = client.swaps(*contract_hash, 0, 10).unwrap();
+            swaps.into_iter().map(|swap| swap.state).collect()
+        })
+        .collect()
+}
+
+#[test]
+fn initiates_and_unlocks_all_or_nothing() {
+    let batch = setup();
+    let (first, second) = (batch.contract_hashes[0], batch.contract_hashes[1]);
+    let initiate_batch = |swaps: &[(ContractHash, Initiate)]| {
+        let deploy = builder()
+            .initiate_batch(batch_wasm(), swaps, &batch.owner)
+            .unwrap();
+        send(&batch.node, &deploy)
+    };
+    assert_reverted(&initiate_batch(&[]), ContractError::EmptyBatch);
+    // the second swap on the same contract fails, and takes the first one with it
+    let twice = [
+        initiate(&batch, first, SECRETS[0]),
+        initiate(&batch, first, SECRETS[1]),
+    ];
+    assert_reverted(
+        &initiate_batch(&twice),
+        ContractError::ContractAlreadyInitialized,
+    );
+    assert_eq!(states(&batch), vec![vec![], vec![]]);
+
+    let swaps = [
+        initiate(&batch, first, SECRETS[0]),
+        initiate(&batch, second, SECRETS[1]),
+    ];
+    assert_success(&initiate_batch(&swaps));
+    let open = vec![SwapState::Open];
+    assert_eq!(states(&batch), vec![open.clone(), open.clone()]);
+
+    let unlock_batch = |swap_ids: [u64; 2]| {
+        let redemptions = [first, second]
+            .iter()
+            .zip(swap_ids.iter())
+            .zip(SECRETS.iter())
+            .map(|((contract_hash, swap_id), secret)| Redemption {
+                contract_hash: *contract_hash,
+                swap_id: *swap_id,
+                secret: secret.to_string(),
+                signed: None,
+            })
+            .collect();
+        let deploy = builder().unlock_batch(batch_wasm(), redemptions, &batch.receiver);
+        send(&batch.node, &deploy)
+    };
+    assert_reverted(&unlock_batch([1, 2]), ContractError::SwapIdMismatch);
+    assert_eq!(states(&batch), vec![open.clone(), open]);
+    assert_success(&unlock_batch([1, 1]));
+    let redeemed = vec![SwapState::Redeemed];
+    assert_eq!(states(&batch), vec![redeemed.clone(), redeemed]);
+}
+
+#[test]
+fn unlocks_swaps_of_a_receiver_public_key() {
+    let batch = setup();
+    let (first, second) = (batch.contract_hashes[0], batch.contract_hashes[1]);
+    let (_, signed) = initiate(&batch, second, SECRETS[1]);
+    let swaps = [
+        initiate(&batch, first, SECRETS[0]),
+        (
+            second,
+            signed.with_receiver_public_key(PublicKey::from(&batch.receiver)),
+        ),
+    ];
+    let deploy = builder()
+        .initiate_batch(batch_wasm(), &swaps, &batch.owner)
+        .unwrap();
+    assert_success(&send(&batch.node, &deploy));
+
+    let redemptions = |signed: Option<(Key, _)>| {
+        vec![
+            Redemption {
+                contract_hash: first,
+                swap_id: 1,
+                secret: SECRETS[0].to_string(),
+                signed: None,
+            },
+            Redemption {
+                contract_hash: second,
+                swap_id: 1,
+                secret: SECRETS[1].to_string(),
+                signed,
+            },
+        ]
+    };
+    // the signed swap needs its payout, and takes the other one with it
+    let deploy = builder().unlock_batch(batch_wasm(), redemptions(None), &batch.receiver);
+    let result = send(&batch.node, &deploy);
+    assert!(result.get("Failure").is_some(), "{}", result);
+    let open = vec![SwapState::Open];
+    assert_eq!(states(&batch), vec![open.clone(), open]);
+
+    // the receiver has the payout go to a fresh key
+    let payout = Key::Account(
+        PublicKey::from(&SecretKey::ed25519_from_bytes([3u8; 32]).unwrap()).to_account_hash(),
+    );
+    let package_hash: ContractPackageHash = NodeClient::new(&batch.node)
+        .query(second, SwapField::ContractPackageHash)
+        .unwrap();
+    let signature = sign_redemption(&batch.receiver, package_hash, 1, SECRETS[1], payout);
+    let deploy = builder().unlock_batch(
+        batch_wasm(),
+        redemptions(Some((payout, signature))),
+        &batch.receiver,
+    );
+    assert_success(&send(&batch.node, &deploy));
+    let redeemed = vec![SwapState::Redeemed];
+    assert_eq!(states(&batch), vec![redeemed.clone(), redeemed]);
+}
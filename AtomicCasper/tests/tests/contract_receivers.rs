@@ -0,0 +1,105 @@
+use casper_types::{runtime_args, ContractHash, Key, PublicKey, RuntimeArgs, U512};
+use serde_json::Value;
+use swap_client::{args::Asset, rpc::NodeClient, ContractError, Initiate, Unlock};
+use swap_tests::{
+    harness::{
+        assert_reverted, assert_success, builder, install_as, install_vault, register_vault, send,
+        Swap, SECRET, TIMEOUT,
+    },
+    session_wasm,
+};
+
+const CSPR: u64 = 1_000_000_000;
+
+/// Deposits `amount` of the owner and locks it for `receiver`.
+fn initiate_for(swap: &Swap, receiver: Key, amount: U512) -> Value {
+    let deposit = builder().deposit(session_wasm(), swap.contract_hash, amount, &swap.owner);
+    assert_success(&send(&swap.node, &deposit));
+    let initiate = Initiate::new(Asset::Direct { amount }, receiver, TIMEOUT)
+        .with_secret("sha3-256", SECRET)
+        .unwrap();
+    let deploy = builder()
+        .initiate(swap.contract_hash, &initiate, &swap.owner)
+        .unwrap();
+    send(&swap.node, &deploy)
+}
+
+fn withdraw(swap: &Swap, vault: ContractHash, amount: U512) {
+    let withdraw = builder().call(
+        vault,
+        "withdraw",
+        runtime_args! { "amount" => amount },
+        &swap.owner,
+    );
+    assert_success(&send(&swap.node, &withdraw));
+}
+
+#[test]
+fn credits_cspr_paid_to_contracts() {
+    let swap = install_as("Direct");
+    let client = NodeClient::new(&swap.node);
+    let (treasury_vault, treasury) = install_vault(&swap);
+    register_vault(&swap, treasury_vault, false);
+    let (vault, package) = install_vault(&swap);
+    register_vault(&swap, vault, false);
+    let deploy = builder().set_treasury(swap.contract_hash, treasury, &swap.owner);
+    assert_success(&send(&swap.node, &deploy));
+    let deploy = builder().set_fee(swap.contract_hash, "Direct", 50, &swap.owner);
+    assert_success(&send(&swap.node, &deploy));
+
+    let amount = U512::from(100 * CSPR);
+    assert_success(&initiate_for(&swap, package, amount));
+
+    // anyone who knows the secret can redeem to the vault
+    let receiver = PublicKey::from(&swap.receiver).to_account_hash();
+    let balance = swap.node.balance(receiver);
+    let unlock = builder()
+        .unlock(swap.contract_hash, &Unlock::new(SECRET), &swap.receiver)
+        .unwrap();
+    assert_success(&send(&swap.node, &unlock));
+    let paid = U512::from(100 * CSPR - CSPR / 2);
+    assert_eq!(client.deposit(swap.contract_hash, package).unwrap(), paid);
+    assert_eq!(
+        client.deposit(swap.contract_hash, treasury).unwrap(),
+        U512::from(CSPR / 2)
+    );
+    // the receiver account only paid for the deploy
+    assert!(swap.node.balance(receiver) <= balance);
+
+    // the vault takes what it was credited into its own purse
+    withdraw(&swap, vault, paid);
+    assert_eq!(
+        client.deposit(swap.contract_hash, package).unwrap(),
+        U512::zero()
+    );
+    withdraw(&swap, treasury_vault, U512::from(CSPR / 2));
+    assert_eq!(
+        client.deposit(swap.contract_hash, treasury).unwrap(),
+        U512::zero()
+    );
+}
+
+#[test]
+fn pays_only_contracts_that_registered_their_package() {
+    let swap = install_as("Direct");
+    let amount = U512::from(100 * CSPR);
+    let (vault, package) = install_vault(&swap);
+    // before it registered, the vault can't be paid
+    assert_reverted(
+        &initiate_for(&swap, package, amount),
+        ContractError::ReceiverNotRegistered,
+    );
+    let deploy = builder().set_treasury(swap.contract_hash, package, &swap.owner);
+    assert_reverted(
+        &send(&swap.node, &deploy),
+        ContractError::ReceiverNotRegistered,
+    );
+
+    // it calls with its package, its contract hash could never withdraw
+    register_vault(&swap, vault, false);
+    assert_reverted(
+        &initiate_for(&swap, Key::from(vault), amount),
+        ContractError::ReceiverNotRegistered,
+    );
+    assert_success(&initiate_for(&swap, package, amount));
+}
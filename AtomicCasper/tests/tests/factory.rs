@@ -0,0 +1,68 @@
+use casper_types::{runtime_args, ContractHash, Key, PublicKey, RuntimeArgs, SecretKey};
+use swap_client::{constants, hashlock, rpc::NodeClient, ContractError};
+use swap_tests::{
+    contract_wasm,
+    harness::{
+        assert_reverted, assert_success, builder, custom, initiate, initiate_with, send, Swap,
+        CHAIN_NAME, SECRET,
+    },
+    MockNode,
+};
+
+#[test]
+fn registers_the_swaps_it_installs() {
+    let owner = SecretKey::ed25519_from_bytes([1u8; 32]).unwrap();
+    let receiver = SecretKey::ed25519_from_bytes([2u8; 32]).unwrap();
+    let node = MockNode::new(
+        CHAIN_NAME,
+        &[PublicKey::from(&owner), PublicKey::from(&receiver)],
+    );
+    assert_success(&send(
+        &node,
+        &builder().install(contract_wasm(), "Factory", &owner),
+    ));
+    let factory_hash = match node.named_key(&PublicKey::from(&owner), "atomic_swap_factory") {
+        Some(Key::Hash(hash)) => ContractHash::new(hash),
+        other => panic!("no factory installed: {:?}", other),
+    };
+
+    let create_swap = builder().create_swap(factory_hash, "Custom", &owner);
+    assert_success(&send(&node, &create_swap));
+    let client = NodeClient::new(&node);
+    let owner_key = Key::Account(PublicKey::from(&owner).to_account_hash());
+    let instances = client.instances(factory_hash, owner_key).unwrap();
+    assert_eq!(instances.len(), 1);
+
+    let swap = Swap {
+        node,
+        owner,
+        receiver,
+        contract_hash: instances[0],
+    };
+    initiate(&swap);
+    let client = NodeClient::new(&swap.node);
+    let hash = hashlock("sha3-256", SECRET).unwrap();
+    assert_eq!(client.find_swap(factory_hash, &hash).unwrap(), instances[0]);
+
+    // only the factory's own instances can record swaps
+    let record = builder().call(
+        factory_hash,
+        constants::ENTRY_POINT_RECORD_SWAP,
+        runtime_args! { constants::ARG_HASH => hash },
+        &swap.owner,
+    );
+    assert_reverted(&send(&swap.node, &record), ContractError::UnknownInstance);
+
+    // nor can another instance take a hashlock one of them was locked with
+    let create_swap = builder().create_swap(factory_hash, "Custom", &swap.owner);
+    assert_success(&send(&swap.node, &create_swap));
+    let instances = client.instances(factory_hash, owner_key).unwrap();
+    let second = Swap {
+        contract_hash: instances[1],
+        ..swap
+    };
+    assert_reverted(
+        &initiate_with(&second, custom(&second)),
+        ContractError::HashlockReused,
+    );
+}
@@ -0,0 +1,42 @@
+use casper_types::{account::AccountHash, Key, U512};
+use swap_client::{args::Asset, rpc::NodeClient, ContractError, Unlock};
+use swap_tests::{
+    harness::{assert_reverted, assert_success, builder, initiate_with, install_as, send, SECRET},
+    session_wasm,
+};
+
+const CSPR: u64 = 1_000_000_000;
+
+#[test]
+fn pays_fee_to_treasury_on_redeem() {
+    let swap = install_as("Direct");
+    let client = NodeClient::new(&swap.node);
+    let treasury = AccountHash::new([7u8; 32]);
+
+    let set_fee = |fee_bps: u32, secret_key| {
+        let deploy = builder().set_fee(swap.contract_hash, "Direct", fee_bps, secret_key);
+        send(&swap.node, &deploy)
+    };
+    assert_reverted(&set_fee(50, &swap.owner), ContractError::TreasuryNotSet);
+    let deploy = builder().set_treasury(swap.contract_hash, Key::Account(treasury), &swap.owner);
+    assert_success(&send(&swap.node, &deploy));
+    assert_reverted(&set_fee(50, &swap.receiver), ContractError::AccessDenied);
+    assert_reverted(&set_fee(1_001, &swap.owner), ContractError::FeeTooHigh);
+    assert_success(&set_fee(50, &swap.owner));
+    let fees = client.fees(swap.contract_hash).unwrap();
+    assert_eq!(fees.treasury, Some(Key::Account(treasury)));
+    assert_eq!(fees.rates.get("Direct"), Some(&50));
+
+    let amount = U512::from(100 * CSPR);
+    let deposit = builder().deposit(session_wasm(), swap.contract_hash, amount, &swap.owner);
+    assert_success(&send(&swap.node, &deposit));
+    assert_success(&initiate_with(&swap, Asset::Direct { amount }));
+    // a later change doesn't apply to the open swap
+    assert_success(&set_fee(1_000, &swap.owner));
+
+    let unlock = builder()
+        .unlock(swap.contract_hash, &Unlock::new(SECRET), &swap.receiver)
+        .unwrap();
+    assert_success(&send(&swap.node, &unlock));
+    assert_eq!(swap.node.balance(treasury), U512::from(CSPR / 2));
+}
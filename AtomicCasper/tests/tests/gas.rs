@@ -0,0 +1,156 @@
+use std::env;
+
+use casper_types::{runtime_args, ContractHash, Key, PublicKey, RuntimeArgs, U256, U512};
+use serde_json::Value;
+use swap_client::{args::Asset, Initiate, TokenContract, Unlock, HASH_TYPES};
+use swap_tests::{
+    contract_wasm,
+    gas::{self, Baseline, Costs},
+    harness::{
+        assert_success, builder, custom, initiate_with, install, install_as, send, Swap, SECRET,
+        TIMEOUT,
+    },
+    session_wasm, test_token_wasm,
+};
+
+const CSPR: u64 = 1_000_000_000;
+
+/// A test token of `standard` installed by the owner of `swap`, with what `mint` gives the
+/// swap contract's package.
+fn token(swap: &Swap, standard: &str, mint: impl FnOnce(Key) -> RuntimeArgs) -> TokenContract {
+    let owner = PublicKey::from(&swap.owner);
+    assert_success(&send(
+        &swap.node,
+        &builder().install(test_token_wasm(), standard, &swap.owner),
+    ));
+    let token = match swap.node.named_key(&owner, "test_token") {
+        Some(Key::Hash(hash)) => ContractHash::new(hash),
+        other => panic!("no token installed: {:?}", other),
+    };
+    let package = swap
+        .node
+        .named_key(&owner, "atomic_swap_package_hash")
+        .unwrap();
+    let deploy = builder().call(token, "mint", mint(package), &swap.owner);
+    assert_success(&send(&swap.node, &deploy));
+    TokenContract::Contract(token)
+}
+
+fn initiate_cost(swap: &Swap, asset: Asset) -> u64 {
+    gas::cost(&initiate_with(swap, asset))
+}
+
+fn unlock(swap: &Swap) -> Value {
+    let deploy = builder()
+        .unlock(swap.contract_hash, &Unlock::new(SECRET), &swap.receiver)
+        .unwrap();
+    send(&swap.node, &deploy)
+}
+
+fn measure() -> Costs {
+    let mut costs = Costs::new();
+    let mut record = |name: String, cost: u64| {
+        costs.insert(name, cost);
+    };
+
+    // a first install on the receiver's account, the owner's is taken by the harness
+    let swap = install();
+    let deploy = builder().install(contract_wasm(), "Custom", &swap.receiver);
+    record("install".to_string(), gas::cost(&send(&swap.node, &deploy)));
+
+    let asset = custom(&swap);
+    record("initiate/Custom".to_string(), initiate_cost(&swap, asset));
+    swap.node.advance_time(TIMEOUT);
+    let refund = builder().refund(swap.contract_hash, &swap.owner);
+    record("refund".to_string(), gas::cost(&send(&swap.node, &refund)));
+
+    let swap = install_as("Direct");
+    let amount = U512::from(100 * CSPR);
+    let deposit = builder().deposit(session_wasm(), swap.contract_hash, amount, &swap.owner);
+    record(
+        "deposit".to_string(),
+        gas::cost(&send(&swap.node, &deposit)),
+    );
+    record(
+        "initiate/Direct".to_string(),
+        initiate_cost(&swap, Asset::Direct { amount }),
+    );
+
+    let swap = install_as("ERC-20");
+    let amount = U256::from(1_000u64);
+    let token_contract = token(&swap, "ERC-20", |package| {
+        runtime_args! { "recipient" => package, "amount" => amount }
+    });
+    let asset = Asset::Erc20 {
+        token_contract,
+        amount,
+    };
+    record("initiate/ERC-20".to_string(), initiate_cost(&swap, asset));
+
+    for count in [1u64, 10, 100] {
+        let swap = install_as("NFT");
+        let token_ids: Vec<U256> = (1..=count).map(U256::from).collect();
+        let ids = token_ids.clone();
+        let token_contract = token(&swap, "NFT", |package| {
+            runtime_args! { "recipient" => package, "token_ids" => ids }
+        });
+        let asset = Asset::Nft {
+            token_contract,
+            token_ids,
+        };
+        record(
+            format!("initiate/NFT/{}", count),
+            initiate_cost(&swap, asset),
+        );
+    }
+
+    for hash_type in HASH_TYPES.iter() {
+        let swap = install();
+        let receiver = Key::Account(PublicKey::from(&swap.receiver).to_account_hash());
+        let initiate = Initiate::new(custom(&swap), receiver, TIMEOUT)
+            .with_secret(hash_type, SECRET)
+            .unwrap();
+        let deploy = builder()
+            .initiate(swap.contract_hash, &initiate, &swap.owner)
+            .unwrap();
+        assert_success(&send(&swap.node, &deploy));
+        record(format!("unlock/{}", hash_type), gas::cost(&unlock(&swap)));
+    }
+    costs
+}
+
+#[test]
+fn entry_point_costs_stay_within_baseline() {
+    let costs = measure();
+    let mut baseline = Baseline::load();
+    for (name, cost) in &costs {
+        eprintln!("{}: {} gas", name, cost);
+        if !baseline.costs.contains_key(name) {
+            eprintln!("{}: not in the baseline yet", name);
+        }
+    }
+    if env::var_os("UPDATE_GAS_BASELINE").is_some() {
+        baseline.costs = costs;
+        baseline.save();
+        return;
+    }
+    let regressions = baseline.regressions(&costs);
+    assert!(
+        regressions.is_empty(),
+        "gas regressed beyond {}%:\n{}\nrerun with UPDATE_GAS_BASELINE=1 if it is expected",
+        baseline.threshold_percent,
+        regressions.join("\n")
+    );
+}
+
+#[test]
+fn flags_costs_above_the_threshold() {
+    let mut baseline = Baseline::default();
+    baseline.costs.insert("unlock".to_string(), 1_000);
+    let mut costs = Costs::new();
+    costs.insert("unlock".to_string(), 1_050);
+    costs.insert("refund".to_string(), 5_000);
+    assert!(baseline.regressions(&costs).is_empty());
+    costs.insert("unlock".to_string(), 1_051);
+    assert_eq!(baseline.regressions(&costs).len(), 1);
+}
@@ -0,0 +1,135 @@
+use casper_types::{Key, PublicKey};
+use serde_json::Value;
+use swap_client::{
+    constants, hashlock, query::SwapField, rpc::NodeClient, salted_hashlock, ContractError,
+    Initiate, Unlock,
+};
+use swap_tests::harness::{
+    assert_reverted, assert_success, builder, custom, initiate, initiate_with,
+    initiate_with_secret, install, send, Swap, SECRET, TIMEOUT,
+};
+
+fn unlock(swap: &Swap) -> Value {
+    let deploy = builder()
+        .unlock(swap.contract_hash, &Unlock::new(SECRET), &swap.receiver)
+        .unwrap();
+    send(&swap.node, &deploy)
+}
+
+/// Opens a `Custom` swap locked with `secret` salted for swap `swap_id` of the contract.
+fn initiate_salted(swap: &Swap, secret: &str, swap_id: u64) -> Value {
+    let client = NodeClient::new(&swap.node);
+    let (contract_package_hash, _next_swap_id) = client.salt(swap.contract_hash).unwrap();
+    let receiver = Key::Account(PublicKey::from(&swap.receiver).to_account_hash());
+    let initiate = Initiate::new(custom(swap), receiver, TIMEOUT)
+        .with_salted_secret("sha3-256", secret, contract_package_hash, swap_id)
+        .unwrap();
+    let deploy = builder()
+        .initiate(swap.contract_hash, &initiate, &swap.owner)
+        .unwrap();
+    send(&swap.node, &deploy)
+}
+
+#[test]
+fn rejects_hashlocks_it_was_locked_with() {
+    let swap = install();
+    initiate(&swap);
+    assert_success(&unlock(&swap));
+    // the secret is public now
+    assert_reverted(
+        &initiate_with(&swap, custom(&swap)),
+        ContractError::HashlockReused,
+    );
+
+    // a refunded secret may have been revealed on the other leg
+    let other = "5d20c4a7";
+    assert_success(&initiate_with_secret(&swap, custom(&swap), other));
+    swap.node.advance_time(TIMEOUT);
+    let refund = builder().refund(swap.contract_hash, &swap.owner);
+    assert_success(&send(&swap.node, &refund));
+    assert_reverted(
+        &initiate_with_secret(&swap, custom(&swap), other),
+        ContractError::HashlockReused,
+    );
+}
+
+#[test]
+fn salted_hashlocks_open_their_own_swap() {
+    let swap = install();
+    let client = NodeClient::new(&swap.node);
+    let (_contract_package_hash, swap_id) = client.salt(swap.contract_hash).unwrap();
+    assert_eq!(swap_id, 1);
+    assert_success(&initiate_salted(&swap, SECRET, swap_id));
+    assert!(client
+        .query::<bool>(swap.contract_hash, SwapField::Salted)
+        .unwrap());
+    assert_success(&unlock(&swap));
+    assert!(!client
+        .query::<bool>(swap.contract_hash, SwapField::Salted)
+        .unwrap());
+
+    // a secret salted for another swap id only opens that swap
+    let other = "5d20c4a7";
+    let (_contract_package_hash, swap_id) = client.salt(swap.contract_hash).unwrap();
+    assert_eq!(swap_id, 2);
+    assert_success(&initiate_salted(&swap, other, swap_id + 1));
+    let deploy = builder()
+        .unlock(swap.contract_hash, &Unlock::new(other), &swap.receiver)
+        .unwrap();
+    assert_reverted(&send(&swap.node, &deploy), ContractError::HashMismatch);
+}
+
+#[test]
+fn rejects_secrets_salted_again() {
+    let swap = install();
+    let client = NodeClient::new(&swap.node);
+    let (_contract_package_hash, swap_id) = client.salt(swap.contract_hash).unwrap();
+    assert_success(&initiate_salted(&swap, SECRET, swap_id));
+    assert_success(&unlock(&swap));
+    // salted for the next swap the hashlock is new, but the secret is public: anyone could
+    // unlock with it, so the contract turns down its bare hashlock
+    let (_contract_package_hash, swap_id) = client.salt(swap.contract_hash).unwrap();
+    assert_reverted(
+        &initiate_salted(&swap, SECRET, swap_id),
+        ContractError::HashlockReused,
+    );
+    // and the hashlock of the bare secret in an unsalted swap
+    assert_reverted(
+        &initiate_with(&swap, custom(&swap)),
+        ContractError::HashlockReused,
+    );
+}
+
+#[test]
+fn salted_swaps_need_the_hashlock_of_the_bare_secret() {
+    let swap = install();
+    let client = NodeClient::new(&swap.node);
+    let (contract_package_hash, swap_id) = client.salt(swap.contract_hash).unwrap();
+    let hash = salted_hashlock("sha3-256", SECRET, contract_package_hash, swap_id).unwrap();
+    let receiver = Key::Account(PublicKey::from(&swap.receiver).to_account_hash());
+    let mut args = Initiate::new(custom(&swap), receiver, TIMEOUT)
+        .with_hashlock("sha3-256", &hash)
+        .build()
+        .unwrap();
+    args.insert(constants::ARG_SALTED, true).unwrap();
+    let deploy = builder().call(
+        swap.contract_hash,
+        constants::ENTRY_POINT_INITIATE,
+        args,
+        &swap.owner,
+    );
+    assert_reverted(
+        &send(&swap.node, &deploy),
+        ContractError::SecretHashRequired,
+    );
+
+    // a wrong one never unlocks
+    let initiate = Initiate::new(custom(&swap), receiver, TIMEOUT)
+        .with_hashlock("sha3-256", &hash)
+        .salted(&hashlock("sha3-256", "5d20c4a7").unwrap());
+    let deploy = builder()
+        .initiate(swap.contract_hash, &initiate, &swap.owner)
+        .unwrap();
+    assert_success(&send(&swap.node, &deploy));
+    assert_reverted(&unlock(&swap), ContractError::HashMismatch);
+}
@@ -0,0 +1,125 @@
+use casper_types::{runtime_args, ContractHash, Key, RuntimeArgs, U512};
+use serde_json::json;
+use swap_client::{
+    args::Asset,
+    query::{self, SwapField},
+    rpc::{NodeClient, Transport},
+    ContractError, Initiate, Unlock,
+};
+use swap_tests::{
+    harness::{
+        assert_reverted, assert_success, builder, install_as, install_vault, register_vault, send,
+        Swap, SECRET, TIMEOUT,
+    },
+    session_wasm,
+};
+
+const CSPR: u64 = 1_000_000_000;
+
+/// Locks 100 CSPR of the owner for `receiver` and redeems it with the receiver account.
+fn redeem_to(swap: &Swap, receiver: Key) {
+    let amount = U512::from(100 * CSPR);
+    let deposit = builder().deposit(session_wasm(), swap.contract_hash, amount, &swap.owner);
+    assert_success(&send(&swap.node, &deposit));
+    let initiate = Initiate::new(Asset::Direct { amount }, receiver, TIMEOUT)
+        .with_secret("sha3-256", SECRET)
+        .unwrap();
+    let deploy = builder()
+        .initiate(swap.contract_hash, &initiate, &swap.owner)
+        .unwrap();
+    assert_success(&send(&swap.node, &deploy));
+    let unlock = builder()
+        .unlock(swap.contract_hash, &Unlock::new(SECRET), &swap.receiver)
+        .unwrap();
+    assert_success(&send(&swap.node, &unlock));
+}
+
+/// What the vault recorded of its `on_swap_received` calls.
+fn received(swap: &Swap, vault: ContractHash) -> Vec<(u64, String, U512)> {
+    let state_root_hash = NodeClient::new(&swap.node).state_root_hash().unwrap();
+    let result = swap
+        .node
+        .call(
+            "query_global_state",
+            json!({
+                "state_identifier": { "StateRootHash": state_root_hash },
+                "key": format!("hash-{}", hex::encode(vault.value())),
+                "path": ["received"],
+            }),
+        )
+        .unwrap();
+    query::decode(&result["stored_value"]).unwrap()
+}
+
+#[test]
+fn calls_back_contract_receivers_with_a_hook() {
+    let swap = install_as("Direct");
+    let client = NodeClient::new(&swap.node);
+    let (vault, package) = install_vault(&swap);
+    register_vault(&swap, vault, true);
+
+    redeem_to(&swap, package);
+    let swap_id = client
+        .query::<u64>(swap.contract_hash, SwapField::SwapId)
+        .unwrap();
+    assert_eq!(
+        received(&swap, vault),
+        vec![(swap_id, "Direct".to_string(), U512::from(100 * CSPR))]
+    );
+    // the hook withdrew what the swap credited it, in the same deploy
+    assert_eq!(
+        client.deposit(swap.contract_hash, package).unwrap(),
+        U512::zero()
+    );
+}
+
+#[test]
+fn skips_receivers_that_disabled_their_hook() {
+    let swap = install_as("Direct");
+    let client = NodeClient::new(&swap.node);
+    let (vault, package) = install_vault(&swap);
+    register_vault(&swap, vault, true);
+    register_vault(&swap, vault, false);
+
+    redeem_to(&swap, package);
+    assert!(received(&swap, vault).is_empty());
+    assert_eq!(
+        client.deposit(swap.contract_hash, package).unwrap(),
+        U512::from(100 * CSPR)
+    );
+}
+
+#[test]
+fn rejects_receivers_given_by_contract_hash() {
+    let swap = install_as("Direct");
+    let (vault, _package) = install_vault(&swap);
+    register_vault(&swap, vault, true);
+
+    // the hook is registered under the package, the contract hash would never be called back
+    let amount = U512::from(100 * CSPR);
+    let deposit = builder().deposit(session_wasm(), swap.contract_hash, amount, &swap.owner);
+    assert_success(&send(&swap.node, &deposit));
+    let initiate = Initiate::new(Asset::Direct { amount }, Key::from(vault), TIMEOUT)
+        .with_secret("sha3-256", SECRET)
+        .unwrap();
+    let deploy = builder()
+        .initiate(swap.contract_hash, &initiate, &swap.owner)
+        .unwrap();
+    assert_reverted(
+        &send(&swap.node, &deploy),
+        ContractError::ReceiverNotRegistered,
+    );
+    assert!(received(&swap, vault).is_empty());
+}
+
+#[test]
+fn only_contracts_register_hooks() {
+    let swap = install_as("Direct");
+    let deploy = builder().call(
+        swap.contract_hash,
+        "set_receiver_hook",
+        runtime_args! { "enabled" => true },
+        &swap.owner,
+    );
+    assert_reverted(&send(&swap.node, &deploy), ContractError::NotAContract);
+}
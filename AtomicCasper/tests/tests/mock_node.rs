@@ -0,0 +1,223 @@
+use casper_types::{
+    runtime_args, ContractHash, ContractPackageHash, Key, PublicKey, RuntimeArgs, SecretKey, U512,
+};
+use serde_json::{json, Value};
+use swap_client::{
+    args::Asset,
+    constants, hashlock,
+    query::SwapField,
+    rpc::{NodeClient, Transport},
+    ContractError, DeployBuilder, SwapState, Unlock,
+};
+use swap_tests::{
+    harness::{
+        assert_reverted, assert_success, builder, custom, initiate, initiate_with,
+        initiate_with_secret, install, install_as, send, SECRET, TIMEOUT,
+    },
+    session_wasm,
+};
+
+#[test]
+fn reads_installed_contract() {
+    let swap = install();
+    let client = NodeClient::new(&swap.node);
+    let type_: String = client.query(swap.contract_hash, SwapField::Type).unwrap();
+    assert_eq!(type_, "Custom");
+    let swap_id: u64 = client.query(swap.contract_hash, SwapField::SwapId).unwrap();
+    assert_eq!(swap_id, 0);
+}
+
+#[test]
+fn runs_init_once() {
+    let swap = install();
+    let owner = PublicKey::from(&swap.owner);
+    let package_hash = match swap.node.named_key(&owner, "atomic_swap_package_hash") {
+        Some(Key::Hash(hash)) => ContractPackageHash::new(hash),
+        other => panic!("no package installed: {:?}", other),
+    };
+    let init = builder().call(
+        swap.contract_hash,
+        constants::ENTRY_POINT_INIT,
+        runtime_args! {
+            constants::NAMED_KEY_OWN_CONTRACT_PACKAGE_HASH => package_hash,
+            constants::NAMED_KEY_OWN_CONTRACT_HASH => swap.contract_hash,
+            constants::ARG_TYPE => "Custom".to_string(),
+            constants::ARG_ADMIN => Key::Account(owner.to_account_hash()),
+            constants::ARG_FACTORY => None::<ContractHash>,
+        },
+        &swap.owner,
+    );
+    // the installer's "constructor" group is empty once the contract is installed
+    let result = send(&swap.node, &init);
+    let message = result
+        .pointer("/Failure/error_message")
+        .and_then(Value::as_str)
+        .unwrap_or_else(|| panic!("{}", result));
+    assert!(message.contains("Invalid context"), "{}", message);
+}
+
+#[test]
+fn unlocks_with_secret() {
+    let swap = install();
+    initiate(&swap);
+    let client = NodeClient::new(&swap.node);
+    let hash: String = client.query(swap.contract_hash, SwapField::Hash).unwrap();
+    assert_eq!(hash, hashlock("sha3-256", SECRET).unwrap());
+    let end_time: u64 = client
+        .query(swap.contract_hash, SwapField::EndTime)
+        .unwrap();
+    assert_eq!(end_time, swap.node.block_time() + TIMEOUT);
+
+    let unlock = |secret: &str, secret_key: &SecretKey| {
+        let deploy = builder()
+            .unlock(swap.contract_hash, &Unlock::new(secret), secret_key)
+            .unwrap();
+        send(&swap.node, &deploy)
+    };
+    assert_reverted(&unlock(SECRET, &swap.owner), ContractError::AccessDenied);
+    assert_reverted(
+        &unlock("wrong", &swap.receiver),
+        ContractError::HashMismatch,
+    );
+    assert_success(&unlock(SECRET, &swap.receiver));
+    let hash: String = client.query(swap.contract_hash, SwapField::Hash).unwrap();
+    assert_eq!(hash, "");
+}
+
+#[test]
+fn keeps_swap_history() {
+    let swap = install();
+    initiate(&swap);
+    let client = NodeClient::new(&swap.node);
+    let open = client.swap(swap.contract_hash, 1).unwrap();
+    assert_eq!(open.state, SwapState::Open);
+    assert_eq!(open.asset_type, "Custom");
+    assert_eq!(open.hashlock, hashlock("sha3-256", SECRET).unwrap());
+    assert_eq!(open.end_time, open.start_time + TIMEOUT);
+
+    let deploy = builder()
+        .unlock(swap.contract_hash, &Unlock::new(SECRET), &swap.receiver)
+        .unwrap();
+    assert_success(&send(&swap.node, &deploy));
+    assert_success(&initiate_with_secret(&swap, custom(&swap), "5d20c4a7"));
+
+    let swaps = client.swaps(swap.contract_hash, 0, 10).unwrap();
+    assert_eq!(swaps.len(), 2);
+    assert_eq!(swaps[0].state, SwapState::Redeemed);
+    assert_eq!(swaps[0].hashlock, open.hashlock);
+    assert_eq!(swaps[1].swap_id, 2);
+    assert_eq!(swaps[1].state, SwapState::Open);
+    assert_eq!(client.swaps(swap.contract_hash, 1, 10).unwrap(), swaps[1..]);
+    assert!(client.swap(swap.contract_hash, 3).is_err());
+}
+
+#[test]
+fn refunds_after_timeout() {
+    let swap = install();
+    initiate(&swap);
+    let refund = || {
+        send(
+            &swap.node,
+            &builder().refund(swap.contract_hash, &swap.owner),
+        )
+    };
+    assert_reverted(&refund(), ContractError::EndTimeNotReached);
+    swap.node.advance_time(TIMEOUT);
+    assert_success(&refund());
+}
+
+#[test]
+fn refund_after_unlock_reverts() {
+    let swap = install();
+    initiate(&swap);
+    let deploy = builder()
+        .unlock(swap.contract_hash, &Unlock::new(SECRET), &swap.receiver)
+        .unwrap();
+    assert_success(&send(&swap.node, &deploy));
+    swap.node.advance_time(TIMEOUT);
+
+    let refund = builder().refund(swap.contract_hash, &swap.owner);
+    assert_reverted(&send(&swap.node, &refund), ContractError::NotInitialized);
+    let client = NodeClient::new(&swap.node);
+    assert_eq!(
+        client.swap(swap.contract_hash, 1).unwrap().state,
+        SwapState::Redeemed
+    );
+}
+
+#[test]
+fn locks_deposited_cspr() {
+    let swap = install_as("Direct");
+    let client = NodeClient::new(&swap.node);
+    let owner = Key::Account(PublicKey::from(&swap.owner).to_account_hash());
+    let motes = |cspr: u64| U512::from(cspr * 1_000_000_000);
+    let direct = |cspr: u64| Asset::Direct {
+        amount: motes(cspr),
+    };
+
+    let deposit = builder().deposit(session_wasm(), swap.contract_hash, motes(10), &swap.owner);
+    assert_success(&send(&swap.node, &deposit));
+    assert_eq!(
+        client.deposit(swap.contract_hash, owner).unwrap(),
+        motes(10)
+    );
+
+    assert_reverted(
+        &initiate_with(&swap, direct(11)),
+        ContractError::NotEnoughBalance,
+    );
+    assert_success(&initiate_with(&swap, direct(4)));
+    assert_eq!(client.deposit(swap.contract_hash, owner).unwrap(), motes(6));
+
+    let withdraw = |cspr: u64| {
+        let deploy = builder().withdraw(swap.contract_hash, motes(cspr), &swap.owner);
+        send(&swap.node, &deploy)
+    };
+    assert_reverted(&withdraw(7), ContractError::NotEnoughBalance);
+    assert_success(&withdraw(6));
+    assert_eq!(
+        client.deposit(swap.contract_hash, owner).unwrap(),
+        U512::zero()
+    );
+
+    let deploy = builder()
+        .unlock(swap.contract_hash, &Unlock::new(SECRET), &swap.receiver)
+        .unwrap();
+    assert_success(&send(&swap.node, &deploy));
+}
+
+#[test]
+fn serves_blocks_and_state() {
+    let swap = install();
+    initiate(&swap);
+    let client = NodeClient::new(&swap.node);
+    let latest = client.block(None).unwrap();
+    assert_eq!(latest["header"]["height"], json!(swap.node.height()));
+    assert_eq!(latest["body"]["deploy_hashes"].as_array().unwrap().len(), 1);
+    let genesis = client.block(Some(0)).unwrap();
+    assert!(genesis["body"]["deploy_hashes"]
+        .as_array()
+        .unwrap()
+        .is_empty());
+
+    // the state before the swap was initiated is still readable
+    let installed = client.block(Some(1)).unwrap();
+    let result = swap
+        .node
+        .call(
+            "query_global_state",
+            json!({
+                "state_identifier": { "BlockHash": installed["hash"] },
+                "key": Key::Hash(swap.contract_hash.value()).to_formatted_string(),
+                "path": ["hash"],
+            }),
+        )
+        .unwrap();
+    let hash: String = swap_client::query::decode(&result["stored_value"]).unwrap();
+    assert_eq!(hash, "");
+
+    // the deploy must be for this network
+    let deploy =
+        DeployBuilder::new("casper", U512::from(1u64)).refund(swap.contract_hash, &swap.owner);
+    assert!(client.put_deploy(&deploy).is_err());
+}
@@ -0,0 +1,41 @@
+use casper_types::{Key, PublicKey};
+use swap_client::{
+    derivation::{recover, DerivationContext, OnChainSwap, Seed},
+    rpc::NodeClient,
+    SwapState, Unlock,
+};
+use swap_tests::harness::{
+    assert_success, builder, custom, initiate_with_secret, install, send, CHAIN_NAME,
+};
+
+#[test]
+fn recovers_secrets_of_closed_swaps() {
+    let swap = install();
+    let seed = Seed::generate();
+    let receiver = Key::Account(PublicKey::from(&swap.receiver).to_account_hash());
+    let context = DerivationContext {
+        counterparty: receiver.to_formatted_string(),
+        chain: CHAIN_NAME.to_string(),
+    };
+    let secret = seed.derive_secret(3, &context);
+    assert_success(&initiate_with_secret(&swap, custom(&swap), &secret));
+    let unlock = builder()
+        .unlock(swap.contract_hash, &Unlock::new(&secret), &swap.receiver)
+        .unwrap();
+    assert_success(&send(&swap.node, &unlock));
+
+    let client = NodeClient::new(&swap.node);
+    let history = client.history(swap.contract_hash).unwrap();
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].state, SwapState::Redeemed);
+    let contract_hash = Key::Hash(swap.contract_hash.value()).to_formatted_string();
+    let swaps: Vec<OnChainSwap> = history
+        .iter()
+        .map(|info| OnChainSwap::from_info(&contract_hash, info, CHAIN_NAME))
+        .collect();
+    let recovered = recover(&seed, &swaps, 10);
+    assert_eq!(recovered.len(), 1);
+    assert_eq!(recovered[0].swap.swap_id, 1);
+    assert_eq!(recovered[0].index, 3);
+    assert_eq!(recovered[0].secret, secret);
+}
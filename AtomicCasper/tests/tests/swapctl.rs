@@ -0,0 +1,243 @@
+use std::{fs, iter, path::Path};
+
+use casper_types::{account::AccountHash, ContractHash, Key, PublicKey, SecretKey, U256};
+use serde_json::{json, Value};
+use swap_client::{
+    derivation::{DerivationContext, Seed},
+    hashlock,
+    terms::LegTerms,
+    Initiate, SwapTerms, TokenContract, Unlock, HASH_TYPES,
+};
+use swap_tests::{
+    harness::{
+        assert_success, builder, custom, initiate_with_secret, install, install_as, send, Swap,
+        CHAIN_NAME, SECRET, TIMEOUT,
+    },
+    MockNode,
+};
+
+const NODE_ADDRESS: &str = "http://mock-node:7777/rpc";
+
+/// Runs `swapctl` with `args` against `node`, and returns what it printed.
+fn swapctl(node: &MockNode, args: &[&str]) -> anyhow::Result<String> {
+    let mut out = Vec::new();
+    swapctl::run(
+        iter::once("swapctl").chain(args.iter().copied()),
+        |node_address| {
+            assert_eq!(node_address, NODE_ADDRESS);
+            node
+        },
+        &mut out,
+    )?;
+    Ok(String::from_utf8(out).unwrap())
+}
+
+fn contract(swap: &Swap) -> String {
+    swap.contract_hash.to_formatted_string()
+}
+
+fn account(secret_key: &SecretKey) -> String {
+    AccountHash::from(&PublicKey::from(secret_key)).to_formatted_string()
+}
+
+fn write_key(dir: &Path, name: &str, secret_key: &SecretKey) -> String {
+    let path = dir.join(name);
+    secret_key.to_file(&path).unwrap();
+    path.to_str().unwrap().to_string()
+}
+
+#[test]
+fn prints_hashlocks() {
+    let node = MockNode::new(CHAIN_NAME, &[]);
+    let output = swapctl(
+        &node,
+        &["hashlock", "--secret", SECRET, "--hash-type", "sha3-256"],
+    );
+    assert_eq!(
+        output.unwrap(),
+        format!("{}\n", hashlock("sha3-256", SECRET).unwrap())
+    );
+
+    let output = swapctl(&node, &["hashlock", "--secret", SECRET]).unwrap();
+    let expected: String = HASH_TYPES
+        .iter()
+        .map(|hash_type| format!("{}: {}\n", hash_type, hashlock(hash_type, SECRET).unwrap()))
+        .collect();
+    assert_eq!(output, expected);
+}
+
+#[test]
+fn shows_fees_and_allowlist() {
+    let swap = install_as("NFT");
+    let contract = contract(&swap);
+    let args = ["--node-address", NODE_ADDRESS, "--contract-hash", &contract];
+    let fees = |swap: &Swap| -> Value {
+        let output = swapctl(&swap.node, &[&["fees"][..], &args].concat()).unwrap();
+        serde_json::from_str(&output).unwrap()
+    };
+    let allowlist = |swap: &Swap| -> Value {
+        let output = swapctl(&swap.node, &[&["allowlist"][..], &args].concat()).unwrap();
+        serde_json::from_str(&output).unwrap()
+    };
+    assert_eq!(fees(&swap), json!({ "treasury": null, "rates": {} }));
+    assert_eq!(allowlist(&swap), json!({ "enforced": false, "tokens": {} }));
+
+    let treasury = Key::Account(AccountHash::new([7u8; 32]));
+    let deploy = builder().set_treasury(swap.contract_hash, treasury, &swap.owner);
+    assert_success(&send(&swap.node, &deploy));
+    let deploy = builder().set_fee(swap.contract_hash, "NFT", 50, &swap.owner);
+    assert_success(&send(&swap.node, &deploy));
+    assert_eq!(
+        fees(&swap),
+        json!({ "treasury": treasury.to_formatted_string(), "rates": { "NFT": 50 } })
+    );
+
+    let token_contract = TokenContract::Contract(ContractHash::new([0u8; 32]));
+    let deploy = builder().allow_token(swap.contract_hash, token_contract, "CEP-47", &swap.owner);
+    assert_success(&send(&swap.node, &deploy));
+    let deploy = builder().enforce_allowlist(swap.contract_hash, true, &swap.owner);
+    assert_success(&send(&swap.node, &deploy));
+    let mut tokens = serde_json::Map::new();
+    tokens.insert(
+        ContractHash::new([0u8; 32]).to_formatted_string(),
+        "CEP-47".into(),
+    );
+    assert_eq!(
+        allowlist(&swap),
+        json!({ "enforced": true, "tokens": tokens })
+    );
+}
+
+#[test]
+fn recovers_secrets_from_a_seed() {
+    let swap = install();
+    let dir = tempfile::tempdir().unwrap();
+    let seed = Seed::generate();
+    let seed_path = dir.path().join("seed.hex");
+    fs::write(&seed_path, seed.to_hex()).unwrap();
+    let context = DerivationContext {
+        counterparty: account(&swap.receiver),
+        chain: CHAIN_NAME.to_string(),
+    };
+    let closed = seed.derive_secret(2, &context);
+    assert_success(&initiate_with_secret(&swap, custom(&swap), &closed));
+    let unlock = builder()
+        .unlock(swap.contract_hash, &Unlock::new(&closed), &swap.receiver)
+        .unwrap();
+    assert_success(&send(&swap.node, &unlock));
+    let open = seed.derive_secret(5, &context);
+    assert_success(&initiate_with_secret(&swap, custom(&swap), &open));
+
+    let contract = contract(&swap);
+    let output = swapctl(
+        &swap.node,
+        &[
+            "recover",
+            "--seed",
+            seed_path.to_str().unwrap(),
+            "--node-address",
+            NODE_ADDRESS,
+            "--contract-hash",
+            &contract,
+            "--gap-limit",
+            "10",
+        ],
+    )
+    .unwrap();
+    // closed swaps too, from the history of the contract
+    assert_eq!(
+        output,
+        format!(
+            "{contract} swap 1 index 2 secret {}\n{contract} swap 2 index 5 secret {}\n",
+            closed,
+            open,
+            contract = contract
+        )
+    );
+}
+
+#[test]
+fn signs_and_verifies_terms() {
+    let swap = install();
+    let dir = tempfile::tempdir().unwrap();
+    let owner_key = write_key(dir.path(), "owner.pem", &swap.owner);
+    let receiver_key = write_key(dir.path(), "receiver.pem", &swap.receiver);
+    let asset = custom(&swap);
+    let token_contract = asset.token_contract().unwrap();
+    let end_time = swap.node.block_time() + 2 * TIMEOUT;
+    let terms = SwapTerms {
+        version: 1,
+        hash_type: "sha3-256".to_string(),
+        hashlock: hashlock("sha3-256", SECRET).unwrap(),
+        initiator: LegTerms {
+            chain: CHAIN_NAME.to_string(),
+            asset: ContractHash::new(token_contract.value()).to_formatted_string(),
+            amount: U256::zero(),
+            token_ids: Vec::new(),
+            sender: account(&swap.owner),
+            receiver: account(&swap.receiver),
+            end_time,
+        },
+        participant: LegTerms {
+            chain: "ethereum".to_string(),
+            asset: "0xdac17f958d2ee523a2206206994597c13d831ec7".to_string(),
+            amount: U256::from(100_000_000u64),
+            token_ids: Vec::new(),
+            sender: "0x02".to_string(),
+            receiver: "0x01".to_string(),
+            end_time: end_time - TIMEOUT,
+        },
+        initiator_key: PublicKey::from(&swap.owner),
+        participant_key: PublicKey::from(&swap.receiver),
+    };
+    let terms_path = dir.path().join("terms.json");
+    fs::write(&terms_path, json!({ "terms": terms }).to_string()).unwrap();
+    let terms_file = terms_path.to_str().unwrap();
+    let sign = |secret_key: &str| {
+        swapctl(
+            &swap.node,
+            &[
+                "sign-terms",
+                "--terms",
+                terms_file,
+                "--secret-key",
+                secret_key,
+            ],
+        )
+    };
+    let verify = |extra: &[&str]| {
+        swapctl(
+            &swap.node,
+            &[&["verify-terms", "--terms", terms_file][..], extra].concat(),
+        )
+    };
+
+    assert_eq!(sign(&owner_key).unwrap(), "");
+    assert!(verify(&[]).is_err());
+    let hash = terms.hash().unwrap();
+    assert_eq!(
+        sign(&receiver_key).unwrap(),
+        format!("signed by both parties, terms hash {}\n", hash)
+    );
+    assert_eq!(verify(&[]).unwrap(), format!("{}\n", hash));
+
+    let receiver = Key::Account(PublicKey::from(&swap.receiver).to_account_hash());
+    let initiate = Initiate::new(asset, receiver, TIMEOUT).with_terms(&terms);
+    let deploy = builder()
+        .initiate(swap.contract_hash, &initiate, &swap.owner)
+        .unwrap();
+    assert_success(&send(&swap.node, &deploy));
+    let contract = contract(&swap);
+    let on_chain = |leg: &str| {
+        verify(&[
+            "--contract-hash",
+            &contract,
+            "--leg",
+            leg,
+            "--node-address",
+            NODE_ADDRESS,
+        ])
+    };
+    assert_eq!(on_chain("initiator").unwrap(), format!("{}\n", hash));
+    assert!(on_chain("participant").is_err());
+}
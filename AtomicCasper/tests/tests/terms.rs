@@ -0,0 +1,155 @@
+use casper_types::{account::AccountHash, ContractHash, PublicKey, SecretKey, U256};
+use swap_client::{
+    args::Asset, hashlock, query::SwapField, rpc::NodeClient, terms::LegTerms, Error, Initiate,
+    SignedTerms, SwapTerms,
+};
+use swap_tests::harness::{
+    assert_success, builder, custom, install, send, Swap, CHAIN_NAME, SECRET, TIMEOUT,
+};
+use swap_watcher::{casper::CasperAdapter, ChainAdapter, Leg};
+
+fn account(secret_key: &SecretKey) -> String {
+    AccountHash::from(&PublicKey::from(secret_key)).to_formatted_string()
+}
+
+/// The owner locks `asset`, a `Custom` one of the harness, for the receiver, against tokens on
+/// another chain. Its lock may end up to a timeout later than `initiate` makes it end.
+fn terms(swap: &Swap, asset: &Asset) -> SwapTerms {
+    let token_contract = asset.token_contract().unwrap();
+    let end_time = swap.node.block_time() + 2 * TIMEOUT;
+    SwapTerms {
+        version: 1,
+        hash_type: "sha3-256".to_string(),
+        hashlock: hashlock("sha3-256", SECRET).unwrap(),
+        initiator: LegTerms {
+            chain: CHAIN_NAME.to_string(),
+            asset: ContractHash::new(token_contract.value()).to_formatted_string(),
+            amount: U256::zero(),
+            token_ids: Vec::new(),
+            sender: account(&swap.owner),
+            receiver: account(&swap.receiver),
+            end_time,
+        },
+        participant: LegTerms {
+            chain: "ethereum".to_string(),
+            asset: "0xdac17f958d2ee523a2206206994597c13d831ec7".to_string(),
+            amount: U256::from(100_000_000u64),
+            token_ids: Vec::new(),
+            sender: "0x02".to_string(),
+            receiver: "0x01".to_string(),
+            end_time: end_time - TIMEOUT,
+        },
+        initiator_key: PublicKey::from(&swap.owner),
+        participant_key: PublicKey::from(&swap.receiver),
+    }
+}
+
+fn initiate(swap: &Swap, asset: Asset, terms: &SwapTerms) {
+    let receiver = PublicKey::from(&swap.receiver).to_account_hash().into();
+    let initiate = Initiate::new(asset, receiver, TIMEOUT).with_terms(terms);
+    let deploy = builder()
+        .initiate(swap.contract_hash, &initiate, &swap.owner)
+        .unwrap();
+    assert_success(&send(&swap.node, &deploy));
+}
+
+fn mismatch(result: Result<(), Error>) -> String {
+    match result {
+        Err(Error::Terms(message)) => message,
+        other => panic!("expected a mismatch, got {:?}", other),
+    }
+}
+
+fn signed(swap: &Swap, terms: &SwapTerms) -> SignedTerms {
+    let mut signed = SignedTerms::new(terms.clone());
+    signed.sign(&swap.owner).unwrap();
+    signed.sign(&swap.receiver).unwrap();
+    signed
+}
+
+#[test]
+fn accepts_the_leg_the_terms_agreed_on() {
+    let swap = install();
+    let asset = custom(&swap);
+    let terms = terms(&swap, &asset);
+    initiate(&swap, asset, &terms);
+    let client = NodeClient::new(&swap.node);
+    client
+        .check_terms(swap.contract_hash, &terms, &terms.initiator)
+        .unwrap();
+
+    // up to the end time included
+    let mut leg = terms.initiator.clone();
+    leg.end_time = client
+        .query(swap.contract_hash, SwapField::EndTime)
+        .unwrap();
+    client
+        .check_terms(swap.contract_hash, &terms, &leg)
+        .unwrap();
+}
+
+#[test]
+fn tells_which_term_differs() {
+    let swap = install();
+    let asset = custom(&swap);
+    let terms = terms(&swap, &asset);
+    initiate(&swap, asset, &terms);
+    let client = NodeClient::new(&swap.node);
+    let end_time: u64 = client
+        .query(swap.contract_hash, SwapField::EndTime)
+        .unwrap();
+    let check = |edit: &dyn Fn(&mut LegTerms)| {
+        let mut leg = terms.initiator.clone();
+        edit(&mut leg);
+        mismatch(client.check_terms(swap.contract_hash, &terms, &leg))
+    };
+
+    assert!(check(&|leg| leg.asset = "CSPR".to_string()).contains("asset"));
+    assert!(check(&|leg| leg.amount = U256::one()).contains("amount"));
+    assert!(check(&|leg| leg.token_ids = vec![U256::one()]).contains("token ids"));
+    assert!(check(&|leg| leg.sender = leg.receiver.clone()).contains("sender"));
+    assert!(check(&|leg| leg.receiver = leg.sender.clone()).contains("receiver"));
+    assert!(check(&|leg| leg.end_time = end_time - 1).contains("end time"));
+
+    // other terms, or the other leg of these
+    let mut other_hashlock = terms.clone();
+    other_hashlock.hashlock = hashlock("sha3-256", "another secret").unwrap();
+    assert!(
+        mismatch(client.check_terms(swap.contract_hash, &other_hashlock, &terms.initiator))
+            .contains("terms hash")
+    );
+    assert!(
+        mismatch(client.check_terms(swap.contract_hash, &terms, &terms.participant))
+            .contains("asset")
+    );
+}
+
+#[test]
+fn watcher_checks_the_leg_that_pays_it() {
+    let swap = install();
+    let asset = custom(&swap);
+    let terms = terms(&swap, &asset);
+    initiate(&swap, asset, &terms);
+    let signed = signed(&swap, &terms);
+    let leg = Leg {
+        chain: CHAIN_NAME.to_string(),
+        contract: swap.contract_hash.to_formatted_string(),
+        hash_type: terms.hash_type.clone(),
+        hashlock: terms.hashlock.clone(),
+        end_time: terms.initiator.end_time,
+        salt: None,
+    };
+    // the harness keys of the owner and the receiver
+    let adapter = |seed: u8| {
+        CasperAdapter::new(
+            CHAIN_NAME,
+            NodeClient::new(&swap.node),
+            builder(),
+            SecretKey::ed25519_from_bytes([seed; 32]).unwrap(),
+        )
+    };
+
+    adapter(2).check_terms(&leg, &signed.terms).unwrap();
+    // no leg of the terms pays the owner
+    assert!(adapter(1).check_terms(&leg, &signed.terms).is_err());
+}
@@ -0,0 +1,251 @@
+use casper_types::{
+    runtime_args, ContractHash, ContractPackageHash, Key, PublicKey, RuntimeArgs, U256,
+};
+use serde_json::Value;
+use swap_client::{
+    args::Asset, rpc::NodeClient, ContractError, Error, SwapState, TokenContract, Unlock,
+};
+use swap_tests::{
+    harness::{
+        assert_reverted, assert_success, builder, custom, initiate_with, initiate_with_secret,
+        install, install_as, send, Swap, SECRET,
+    },
+    test_receiver_wasm, test_token_wasm,
+};
+
+/// An NFT swap contract owning token 1 of a freshly installed test token, with the contract and
+/// package of the token.
+fn nft_token() -> (Swap, ContractHash, ContractPackageHash) {
+    let swap = install_as("NFT");
+    let owner = PublicKey::from(&swap.owner);
+    assert_success(&send(
+        &swap.node,
+        &builder().install(test_token_wasm(), "NFT", &swap.owner),
+    ));
+    let token = match swap.node.named_key(&owner, "test_token") {
+        Some(Key::Hash(hash)) => ContractHash::new(hash),
+        other => panic!("no token installed: {:?}", other),
+    };
+    let token_package = match swap.node.named_key(&owner, "test_token_package") {
+        Some(Key::Hash(hash)) => ContractPackageHash::new(hash),
+        other => panic!("no token installed: {:?}", other),
+    };
+    let package = swap
+        .node
+        .named_key(&owner, "atomic_swap_package_hash")
+        .unwrap();
+    let mint = builder().call(
+        token,
+        "mint",
+        runtime_args! {
+            "recipient" => package,
+            "token_ids" => vec![U256::one()],
+        },
+        &swap.owner,
+    );
+    assert_success(&send(&swap.node, &mint));
+    (swap, token, token_package)
+}
+
+/// An ERC-20 swap contract holding `amount` of a freshly installed test token.
+fn erc20_token(amount: U256) -> (Swap, ContractHash) {
+    let swap = install_as("ERC-20");
+    let owner = PublicKey::from(&swap.owner);
+    assert_success(&send(
+        &swap.node,
+        &builder().install(test_token_wasm(), "ERC-20", &swap.owner),
+    ));
+    let token = match swap.node.named_key(&owner, "test_token") {
+        Some(Key::Hash(hash)) => ContractHash::new(hash),
+        other => panic!("no token installed: {:?}", other),
+    };
+    let package = swap
+        .node
+        .named_key(&owner, "atomic_swap_package_hash")
+        .unwrap();
+    let mint = builder().call(
+        token,
+        "mint",
+        runtime_args! { "recipient" => package, "amount" => amount },
+        &swap.owner,
+    );
+    assert_success(&send(&swap.node, &mint));
+    (swap, token)
+}
+
+fn nft(token_contract: TokenContract) -> Asset {
+    Asset::Nft {
+        token_contract,
+        token_ids: vec![U256::one()],
+    }
+}
+
+/// An NFT swap locking token 1 of a freshly installed test token.
+fn nft_swap() -> (Swap, ContractHash) {
+    let (swap, token, _package) = nft_token();
+    assert_success(&initiate_with(&swap, nft(TokenContract::Contract(token))));
+    (swap, token)
+}
+
+fn configure(swap: &Swap, token: ContractHash, mode: &str) {
+    let configure = builder().call(
+        token,
+        "configure",
+        runtime_args! {
+            "mode" => mode.to_string(),
+            "swap_contract" => Some(Key::from(swap.contract_hash)),
+            "secret" => SECRET.to_string(),
+        },
+        &swap.owner,
+    );
+    assert_success(&send(&swap.node, &configure));
+}
+
+fn unlock(swap: &Swap) -> Value {
+    let deploy = builder()
+        .unlock(swap.contract_hash, &Unlock::new(SECRET), &swap.receiver)
+        .unwrap();
+    send(&swap.node, &deploy)
+}
+
+#[test]
+fn pays_out_through_honest_token() {
+    let (swap, _token) = nft_swap();
+    assert_success(&unlock(&swap));
+    let client = NodeClient::new(&swap.node);
+    let redeemed = client.swap(swap.contract_hash, 1).unwrap();
+    assert_eq!(redeemed.state, SwapState::Redeemed);
+}
+
+#[test]
+fn rejects_reentrant_token() {
+    let (swap, token) = nft_swap();
+    configure(&swap, token, "reenter");
+    assert_reverted(&unlock(&swap), ContractError::Reentrancy);
+    // nothing was paid out twice, the swap is still open
+    let client = NodeClient::new(&swap.node);
+    assert_eq!(
+        client.swap(swap.contract_hash, 1).unwrap().state,
+        SwapState::Open
+    );
+}
+
+#[test]
+fn rejects_token_that_does_not_move() {
+    let (swap, token) = nft_swap();
+    configure(&swap, token, "noop");
+    assert_reverted(&unlock(&swap), ContractError::TokenNotTransferred);
+    configure(&swap, token, "reject");
+    assert_reverted(&unlock(&swap), ContractError::TokenTransferRejected);
+    configure(&swap, token, "honest");
+    assert_success(&unlock(&swap));
+}
+
+#[test]
+fn calls_the_latest_version_of_a_package() {
+    let (swap, _token, package) = nft_token();
+    let token_contract = TokenContract::Package(package);
+    assert_success(&initiate_with(&swap, nft(token_contract)));
+    assert_success(&unlock(&swap));
+    let redeemed = NodeClient::new(&swap.node)
+        .swap(swap.contract_hash, 1)
+        .unwrap();
+    assert_eq!(redeemed.token_contract, Some(token_contract));
+    assert_eq!(redeemed.state, SwapState::Redeemed);
+}
+
+#[test]
+fn checks_token_contracts_before_initiate() {
+    let (swap, token, package) = nft_token();
+    let client = NodeClient::new(&swap.node);
+    client
+        .check_token_contract(TokenContract::Contract(token), "NFT")
+        .unwrap();
+    client
+        .check_token_contract(TokenContract::Package(package), "ERC-20")
+        .unwrap();
+    // the swap contract has none of the entry points of a token
+    let swap_contract = TokenContract::Contract(swap.contract_hash);
+    assert!(matches!(
+        client.check_token_contract(swap_contract, "NFT"),
+        Err(Error::TokenContract(_))
+    ));
+    // and a contract is no package
+    let not_a_package = TokenContract::Package(ContractPackageHash::new(token.value()));
+    assert!(matches!(
+        client.check_token_contract(not_a_package, "NFT"),
+        Err(Error::TokenContract(_))
+    ));
+}
+
+#[test]
+fn pays_out_custom_tokens_it_holds() {
+    let swap = install();
+    let asset = custom(&swap);
+    let token = ContractHash::new(asset.token_contract().unwrap().value());
+    assert_success(&initiate_with(&swap, asset.clone()));
+    configure(&swap, token, "noop");
+    assert_reverted(&unlock(&swap), ContractError::TokenNotTransferred);
+    configure(&swap, token, "honest");
+    assert_success(&unlock(&swap));
+    // it paid out all it held, nothing is left to lock
+    assert_reverted(
+        &initiate_with_secret(&swap, asset, "5d20c4a7"),
+        ContractError::NotEnoughBalance,
+    );
+}
+
+#[test]
+fn rejects_custom_tokens_without_the_entry_points() {
+    let swap = install();
+    let owner = PublicKey::from(&swap.owner);
+    let install = builder().install(test_receiver_wasm(), "Custom", &swap.owner);
+    assert_success(&send(&swap.node, &install));
+    // the vault has no `balance_of`
+    let vault = match swap.node.named_key(&owner, "test_receiver_package") {
+        Some(Key::Hash(hash)) => ContractPackageHash::new(hash),
+        other => panic!("no vault installed: {:?}", other),
+    };
+    let asset = Asset::Custom {
+        token_contract: TokenContract::Package(vault),
+    };
+    let result = initiate_with(&swap, asset);
+    assert!(result.get("Failure").is_some(), "{}", result);
+    let client = NodeClient::new(&swap.node);
+    assert!(client.swap(swap.contract_hash, 1).is_err());
+    assert!(client
+        .check_token_contract(TokenContract::Package(vault), "Custom")
+        .is_err());
+}
+
+#[test]
+fn pays_out_erc20_amounts_above_u64() {
+    // 1M tokens of 18 decimals
+    let amount = U256::from(10).pow(U256::from(24));
+    let (swap, token) = erc20_token(amount);
+    let asset = Asset::Erc20 {
+        token_contract: TokenContract::Contract(token),
+        amount,
+    };
+    assert_success(&initiate_with(&swap, asset));
+    assert_success(&unlock(&swap));
+    let client = NodeClient::new(&swap.node);
+    assert_eq!(
+        client.swap(swap.contract_hash, 1).unwrap().state,
+        SwapState::Redeemed
+    );
+}
+
+#[test]
+fn rejects_erc20_amounts_above_the_balance() {
+    let amount = U256::from(10).pow(U256::from(24));
+    let (swap, token) = erc20_token(amount);
+    let asset = Asset::Erc20 {
+        token_contract: TokenContract::Contract(token),
+        amount: amount + U256::one(),
+    };
+    assert_reverted(
+        &initiate_with(&swap, asset),
+        ContractError::NotEnoughBalance,
+    );
+}
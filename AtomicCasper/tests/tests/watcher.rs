@@ -0,0 +1,97 @@
+use casper_types::{Key, PublicKey, SecretKey};
+use swap_client::{hashlock, rpc::NodeClient, Initiate, Redemption};
+use swap_tests::{
+    batch_wasm,
+    harness::{
+        assert_success, builder, custom, initiate, install, send, Swap, CHAIN_NAME, SECRET, TIMEOUT,
+    },
+    MockNode,
+};
+use swap_watcher::{
+    casper::CasperAdapter, ChainAdapter, Leg, LegState, RevealedSecret, TransactionStatus,
+};
+
+fn adapter(node: &MockNode, secret_key: SecretKey) -> CasperAdapter<&MockNode> {
+    CasperAdapter::new(CHAIN_NAME, NodeClient::new(node), builder(), secret_key)
+}
+
+fn leg(swap: &Swap) -> Leg {
+    Leg {
+        chain: CHAIN_NAME.to_string(),
+        contract: swap.contract_hash.to_formatted_string(),
+        hash_type: "sha3-256".to_string(),
+        hashlock: hashlock("sha3-256", SECRET).unwrap(),
+        end_time: swap.node.block_time() + TIMEOUT,
+        salt: None,
+    }
+}
+
+#[test]
+fn tells_failed_claims_from_claims_that_went_through() {
+    let swap = install();
+    initiate(&swap);
+    let leg = leg(&swap);
+
+    // the owner can't unlock its own swap, the deploy is accepted and reverts
+    let owner = adapter(
+        &swap.node,
+        SecretKey::ed25519_from_bytes([1u8; 32]).unwrap(),
+    );
+    let transaction = owner.claim(&leg, SECRET).unwrap();
+    assert!(matches!(
+        owner.transaction_status(&transaction).unwrap(),
+        TransactionStatus::Failed(_)
+    ));
+    assert!(matches!(
+        owner.leg_state(&leg).unwrap(),
+        LegState::Open { .. }
+    ));
+
+    let receiver = adapter(
+        &swap.node,
+        SecretKey::ed25519_from_bytes([2u8; 32]).unwrap(),
+    );
+    let transaction = receiver.claim(&leg, SECRET).unwrap();
+    assert_eq!(
+        receiver.transaction_status(&transaction).unwrap(),
+        TransactionStatus::Succeeded
+    );
+    assert_eq!(receiver.leg_state(&leg).unwrap(), LegState::Closed);
+}
+
+#[test]
+fn sees_swaps_of_the_batch_session() {
+    let swap = install();
+    let owner = adapter(
+        &swap.node,
+        SecretKey::ed25519_from_bytes([1u8; 32]).unwrap(),
+    );
+    let receiver = Key::Account(PublicKey::from(&swap.receiver).to_account_hash());
+    let initiate = Initiate::new(custom(&swap), receiver, TIMEOUT)
+        .with_secret("sha3-256", SECRET)
+        .unwrap();
+    let deploy = builder()
+        .initiate_batch(batch_wasm(), &[(swap.contract_hash, initiate)], &swap.owner)
+        .unwrap();
+    assert_success(&send(&swap.node, &deploy));
+    assert_eq!(
+        owner.initiated(swap.node.height()).unwrap(),
+        vec![leg(&swap)]
+    );
+
+    let redemption = Redemption {
+        contract_hash: swap.contract_hash,
+        swap_id: 1,
+        secret: SECRET.to_string(),
+        signed: None,
+    };
+    let deploy = builder().unlock_batch(batch_wasm(), vec![redemption], &swap.receiver);
+    assert_success(&send(&swap.node, &deploy));
+    assert_eq!(
+        owner.revealed_secrets(swap.node.height()).unwrap(),
+        vec![RevealedSecret {
+            contract: swap.contract_hash.to_formatted_string(),
+            secret: SECRET.to_string(),
+        }]
+    );
+}
@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+use swap_client::{swap_hashlock, Salt, SwapTerms};
+
+use crate::Error;
+
+/// One side of a swap: a hashlocked asset on one chain.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Leg {
+    /// Name of the adapter for the chain, e.g. `casper-test`.
+    pub chain: String,
+    /// How the chain identifies the swap, e.g. the hash of the swap contract on Casper.
+    pub contract: String,
+    pub hash_type: String,
+    pub hashlock: String,
+    /// Deadline of the lock, in milliseconds since the unix epoch.
+    pub end_time: u64,
+    /// Set for a salted swap on Casper: the hashlock is over the secret salted with the
+    /// contract package and swap id, and the contract is unlocked with the bare secret.
+    #[serde(default)]
+    pub salt: Option<Salt>,
+}
+
+impl Leg {
+    /// Whether `secret` unlocks the leg.
+    pub fn opens(&self, secret: &str) -> bool {
+        swap_hashlock(&self.hash_type, secret, self.salt.as_ref())
+            .map_or(false, |hashlock| hashlock == self.hashlock)
+    }
+}
+
+/// A preimage that went public when a swap was redeemed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RevealedSecret {
+    pub contract: String,
+    pub secret: String,
+}
+
+/// What is currently locked under a leg's contract.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LegState {
+    /// Still locked with the leg's hashlock, until `end_time`, which may have been extended.
+    Open { end_time: u64 },
+    /// Redeemed, refunded or cancelled: nothing of the leg is left to get back.
+    Closed,
+}
+
+/// What became of a transaction an adapter sent.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TransactionStatus {
+    /// Accepted, not executed yet.
+    Pending,
+    Succeeded,
+    /// Executed, but reverted: nothing changed on chain.
+    Failed(String),
+}
+
+/// What the services need from a chain.
+pub trait ChainAdapter {
+    /// The name legs refer to the chain by.
+    fn name(&self) -> &str;
+    /// Height of the latest block.
+    fn latest_height(&self) -> Result<u64, Error>;
+    /// Time of the latest block, in milliseconds since the unix epoch.
+    fn block_time(&self) -> Result<u64, Error>;
+    /// Secrets revealed by the redemptions executed in the block at `height`.
+    fn revealed_secrets(&self, height: u64) -> Result<Vec<RevealedSecret>, Error>;
+    /// Swaps our keys initiated in the block at `height`.
+    fn initiated(&self, height: u64) -> Result<Vec<Leg>, Error>;
+    fn leg_state(&self, leg: &Leg) -> Result<LegState, Error>;
+    /// Checks that what is locked under `leg` is the leg of the signed `terms` that pays us: its
+    /// asset, amount, parties, hashlock and deadline.
+    fn check_terms(&self, leg: &Leg, terms: &SwapTerms) -> Result<(), Error>;
+    /// Redeems `leg` with `secret`, returns the id of the transaction.
+    fn claim(&self, leg: &Leg, secret: &str) -> Result<String, Error>;
+    /// Gets the asset of `leg` back after its deadline, returns the id of the transaction.
+    fn refund(&self, leg: &Leg) -> Result<String, Error>;
+    /// Whether the transaction `claim` or `refund` returned went through. Being accepted by a
+    /// node says nothing about that.
+    fn transaction_status(&self, transaction: &str) -> Result<TransactionStatus, Error>;
+}
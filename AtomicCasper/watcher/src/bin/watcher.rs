@@ -0,0 +1,68 @@
+//! `watcher` follows the swaps listed in its config and claims our leg of each as soon as the
+//! counterparty's redemption reveals the secret. What it has scanned and claimed is kept in the
+//! state file, so it can be restarted at any time.
+use std::{path::PathBuf, thread, time::Duration};
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use serde::Deserialize;
+use swap_watcher::{
+    config::{self, ChainConfig},
+    Claimer, SwapPair,
+};
+
+#[derive(Parser)]
+#[clap(
+    name = "watcher",
+    about = "Claims swaps with the secrets counterparties reveal"
+)]
+struct Cli {
+    /// The config file, see the readme
+    #[clap(long, short)]
+    config: PathBuf,
+}
+
+#[derive(Deserialize)]
+struct Config {
+    /// Where the state is kept between runs
+    state: PathBuf,
+    #[serde(default = "default_interval")]
+    interval_secs: u64,
+    chains: Vec<ChainConfig>,
+    #[serde(default)]
+    pairs: Vec<SwapPair>,
+}
+
+fn default_interval() -> u64 {
+    10
+}
+
+fn main() -> Result<()> {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+    let cli = Cli::parse();
+    let config: Config =
+        config::load(&cli.config).with_context(|| format!("reading {}", cli.config.display()))?;
+
+    let mut claimer = Claimer::new(config::load_state(&config.state)?);
+    for chain in &config.chains {
+        let fresh = !claimer.state().scanned(&chain.name);
+        claimer = claimer.with_adapter(chain.adapter()?);
+        if let (true, Some(height)) = (fresh, chain.start_height) {
+            claimer.start_at(&chain.name, height);
+        }
+    }
+    for pair in config.pairs {
+        claimer.watch(pair)?;
+    }
+
+    loop {
+        for claim in claimer.poll() {
+            println!(
+                "claimed {} on {}: {}",
+                claim.pair.claim.contract, claim.pair.claim.chain, claim.transaction
+            );
+        }
+        config::save_state(&config.state, claimer.state())?;
+        thread::sleep(Duration::from_secs(config.interval_secs));
+    }
+}
@@ -0,0 +1,100 @@
+//! `watchtower` tracks every swap the keys of its config initiate and sends the `refund` deploy
+//! once the swap has expired, retrying until it goes through. Outcomes are kept in the state
+//! file next to what is still tracked.
+use std::{path::PathBuf, thread, time::Duration};
+
+use anyhow::{Context, Result};
+use clap::Parser;
+use serde::Deserialize;
+use swap_watcher::{
+    config::{self, ChainConfig},
+    Leg, Outcome, Refunder,
+};
+
+#[derive(Parser)]
+#[clap(name = "watchtower", about = "Refunds our swaps once they expire")]
+struct Cli {
+    /// The config file, see the readme
+    #[clap(long, short)]
+    config: PathBuf,
+}
+
+#[derive(Deserialize)]
+struct Config {
+    /// Where the state is kept between runs
+    state: PathBuf,
+    #[serde(default = "default_interval")]
+    interval_secs: u64,
+    /// Milliseconds to wait after a swap's end time before refunding
+    #[serde(default = "default_buffer")]
+    buffer_ms: u64,
+    /// Milliseconds between two attempts of a failed refund
+    #[serde(default = "default_retry_delay")]
+    retry_delay_ms: u64,
+    max_attempts: Option<u32>,
+    chains: Vec<ChainConfig>,
+    /// Swaps to track on top of the ones found on chain
+    #[serde(default)]
+    legs: Vec<Leg>,
+}
+
+fn default_interval() -> u64 {
+    30
+}
+
+fn default_buffer() -> u64 {
+    60_000
+}
+
+fn default_retry_delay() -> u64 {
+    120_000
+}
+
+fn main() -> Result<()> {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+    let cli = Cli::parse();
+    let config: Config =
+        config::load(&cli.config).with_context(|| format!("reading {}", cli.config.display()))?;
+
+    let state = config::load_state(&config.state)?;
+    let mut refunder = Refunder::new(state, config.buffer_ms, config.retry_delay_ms);
+    if let Some(max_attempts) = config.max_attempts {
+        refunder = refunder.with_max_attempts(max_attempts);
+    }
+    for chain in &config.chains {
+        let fresh = !refunder.state().scanned(&chain.name);
+        refunder = refunder.with_adapter(chain.adapter()?);
+        if let (true, Some(height)) = (fresh, chain.start_height) {
+            refunder.start_at(&chain.name, height);
+        }
+    }
+    for leg in config.legs {
+        refunder.track(leg)?;
+    }
+
+    loop {
+        for outcome in refunder.poll() {
+            match &outcome {
+                Outcome::Refunded { leg, transaction } => {
+                    println!(
+                        "refunded {} on {}: {}",
+                        leg.contract, leg.chain, transaction
+                    )
+                }
+                Outcome::Closed { leg } => {
+                    println!("{} on {} closed before expiring", leg.contract, leg.chain)
+                }
+                Outcome::Abandoned {
+                    leg,
+                    attempts,
+                    error,
+                } => eprintln!(
+                    "gave up refunding {} on {} after {} attempts: {}",
+                    leg.contract, leg.chain, attempts, error
+                ),
+            }
+        }
+        config::save_state(&config.state, refunder.state())?;
+        thread::sleep(Duration::from_secs(config.interval_secs));
+    }
+}
@@ -0,0 +1,353 @@
+//! [`ChainAdapter`] for a Casper network, on top of the node's JSON-RPC api.
+use std::{convert::TryFrom, str::FromStr};
+
+use casper_node::types::Timestamp;
+use casper_types::{
+    account::AccountHash,
+    bytesrepr::{self, FromBytes, ToBytes},
+    AsymmetricType, CLTyped, ContractHash, ContractPackageHash, Key, PublicKey, SecretKey,
+};
+use serde_json::Value;
+use swap_client::{
+    args::sign_redemption,
+    constants,
+    query::SwapField,
+    rpc::{NodeClient, Transport},
+    DeployBuilder, Initiation, Redemption, SwapTerms, Unlock,
+};
+
+use crate::{ChainAdapter, Error, Leg, LegState, RevealedSecret, TransactionStatus};
+
+/// A successful call of the swap contract, by a deploy of its own or by the batch session.
+struct Call {
+    account: String,
+    contract: ContractHash,
+    entry_point: String,
+    /// The name and serialized value of every argument.
+    args: Vec<(String, Vec<u8>)>,
+}
+
+impl Call {
+    fn arg<T: CLTyped + FromBytes>(&self, name: &str) -> Option<T> {
+        arg(&self.args, name)
+    }
+}
+
+fn arg<T: CLTyped + FromBytes>(args: &[(String, Vec<u8>)], name: &str) -> Option<T> {
+    args.iter()
+        .find(|(arg, _)| arg == name)
+        .and_then(|(_, bytes)| bytesrepr::deserialize(bytes.clone()).ok())
+}
+
+/// The args of a deploy's session as the node shows them, `[name, { "bytes": <hex>, .. }]`.
+fn session_args(session: &Value) -> Vec<(String, Vec<u8>)> {
+    session
+        .get("args")
+        .and_then(Value::as_array)
+        .map(|args| {
+            args.iter()
+                .filter_map(|arg| {
+                    let name = arg.get(0).and_then(Value::as_str)?;
+                    let bytes = arg.pointer("/1/bytes").and_then(Value::as_str)?;
+                    Some((name.to_string(), hex::decode(bytes).ok()?))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Legs on Casper name their swap contract by its formatted hash, `contract-<hex>`.
+pub struct CasperAdapter<T> {
+    name: String,
+    client: NodeClient<T>,
+    builder: DeployBuilder,
+    secret_key: SecretKey,
+    payout: Option<Key>,
+}
+
+impl<T: Transport> CasperAdapter<T> {
+    /// Claims are signed with `secret_key` and built by `builder`.
+    pub fn new(
+        name: &str,
+        client: NodeClient<T>,
+        builder: DeployBuilder,
+        secret_key: SecretKey,
+    ) -> Self {
+        CasperAdapter {
+            name: name.to_string(),
+            client,
+            builder,
+            secret_key,
+            payout: None,
+        }
+    }
+
+    /// For swaps initiated with our public key as the receiver public key: claims pay out to
+    /// `payout`, signed with the adapter's key.
+    pub fn with_payout(mut self, payout: Key) -> Self {
+        self.payout = Some(payout);
+        self
+    }
+
+    fn error(&self, message: String) -> Error {
+        Error::Chain {
+            chain: self.name.clone(),
+            message,
+        }
+    }
+
+    fn contract_hash(&self, leg: &Leg) -> Result<ContractHash, Error> {
+        ContractHash::from_formatted_str(&leg.contract)
+            .map_err(|error| self.error(format!("invalid contract {}: {:?}", leg.contract, error)))
+    }
+
+    fn block(&self, height: Option<u64>) -> Result<(Value, u64), Error> {
+        let block = self.client.block(height)?;
+        let timestamp = block
+            .pointer("/header/timestamp")
+            .and_then(Value::as_str)
+            .and_then(|timestamp| Timestamp::from_str(timestamp).ok())
+            .map(|timestamp| timestamp.millis())
+            .ok_or_else(|| self.error("block has no timestamp".to_string()))?;
+        Ok((block, timestamp))
+    }
+
+    /// The successful contract calls of the block at `height`, and the block's time.
+    fn calls(&self, height: u64) -> Result<(Vec<Call>, u64), Error> {
+        let (block, timestamp) = self.block(Some(height))?;
+        let deploy_hashes = block
+            .pointer("/body/deploy_hashes")
+            .and_then(Value::as_array)
+            .ok_or_else(|| self.error(format!("block {} has no deploys", height)))?;
+        let mut calls = Vec::new();
+        for deploy_hash in deploy_hashes.iter().filter_map(Value::as_str) {
+            calls.extend(self.deploy_calls(deploy_hash)?);
+        }
+        Ok((calls, timestamp))
+    }
+
+    /// The calls of the swap contract a successful deploy made: one for a call of the contract,
+    /// one per swap for the batch session. Any wasm can pass the args of the batch session, which
+    /// is harmless: the claimer checks secrets against the hashlock, and the refunder only
+    /// follows deploys of our account.
+    fn deploy_calls(&self, deploy_hash: &str) -> Result<Vec<Call>, Error> {
+        let result = self.client.deploy(deploy_hash)?;
+        let succeeded = result
+            .get("execution_results")
+            .and_then(Value::as_array)
+            .map_or(false, |results| {
+                results
+                    .iter()
+                    .any(|result| result.pointer("/result/Success").is_some())
+            });
+        if !succeeded {
+            return Ok(Vec::new());
+        }
+        let field = |pointer: &str| {
+            result
+                .pointer(pointer)
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string()
+        };
+        let account = field("/deploy/header/account");
+        if let Some(session) = result.pointer("/deploy/session/StoredContractByHash") {
+            let contract = session
+                .get("hash")
+                .and_then(Value::as_str)
+                .and_then(|hash| hex::decode(hash).ok())
+                .and_then(|bytes| ContractHash::try_from(bytes.as_slice()).ok())
+                .ok_or_else(|| {
+                    self.error(format!("deploy {} has no contract hash", deploy_hash))
+                })?;
+            return Ok(vec![Call {
+                account,
+                contract,
+                entry_point: field("/deploy/session/StoredContractByHash/entry_point"),
+                args: session_args(session),
+            }]);
+        }
+        let args = match result.pointer("/deploy/session/ModuleBytes") {
+            Some(session) => session_args(session),
+            None => return Ok(Vec::new()),
+        };
+        let batch: Option<String> = arg(&args, constants::ARG_BATCH);
+        let calls = match batch.as_deref() {
+            Some(constants::BATCH_UNLOCK) => {
+                arg::<Vec<Redemption>>(&args, constants::ARG_REDEMPTIONS)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter_map(|redemption| {
+                        let mut args = vec![
+                            (
+                                constants::ARG_SECRET.to_string(),
+                                redemption.secret.to_bytes().ok()?,
+                            ),
+                            (
+                                constants::ARG_SWAP_ID.to_string(),
+                                redemption.swap_id.to_bytes().ok()?,
+                            ),
+                        ];
+                        if let Some((payout, signature)) = redemption.signed {
+                            args.push((constants::ARG_PAYOUT.to_string(), payout.to_bytes().ok()?));
+                            args.push((
+                                constants::ARG_SIGNATURE.to_string(),
+                                signature.to_bytes().ok()?,
+                            ));
+                        }
+                        Some(Call {
+                            account: account.clone(),
+                            contract: redemption.contract_hash,
+                            entry_point: constants::ENTRY_POINT_UNLOCK.to_string(),
+                            args,
+                        })
+                    })
+                    .collect()
+            }
+            Some(constants::BATCH_INITIATE) => {
+                arg::<Vec<Initiation>>(&args, constants::ARG_INITIATIONS)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|initiation| Call {
+                        account: account.clone(),
+                        contract: initiation.contract_hash,
+                        entry_point: constants::ENTRY_POINT_INITIATE.to_string(),
+                        args: initiation
+                            .args
+                            .named_args()
+                            .map(|arg| {
+                                (arg.name().to_string(), arg.cl_value().inner_bytes().clone())
+                            })
+                            .collect(),
+                    })
+                    .collect()
+            }
+            _ => Vec::new(),
+        };
+        Ok(calls)
+    }
+}
+
+impl<T: Transport> ChainAdapter for CasperAdapter<T> {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn latest_height(&self) -> Result<u64, Error> {
+        let (block, _) = self.block(None)?;
+        block
+            .pointer("/header/height")
+            .and_then(Value::as_u64)
+            .ok_or_else(|| self.error("block has no height".to_string()))
+    }
+
+    fn block_time(&self) -> Result<u64, Error> {
+        Ok(self.block(None)?.1)
+    }
+
+    fn revealed_secrets(&self, height: u64) -> Result<Vec<RevealedSecret>, Error> {
+        let (calls, _) = self.calls(height)?;
+        Ok(calls
+            .into_iter()
+            .filter(|call| call.entry_point == constants::ENTRY_POINT_UNLOCK)
+            .filter_map(|call| {
+                Some(RevealedSecret {
+                    secret: call.arg(constants::ARG_SECRET)?,
+                    contract: call.contract.to_formatted_string(),
+                })
+            })
+            .collect())
+    }
+
+    /// `initiate` deploys sent from the adapter's account. The contract starts the timeout at
+    /// the block time, which is the block's timestamp. The args don't tell the swap id a salted
+    /// swap got, so its leg has no salt, refunds don't need it.
+    fn initiated(&self, height: u64) -> Result<Vec<Leg>, Error> {
+        let account = PublicKey::from(&self.secret_key).to_hex();
+        let (calls, timestamp) = self.calls(height)?;
+        Ok(calls
+            .into_iter()
+            .filter(|call| {
+                call.entry_point == constants::ENTRY_POINT_INITIATE
+                    && call.account.eq_ignore_ascii_case(&account)
+            })
+            .filter_map(|call| {
+                let timeout: u64 = call.arg(constants::ARG_TIMEOUT)?;
+                Some(Leg {
+                    chain: self.name.clone(),
+                    contract: call.contract.to_formatted_string(),
+                    hash_type: call.arg(constants::ARG_HASH_TYPE)?,
+                    hashlock: call.arg(constants::ARG_HASH)?,
+                    end_time: timestamp.checked_add(timeout)?,
+                    salt: None,
+                })
+            })
+            .collect())
+    }
+
+    /// `clear_all` wipes the hash when a swap ends, and a new swap of the same contract has
+    /// another hashlock, so the leg is open as long as the contract still holds its hashlock.
+    fn leg_state(&self, leg: &Leg) -> Result<LegState, Error> {
+        let contract_hash = self.contract_hash(leg)?;
+        let hashlock: String = self.client.query(contract_hash, SwapField::Hash)?;
+        if hashlock != leg.hashlock {
+            return Ok(LegState::Closed);
+        }
+        let end_time = self.client.query(contract_hash, SwapField::EndTime)?;
+        Ok(LegState::Open { end_time })
+    }
+
+    fn check_terms(&self, leg: &Leg, terms: &SwapTerms) -> Result<(), Error> {
+        let account = AccountHash::from(&PublicKey::from(&self.secret_key)).to_formatted_string();
+        let payout = self.payout.map(|payout| payout.to_formatted_string());
+        let ours = [&terms.initiator, &terms.participant]
+            .iter()
+            .find(|terms_leg| {
+                terms_leg.receiver == account || Some(&terms_leg.receiver) == payout.as_ref()
+            })
+            .ok_or_else(|| self.error("no leg of the terms pays us".to_string()))?;
+        let contract_hash = self.contract_hash(leg)?;
+        Ok(self.client.check_terms(contract_hash, terms, ours)?)
+    }
+
+    fn claim(&self, leg: &Leg, secret: &str) -> Result<String, Error> {
+        let contract_hash = self.contract_hash(leg)?;
+        let mut unlock = Unlock::new(secret);
+        if let Some(payout) = self.payout {
+            let package_hash: ContractPackageHash = self
+                .client
+                .query(contract_hash, SwapField::ContractPackageHash)?;
+            let swap_id: u64 = self.client.query(contract_hash, SwapField::SwapId)?;
+            let signature =
+                sign_redemption(&self.secret_key, package_hash, swap_id, secret, payout);
+            unlock = unlock.signed(payout, signature);
+        }
+        let deploy = self
+            .builder
+            .unlock(contract_hash, &unlock, &self.secret_key)?;
+        Ok(self.client.put_deploy(&deploy)?)
+    }
+
+    fn refund(&self, leg: &Leg) -> Result<String, Error> {
+        let contract_hash = self.contract_hash(leg)?;
+        let deploy = self.builder.refund(contract_hash, &self.secret_key);
+        Ok(self.client.put_deploy(&deploy)?)
+    }
+
+    /// A deploy has no `execution_results` until a block executes it.
+    fn transaction_status(&self, transaction: &str) -> Result<TransactionStatus, Error> {
+        let deploy = self.client.deploy(transaction)?;
+        let result = match deploy.pointer("/execution_results/0/result") {
+            Some(result) => result,
+            None => return Ok(TransactionStatus::Pending),
+        };
+        if result.get("Success").is_some() {
+            return Ok(TransactionStatus::Succeeded);
+        }
+        let message = result
+            .pointer("/Failure/error_message")
+            .and_then(Value::as_str)
+            .unwrap_or("failed");
+        Ok(TransactionStatus::Failed(message.to_string()))
+    }
+}
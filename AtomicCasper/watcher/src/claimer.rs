@@ -0,0 +1,313 @@
+//! Claims our leg of a swap as soon as the counterparty redeems theirs.
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use swap_client::SignedTerms;
+
+use crate::{ChainAdapter, Error, Leg, LegState, RevealedSecret, TransactionStatus};
+
+/// The two legs of one swap, as seen by the participant: the counterparty redeems `watched`,
+/// which reveals the secret that redeems `claim`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SwapPair {
+    pub watched: Leg,
+    pub claim: Leg,
+    /// The terms both parties signed, if any: the claim leg is checked against them before the
+    /// pair is followed.
+    #[serde(default)]
+    pub terms: Option<SignedTerms>,
+}
+
+/// A claim that went through.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Claim {
+    pub pair: SwapPair,
+    pub secret: String,
+    pub transaction: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct Pending {
+    pair: SwapPair,
+    /// Set once the secret is revealed, so a failed claim is retried without scanning again.
+    secret: Option<String>,
+    /// The claim sent last, until it executes.
+    #[serde(default)]
+    transaction: Option<String>,
+}
+
+/// Where the claim of a revealed pair stands after a poll.
+enum Step {
+    /// Waiting for the claim to execute, or to be sent again.
+    Wait,
+    Claimed(String),
+    /// The claim leg expired or closed before a claim went through.
+    Lost,
+}
+
+/// Everything the claimer has to remember between runs.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct State {
+    /// Next block to scan, per chain.
+    cursors: BTreeMap<String, u64>,
+    pending: Vec<Pending>,
+    claims: Vec<Claim>,
+    expired: Vec<SwapPair>,
+}
+
+impl State {
+    /// Whether `chain` has been scanned before.
+    pub fn scanned(&self, chain: &str) -> bool {
+        self.cursors.contains_key(chain)
+    }
+}
+
+pub struct Claimer {
+    adapters: BTreeMap<String, Box<dyn ChainAdapter>>,
+    state: State,
+}
+
+impl Claimer {
+    pub fn new(state: State) -> Self {
+        Claimer {
+            adapters: BTreeMap::new(),
+            state,
+        }
+    }
+
+    pub fn with_adapter<A: ChainAdapter + 'static>(mut self, adapter: A) -> Self {
+        self.adapters
+            .insert(adapter.name().to_string(), Box::new(adapter));
+        self
+    }
+
+    pub fn state(&self) -> &State {
+        &self.state
+    }
+
+    /// Claims that went through so far.
+    pub fn claims(&self) -> &[Claim] {
+        &self.state.claims
+    }
+
+    /// Pairs whose claim leg expired, or closed, before a claim went through.
+    pub fn expired(&self) -> &[SwapPair] {
+        &self.state.expired
+    }
+
+    /// Scans `chain` from `height` on, instead of from its latest block when the claimer first
+    /// sees it. Use it when the redemption may have happened before the claimer started.
+    pub fn start_at(&mut self, chain: &str, height: u64) {
+        self.state.cursors.insert(chain.to_string(), height);
+    }
+
+    /// Starts following `pair`. Both chains need an adapter and the legs must share the secret.
+    /// A pair with signed terms is only followed if the claim leg is the one they agreed on,
+    /// which is worth knowing before locking the watched leg.
+    pub fn watch(&mut self, pair: SwapPair) -> Result<(), Error> {
+        for leg in [&pair.watched, &pair.claim] {
+            if !self.adapters.contains_key(&leg.chain) {
+                return Err(Error::UnknownChain(leg.chain.clone()));
+            }
+        }
+        // a salted leg has a hashlock of its own, only the revealed secret tells if it matches
+        let salted = pair.watched.salt.is_some() || pair.claim.salt.is_some();
+        if pair.watched.hashlock != pair.claim.hashlock
+            && pair.watched.hash_type == pair.claim.hash_type
+            && !salted
+        {
+            return Err(Error::SecretMismatch(pair.watched.contract));
+        }
+        let known = self
+            .state
+            .pending
+            .iter()
+            .any(|pending| pending.pair == pair)
+            || self.state.claims.iter().any(|claim| claim.pair == pair);
+        if !known {
+            if let Some(signed) = &pair.terms {
+                signed.verify()?;
+                self.adapters[&pair.claim.chain].check_terms(&pair.claim, &signed.terms)?;
+            }
+            self.state.pending.push(Pending {
+                pair,
+                secret: None,
+                transaction: None,
+            });
+        }
+        Ok(())
+    }
+
+    /// Scans the new blocks of every watched chain and claims what can be claimed. Errors of one
+    /// chain are logged and retried on the next poll, they don't stop the others.
+    pub fn poll(&mut self) -> Vec<Claim> {
+        let chains: Vec<String> = self
+            .state
+            .pending
+            .iter()
+            .filter(|pending| pending.secret.is_none())
+            .map(|pending| pending.pair.watched.chain.clone())
+            .collect();
+        for chain in chains {
+            if let Err(error) = self.scan(&chain) {
+                log::warn!("scanning {} failed: {}", chain, error);
+            }
+        }
+        self.claim_revealed()
+    }
+
+    fn scan(&mut self, chain: &str) -> Result<(), Error> {
+        let adapter = self
+            .adapters
+            .get(chain)
+            .ok_or_else(|| Error::UnknownChain(chain.to_string()))?;
+        let latest = adapter.latest_height()?;
+        let mut height = *self.state.cursors.get(chain).unwrap_or(&latest);
+        while height <= latest {
+            let secrets = adapter.revealed_secrets(height)?;
+            for revealed in secrets {
+                reveal(&mut self.state.pending, chain, &revealed);
+            }
+            height += 1;
+            self.state.cursors.insert(chain.to_string(), height);
+        }
+        Ok(())
+    }
+
+    fn claim_revealed(&mut self) -> Vec<Claim> {
+        let mut claimed = Vec::new();
+        let mut still_pending = Vec::new();
+        for mut pending in std::mem::take(&mut self.state.pending) {
+            let secret = match pending.secret.clone() {
+                Some(secret) => secret,
+                None => {
+                    still_pending.push(pending);
+                    continue;
+                }
+            };
+            match self.advance(&mut pending, &secret) {
+                Ok(Step::Wait) => still_pending.push(pending),
+                Ok(Step::Claimed(transaction)) => {
+                    log::info!(
+                        "claimed {} with {}",
+                        pending.pair.claim.contract,
+                        transaction
+                    );
+                    let claim = Claim {
+                        pair: pending.pair,
+                        secret,
+                        transaction,
+                    };
+                    self.state.claims.push(claim.clone());
+                    claimed.push(claim);
+                }
+                Ok(Step::Lost) => self.state.expired.push(pending.pair),
+                Err(error) => {
+                    log::warn!(
+                        "claiming {} failed, will retry: {}",
+                        pending.pair.claim.contract,
+                        error
+                    );
+                    still_pending.push(pending);
+                }
+            }
+        }
+        self.state.pending = still_pending;
+        claimed
+    }
+
+    /// Checks the claim sent last, and sends another one while the claim leg is open.
+    fn advance(&self, pending: &mut Pending, secret: &str) -> Result<Step, Error> {
+        let leg = &pending.pair.claim;
+        let adapter = self
+            .adapters
+            .get(&leg.chain)
+            .ok_or_else(|| Error::UnknownChain(leg.chain.clone()))?;
+        if let Some(transaction) = pending.transaction.clone() {
+            match adapter.transaction_status(&transaction)? {
+                TransactionStatus::Succeeded => return Ok(Step::Claimed(transaction)),
+                TransactionStatus::Pending => return Ok(Step::Wait),
+                TransactionStatus::Failed(error) => {
+                    log::warn!(
+                        "claim {} of {} failed, sending another: {}",
+                        transaction,
+                        leg.contract,
+                        error
+                    );
+                    pending.transaction = None;
+                }
+            }
+        }
+        // an accepted `propose_end_time` may have moved the deadline since the pair was watched
+        let end_time = match adapter.leg_state(leg)? {
+            LegState::Open { end_time } => end_time,
+            LegState::Closed => {
+                log::error!("{} closed before it could be claimed", leg.contract);
+                return Ok(Step::Lost);
+            }
+        };
+        // the contract unlocks up to and including the end time
+        if adapter.block_time()? > end_time {
+            log::error!("{} expired before it could be claimed", leg.contract);
+            return Ok(Step::Lost);
+        }
+        let transaction = adapter.claim(leg, secret)?;
+        log::info!("sent claim {} of {}", transaction, leg.contract);
+        pending.transaction = Some(transaction.clone());
+        // a chain that executes on submission settles the claim in the same poll
+        match adapter.transaction_status(&transaction)? {
+            TransactionStatus::Succeeded => Ok(Step::Claimed(transaction)),
+            TransactionStatus::Pending => Ok(Step::Wait),
+            TransactionStatus::Failed(error) => {
+                pending.transaction = None;
+                log::warn!(
+                    "claim {} of {} failed, will retry: {}",
+                    transaction,
+                    leg.contract,
+                    error
+                );
+                Ok(Step::Wait)
+            }
+        }
+    }
+}
+
+/// Attaches `revealed` to the pairs it opens. Contracts are reused between swaps, so a secret
+/// only counts for a pair if it matches the watched leg's hashlock.
+fn reveal(pending: &mut [Pending], chain: &str, revealed: &RevealedSecret) {
+    for pending in pending
+        .iter_mut()
+        .filter(|pending| pending.secret.is_none())
+    {
+        let watched = &pending.pair.watched;
+        if watched.chain != chain || watched.contract != revealed.contract {
+            continue;
+        }
+        if !watched.opens(&revealed.secret) {
+            continue;
+        }
+        match claim_secret(watched, &pending.pair.claim, &revealed.secret) {
+            Some(secret) => pending.secret = Some(secret),
+            None => log::error!(
+                "{}",
+                Error::SecretMismatch(pending.pair.watched.contract.clone())
+            ),
+        }
+    }
+}
+
+/// The secret that unlocks `claim`, from `secret` which unlocked `watched`: the same one, or,
+/// when a salted leg is paired with one locked with the same hashlock, the salted preimage of
+/// `secret` or the bare secret `secret` is the salted preimage of.
+fn claim_secret(watched: &Leg, claim: &Leg, secret: &str) -> Option<String> {
+    let salted = watched.salt.as_ref().map(|salt| salt.preimage(secret));
+    let bare = claim
+        .salt
+        .as_ref()
+        .and_then(|salt| secret.strip_suffix(&salt.preimage("")))
+        .map(str::to_string);
+    std::iter::once(secret.to_string())
+        .chain(salted)
+        .chain(bare)
+        .find(|candidate| claim.opens(candidate))
+}
@@ -0,0 +1,73 @@
+//! What the daemons read from their config file and keep in their state file.
+use std::{fs, path::Path};
+
+use casper_types::{Key, U512};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use swap_client::{
+    load_secret_key,
+    rpc::{HttpTransport, NodeClient},
+    DeployBuilder,
+};
+
+use crate::{casper::CasperAdapter, Error};
+
+/// A Casper network the daemon sends deploys to.
+#[derive(Clone, Debug, Deserialize)]
+pub struct ChainConfig {
+    /// What legs call the chain.
+    pub name: String,
+    /// e.g. `http://<node address>:7777/rpc`.
+    pub node_address: String,
+    /// `casper` or `casper-test`.
+    pub chain_name: String,
+    pub secret_key: String,
+    /// In motes.
+    pub payment_amount: String,
+    /// Formatted key claims pay out to, for swaps locked to our public key.
+    pub payout: Option<String>,
+    /// First block to scan when the chain isn't in the state yet.
+    pub start_height: Option<u64>,
+}
+
+impl ChainConfig {
+    pub fn adapter(&self) -> Result<CasperAdapter<HttpTransport>, Error> {
+        let payment_amount = U512::from_dec_str(&self.payment_amount).map_err(|error| {
+            Error::Config(format!(
+                "invalid payment amount {}: {:?}",
+                self.payment_amount, error
+            ))
+        })?;
+        let secret_key = load_secret_key(&self.secret_key)?;
+        let client = NodeClient::new(HttpTransport::new(&self.node_address));
+        let builder = DeployBuilder::new(&self.chain_name, payment_amount);
+        let mut adapter = CasperAdapter::new(&self.name, client, builder, secret_key);
+        if let Some(payout) = &self.payout {
+            let payout = Key::from_formatted_str(payout).map_err(|error| {
+                Error::Config(format!("invalid payout {}: {:?}", payout, error))
+            })?;
+            adapter = adapter.with_payout(payout);
+        }
+        Ok(adapter)
+    }
+}
+
+/// Reads a JSON file.
+pub fn load<T: DeserializeOwned>(path: &Path) -> Result<T, Error> {
+    Ok(serde_json::from_slice(&fs::read(path)?)?)
+}
+
+/// Reads the state, or starts from scratch on the first run.
+pub fn load_state<T: DeserializeOwned + Default>(path: &Path) -> Result<T, Error> {
+    if !path.exists() {
+        return Ok(T::default());
+    }
+    load(path)
+}
+
+/// Replaces the state file in one step, so a crash never leaves half of it.
+pub fn save_state<T: Serialize>(path: &Path, state: &T) -> Result<(), Error> {
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, serde_json::to_vec_pretty(state)?)?;
+    fs::rename(&tmp, path)?;
+    Ok(())
+}
@@ -0,0 +1,17 @@
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("no adapter for chain {0}")]
+    UnknownChain(String),
+    #[error("{chain}: {message}")]
+    Chain { chain: String, message: String },
+    #[error("the secret revealed for {0} doesn't open the paired leg")]
+    SecretMismatch(String),
+    #[error("invalid config: {0}")]
+    Config(String),
+    #[error(transparent)]
+    Client(#[from] swap_client::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
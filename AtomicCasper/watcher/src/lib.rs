@@ -0,0 +1,22 @@
+//! Off-chain services that follow running swaps and act on them before their deadlines.
+//!
+//! [`Claimer`] watches the leg the counterparty redeems and claims the paired leg with the
+//! secret the redemption revealed. [`Refunder`] gets back the swaps our keys initiated once
+//! they expire.
+//!
+//! Chains are reached through the [`ChainAdapter`] trait. [`casper::CasperAdapter`] talks to a
+//! Casper node, other chains plug in with their own adapter, and `mock::MockChain`, built with
+//! the `test-util` feature, stands in for a node in tests.
+pub mod adapter;
+pub mod casper;
+pub mod claimer;
+pub mod config;
+mod error;
+#[cfg(feature = "test-util")]
+pub mod mock;
+pub mod refunder;
+
+pub use adapter::{ChainAdapter, Leg, LegState, RevealedSecret, TransactionStatus};
+pub use claimer::{Claim, Claimer, SwapPair};
+pub use error::Error;
+pub use refunder::{Outcome, Refunder};
@@ -0,0 +1,237 @@
+//! An in-memory chain for tests of the services.
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use swap_client::SwapTerms;
+
+use crate::{ChainAdapter, Error, Leg, LegState, RevealedSecret, TransactionStatus};
+
+#[derive(Clone, Default)]
+struct Block {
+    revealed: Vec<RevealedSecret>,
+    initiated: Vec<Leg>,
+}
+
+#[derive(Default)]
+struct Inner {
+    blocks: Vec<Block>,
+    block_time: u64,
+    /// Hashlock and end time of the open swap of each contract.
+    swaps: BTreeMap<String, (String, u64)>,
+    claims: Vec<(Leg, String)>,
+    refunds: Vec<Leg>,
+    failures: usize,
+    reverts: usize,
+    transactions: BTreeMap<String, TransactionStatus>,
+}
+
+/// Cheap to clone: every clone is a handle to the same chain, so a test can keep one while the
+/// service owns another.
+#[derive(Clone)]
+pub struct MockChain {
+    name: String,
+    inner: Arc<Mutex<Inner>>,
+}
+
+impl MockChain {
+    /// Starts with an empty genesis block at height 0.
+    pub fn new(name: &str) -> Self {
+        let inner = Inner {
+            blocks: vec![Block::default()],
+            ..Inner::default()
+        };
+        MockChain {
+            name: name.to_string(),
+            inner: Arc::new(Mutex::new(inner)),
+        }
+    }
+
+    fn inner(&self) -> MutexGuard<Inner> {
+        self.inner.lock().unwrap()
+    }
+
+    fn error(&self, message: &str) -> Error {
+        Error::Chain {
+            chain: self.name.clone(),
+            message: message.to_string(),
+        }
+    }
+
+    /// Locks `leg` without a block our keys would pick up, like a swap of the counterparty.
+    pub fn open(&self, leg: &Leg) {
+        self.inner()
+            .swaps
+            .insert(leg.contract.clone(), (leg.hashlock.clone(), leg.end_time));
+    }
+
+    /// Adds a block in which our keys initiated `leg`.
+    pub fn initiate(&self, leg: &Leg) {
+        self.open(leg);
+        self.inner().blocks.push(Block {
+            initiated: vec![leg.clone()],
+            ..Block::default()
+        });
+    }
+
+    /// Adds a block in which `contract` was redeemed with `secret`.
+    pub fn reveal(&self, contract: &str, secret: &str) {
+        let mut inner = self.inner();
+        inner.swaps.remove(contract);
+        inner.blocks.push(Block {
+            revealed: vec![RevealedSecret {
+                contract: contract.to_string(),
+                secret: secret.to_string(),
+            }],
+            ..Block::default()
+        });
+    }
+
+    /// Adds an empty block.
+    pub fn produce_block(&self) {
+        self.inner().blocks.push(Block::default());
+    }
+
+    pub fn set_block_time(&self, block_time: u64) {
+        self.inner().block_time = block_time;
+    }
+
+    /// Moves the deadline of the open swap of `contract`, like an accepted `propose_end_time`.
+    pub fn extend(&self, contract: &str, end_time: u64) {
+        if let Some(swap) = self.inner().swaps.get_mut(contract) {
+            swap.1 = end_time;
+        }
+    }
+
+    /// Makes the next `count` transactions fail.
+    pub fn fail_next(&self, count: usize) {
+        self.inner().failures = count;
+    }
+
+    /// Makes the next `count` transactions revert: they are accepted, and change nothing.
+    pub fn revert_next(&self, count: usize) {
+        self.inner().reverts = count;
+    }
+
+    /// Legs claimed so far, with the secret used.
+    pub fn claims(&self) -> Vec<(Leg, String)> {
+        self.inner().claims.clone()
+    }
+
+    /// Legs refunded so far.
+    pub fn refunds(&self) -> Vec<Leg> {
+        self.inner().refunds.clone()
+    }
+
+    /// Executes a transaction right away, returns its id and whether it succeeded.
+    fn submit(&self) -> Result<(String, bool), Error> {
+        let mut inner = self.inner();
+        if inner.failures > 0 {
+            inner.failures -= 1;
+            return Err(self.error("transaction rejected"));
+        }
+        let transaction = format!("{}-{}", self.name, inner.transactions.len() + 1);
+        let status = if inner.reverts > 0 {
+            inner.reverts -= 1;
+            TransactionStatus::Failed("reverted".to_string())
+        } else {
+            TransactionStatus::Succeeded
+        };
+        let succeeded = status == TransactionStatus::Succeeded;
+        inner.transactions.insert(transaction.clone(), status);
+        Ok((transaction, succeeded))
+    }
+
+    fn block(&self, height: u64) -> Result<Block, Error> {
+        self.inner()
+            .blocks
+            .get(height as usize)
+            .cloned()
+            .ok_or_else(|| self.error(&format!("no block {}", height)))
+    }
+}
+
+impl ChainAdapter for MockChain {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn latest_height(&self) -> Result<u64, Error> {
+        Ok(self.inner().blocks.len() as u64 - 1)
+    }
+
+    fn block_time(&self) -> Result<u64, Error> {
+        Ok(self.inner().block_time)
+    }
+
+    fn revealed_secrets(&self, height: u64) -> Result<Vec<RevealedSecret>, Error> {
+        Ok(self.block(height)?.revealed)
+    }
+
+    fn initiated(&self, height: u64) -> Result<Vec<Leg>, Error> {
+        Ok(self.block(height)?.initiated)
+    }
+
+    fn leg_state(&self, leg: &Leg) -> Result<LegState, Error> {
+        Ok(match self.inner().swaps.get(&leg.contract) {
+            Some((hashlock, end_time)) if *hashlock == leg.hashlock => LegState::Open {
+                end_time: *end_time,
+            },
+            _ => LegState::Closed,
+        })
+    }
+
+    /// Only knows hashlocks and end times: the swap must be locked with the terms' hashlock,
+    /// until at most the end time of one of their legs on this chain.
+    fn check_terms(&self, leg: &Leg, terms: &SwapTerms) -> Result<(), Error> {
+        let (hashlock, end_time) = self
+            .inner()
+            .swaps
+            .get(&leg.contract)
+            .cloned()
+            .ok_or_else(|| self.error("no open swap"))?;
+        let agreed = [&terms.initiator, &terms.participant]
+            .iter()
+            .any(|terms_leg| terms_leg.chain == self.name && end_time <= terms_leg.end_time);
+        if hashlock != terms.hashlock || !agreed {
+            return Err(self.error("the swap differs from the terms"));
+        }
+        Ok(())
+    }
+
+    fn claim(&self, leg: &Leg, secret: &str) -> Result<String, Error> {
+        let (transaction, succeeded) = self.submit()?;
+        if succeeded {
+            let mut inner = self.inner();
+            inner.swaps.remove(&leg.contract);
+            inner.claims.push((leg.clone(), secret.to_string()));
+        }
+        Ok(transaction)
+    }
+
+    fn refund(&self, leg: &Leg) -> Result<String, Error> {
+        let end_time = match self.leg_state(leg)? {
+            LegState::Open { end_time } => end_time,
+            LegState::Closed => return Err(self.error("not initialized")),
+        };
+        if self.inner().block_time <= end_time {
+            return Err(self.error("end time not reached"));
+        }
+        let (transaction, succeeded) = self.submit()?;
+        if succeeded {
+            let mut inner = self.inner();
+            inner.swaps.remove(&leg.contract);
+            inner.refunds.push(leg.clone());
+        }
+        Ok(transaction)
+    }
+
+    fn transaction_status(&self, transaction: &str) -> Result<TransactionStatus, Error> {
+        self.inner()
+            .transactions
+            .get(transaction)
+            .cloned()
+            .ok_or_else(|| self.error(&format!("no transaction {}", transaction)))
+    }
+}
@@ -0,0 +1,270 @@
+//! Gets back the swaps our keys initiated once they expire, so no funds stay locked because
+//! nobody remembered to call `refund`.
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{ChainAdapter, Error, Leg, LegState, TransactionStatus};
+
+/// How a tracked swap ended.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Outcome {
+    Refunded {
+        leg: Leg,
+        transaction: String,
+    },
+    /// Redeemed by the counterparty, or cancelled, before we had to refund.
+    Closed {
+        leg: Leg,
+    },
+    /// Every attempt failed, the refund needs a human.
+    Abandoned {
+        leg: Leg,
+        attempts: u32,
+        error: String,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct Tracked {
+    leg: Leg,
+    attempts: u32,
+    /// Block time before which the refund isn't tried again.
+    retry_at: u64,
+    /// The refund sent last, until it executes.
+    #[serde(default)]
+    transaction: Option<String>,
+}
+
+/// Everything the refunder has to remember between runs.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct State {
+    /// Next block to scan, per chain.
+    cursors: BTreeMap<String, u64>,
+    tracked: Vec<Tracked>,
+    outcomes: Vec<Outcome>,
+}
+
+impl State {
+    /// Whether `chain` has been scanned before.
+    pub fn scanned(&self, chain: &str) -> bool {
+        self.cursors.contains_key(chain)
+    }
+}
+
+pub struct Refunder {
+    adapters: BTreeMap<String, Box<dyn ChainAdapter>>,
+    state: State,
+    buffer: u64,
+    retry_delay: u64,
+    max_attempts: u32,
+}
+
+impl Refunder {
+    /// Refunds `buffer` milliseconds after a swap's end time, so that a refund is not sent on a
+    /// block whose time the contract still sees as before the deadline. Failed refunds are
+    /// retried every `retry_delay` milliseconds, up to 10 times.
+    pub fn new(state: State, buffer: u64, retry_delay: u64) -> Self {
+        Refunder {
+            adapters: BTreeMap::new(),
+            state,
+            buffer,
+            retry_delay,
+            max_attempts: 10,
+        }
+    }
+
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    pub fn with_adapter<A: ChainAdapter + 'static>(mut self, adapter: A) -> Self {
+        self.adapters
+            .insert(adapter.name().to_string(), Box::new(adapter));
+        self
+    }
+
+    pub fn state(&self) -> &State {
+        &self.state
+    }
+
+    /// Swaps waiting for their deadline or for a retry.
+    pub fn tracked(&self) -> Vec<&Leg> {
+        self.state
+            .tracked
+            .iter()
+            .map(|tracked| &tracked.leg)
+            .collect()
+    }
+
+    pub fn outcomes(&self) -> &[Outcome] {
+        &self.state.outcomes
+    }
+
+    /// Scans `chain` from `height` on, instead of from its latest block when the refunder first
+    /// sees it, to pick up swaps initiated while it wasn't running.
+    pub fn start_at(&mut self, chain: &str, height: u64) {
+        self.state.cursors.insert(chain.to_string(), height);
+    }
+
+    /// Tracks a swap that wasn't initiated from one of the adapters' keys, e.g. through another
+    /// node.
+    pub fn track(&mut self, leg: Leg) -> Result<(), Error> {
+        if !self.adapters.contains_key(&leg.chain) {
+            return Err(Error::UnknownChain(leg.chain));
+        }
+        let known = self.state.tracked.iter().any(|tracked| tracked.leg == leg)
+            || self
+                .state
+                .outcomes
+                .iter()
+                .any(|outcome| outcome.leg() == &leg);
+        if !known {
+            self.state.tracked.push(Tracked {
+                leg,
+                attempts: 0,
+                retry_at: 0,
+                transaction: None,
+            });
+        }
+        Ok(())
+    }
+
+    /// Picks up new swaps and refunds the expired ones. Returns the outcomes reached by this
+    /// poll; errors are logged and retried on the next one.
+    pub fn poll(&mut self) -> Vec<Outcome> {
+        let chains: Vec<String> = self.adapters.keys().cloned().collect();
+        for chain in chains {
+            if let Err(error) = self.scan(&chain) {
+                log::warn!("scanning {} failed: {}", chain, error);
+            }
+        }
+        let mut reached = Vec::new();
+        for mut tracked in std::mem::take(&mut self.state.tracked) {
+            match self.advance(&mut tracked) {
+                Ok(Some(outcome)) => {
+                    self.state.outcomes.push(outcome.clone());
+                    reached.push(outcome);
+                }
+                Ok(None) => self.state.tracked.push(tracked),
+                Err(error) => {
+                    log::warn!("checking {} failed: {}", tracked.leg.contract, error);
+                    self.state.tracked.push(tracked);
+                }
+            }
+        }
+        reached
+    }
+
+    fn scan(&mut self, chain: &str) -> Result<(), Error> {
+        let latest = self.adapter(chain)?.latest_height()?;
+        let mut height = *self.state.cursors.get(chain).unwrap_or(&latest);
+        while height <= latest {
+            let legs = self.adapter(chain)?.initiated(height)?;
+            for leg in legs {
+                log::info!("tracking {}, ends at {}", leg.contract, leg.end_time);
+                self.track(leg)?;
+            }
+            height += 1;
+            self.state.cursors.insert(chain.to_string(), height);
+        }
+        Ok(())
+    }
+
+    fn adapter(&self, chain: &str) -> Result<&dyn ChainAdapter, Error> {
+        self.adapters
+            .get(chain)
+            .map(Box::as_ref)
+            .ok_or_else(|| Error::UnknownChain(chain.to_string()))
+    }
+
+    fn advance(&self, tracked: &mut Tracked) -> Result<Option<Outcome>, Error> {
+        let adapter = self.adapter(&tracked.leg.chain)?;
+        let now = adapter.block_time()?;
+        if let Some(transaction) = tracked.transaction.clone() {
+            return self.settle(tracked, transaction, now);
+        }
+        let due = tracked.leg.end_time.saturating_add(self.buffer);
+        if now < due || now < tracked.retry_at {
+            return Ok(None);
+        }
+        match adapter.leg_state(&tracked.leg)? {
+            LegState::Closed => {
+                return Ok(Some(Outcome::Closed {
+                    leg: tracked.leg.clone(),
+                }))
+            }
+            LegState::Open { end_time } if end_time > tracked.leg.end_time => {
+                log::info!("{} was extended to {}", tracked.leg.contract, end_time);
+                tracked.leg.end_time = end_time;
+                return Ok(None);
+            }
+            LegState::Open { .. } => {}
+        }
+        match adapter.refund(&tracked.leg) {
+            Ok(transaction) => {
+                log::info!("sent refund {} of {}", transaction, tracked.leg.contract);
+                tracked.transaction = Some(transaction.clone());
+                // a chain that executes on submission settles the refund in the same poll
+                self.settle(tracked, transaction, now)
+            }
+            Err(error) => Ok(self.failed(tracked, now, error.to_string())),
+        }
+    }
+
+    /// Checks the refund sent last: done once it succeeded, retried if it reverted.
+    fn settle(
+        &self,
+        tracked: &mut Tracked,
+        transaction: String,
+        now: u64,
+    ) -> Result<Option<Outcome>, Error> {
+        let adapter = self.adapter(&tracked.leg.chain)?;
+        match adapter.transaction_status(&transaction)? {
+            TransactionStatus::Succeeded => {
+                log::info!("refunded {} with {}", tracked.leg.contract, transaction);
+                Ok(Some(Outcome::Refunded {
+                    leg: tracked.leg.clone(),
+                    transaction,
+                }))
+            }
+            TransactionStatus::Pending => Ok(None),
+            TransactionStatus::Failed(error) => {
+                tracked.transaction = None;
+                Ok(self.failed(tracked, now, error))
+            }
+        }
+    }
+
+    /// Counts a failed attempt, and gives up after the last one.
+    fn failed(&self, tracked: &mut Tracked, now: u64, error: String) -> Option<Outcome> {
+        tracked.attempts += 1;
+        if tracked.attempts >= self.max_attempts {
+            log::error!("giving up on refunding {}: {}", tracked.leg.contract, error);
+            return Some(Outcome::Abandoned {
+                leg: tracked.leg.clone(),
+                attempts: tracked.attempts,
+                error,
+            });
+        }
+        log::warn!(
+            "refunding {} failed, attempt {}: {}",
+            tracked.leg.contract,
+            tracked.attempts,
+            error
+        );
+        tracked.retry_at = now.saturating_add(self.retry_delay);
+        None
+    }
+}
+
+impl Outcome {
+    pub fn leg(&self) -> &Leg {
+        match self {
+            Outcome::Refunded { leg, .. }
+            | Outcome::Closed { leg }
+            | Outcome::Abandoned { leg, .. } => leg,
+        }
+    }
+}
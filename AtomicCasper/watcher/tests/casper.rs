@@ -0,0 +1,171 @@
+use casper_types::{bytesrepr::ToBytes, AsymmetricType, PublicKey, SecretKey, U512};
+use serde_json::{json, Value};
+use swap_client::{
+    rpc::{NodeClient, Transport},
+    DeployBuilder, Error,
+};
+use swap_watcher::{casper::CasperAdapter, ChainAdapter, Leg, LegState, TransactionStatus};
+
+const CONTRACT: &str = "0101010101010101010101010101010101010101010101010101010101010101";
+
+/// Answers like a node with one block holding an `unlock`, a failed `unlock`, a transfer and
+/// two `initiate`s, one of them ours.
+struct Node;
+
+fn secret_key() -> SecretKey {
+    SecretKey::ed25519_from_bytes([7u8; 32]).unwrap()
+}
+
+fn cl_value<T: ToBytes>(value: T) -> Value {
+    json!({ "bytes": hex::encode(value.to_bytes().unwrap()) })
+}
+
+fn contract_deploy(account: &str, entry_point: &str, args: Value, result: Value) -> Value {
+    json!({
+        "deploy": {
+            "header": { "account": account },
+            "session": {
+                "StoredContractByHash": {
+                    "hash": CONTRACT,
+                    "entry_point": entry_point,
+                    "args": args,
+                }
+            }
+        },
+        "execution_results": [{ "block_hash": "00", "result": result }],
+    })
+}
+
+fn unlock_deploy(secret: &str, result: Value) -> Value {
+    let args = json!([["secret", cl_value(secret.to_string())]]);
+    contract_deploy("01", "unlock", args, result)
+}
+
+fn initiate_deploy(account: &str) -> Value {
+    let args = json!([
+        ["hash", cl_value("lock".to_string())],
+        ["hash_type", cl_value("sha3-256".to_string())],
+        ["timeout", cl_value(60_000u64)],
+    ]);
+    contract_deploy(account, "initiate", args, json!({ "Success": {} }))
+}
+
+impl Transport for Node {
+    fn call(&self, method: &str, params: Value) -> Result<Value, Error> {
+        match method {
+            "chain_get_block" => Ok(json!({
+                "block": {
+                    "header": { "height": 7, "timestamp": "2022-03-01T12:00:00.000Z" },
+                    "body": { "deploy_hashes": ["aa", "bb", "cc", "ee", "ff"] },
+                }
+            })),
+            "info_get_deploy" => match params["deploy_hash"].as_str().unwrap() {
+                "aa" => Ok(unlock_deploy("revealed", json!({ "Success": {} }))),
+                "bb" => Ok(unlock_deploy(
+                    "wrong",
+                    json!({ "Failure": { "error_message": "User error: 8" } }),
+                )),
+                "ee" => Ok(initiate_deploy(&PublicKey::from(&secret_key()).to_hex())),
+                "ff" => Ok(initiate_deploy("01aa")),
+                "dd" => Ok(json!({ "deploy": {}, "execution_results": [] })),
+                _ => Ok(json!({
+                    "deploy": { "session": { "Transfer": { "args": [] } } },
+                    "execution_results": [{ "result": { "Success": {} } }],
+                })),
+            },
+            "chain_get_state_root_hash" => Ok(json!({ "state_root_hash": "00" })),
+            "query_global_state" => {
+                let value = match params["path"][0].as_str().unwrap() {
+                    "hash" => cl_value("lock".to_string()),
+                    "end_time" => cl_value(1_646_136_060_000u64),
+                    other => return Err(Error::Rpc(format!("unexpected {}", other))),
+                };
+                Ok(json!({ "stored_value": { "CLValue": value } }))
+            }
+            "account_put_deploy" => {
+                let entry_point = params
+                    .pointer("/deploy/session/StoredContractByHash/entry_point")
+                    .and_then(Value::as_str)
+                    .unwrap();
+                Ok(json!({ "deploy_hash": entry_point }))
+            }
+            other => Err(Error::Rpc(format!("unexpected {}", other))),
+        }
+    }
+}
+
+fn adapter() -> CasperAdapter<Node> {
+    CasperAdapter::new(
+        "casper-test",
+        NodeClient::new(Node),
+        DeployBuilder::new("casper-test", U512::from(3_000_000_000u64)),
+        secret_key(),
+    )
+}
+
+fn leg(hashlock: &str) -> Leg {
+    Leg {
+        chain: "casper-test".to_string(),
+        contract: format!("contract-{}", CONTRACT),
+        hash_type: "sha3-256".to_string(),
+        hashlock: hashlock.to_string(),
+        end_time: 1_646_136_060_000,
+        salt: None,
+    }
+}
+
+#[test]
+fn reads_blocks() {
+    let adapter = adapter();
+    assert_eq!(adapter.latest_height().unwrap(), 7);
+    assert_eq!(adapter.block_time().unwrap(), 1_646_136_000_000);
+}
+
+#[test]
+fn extracts_secrets_of_successful_unlocks() {
+    let secrets = adapter().revealed_secrets(7).unwrap();
+    assert_eq!(secrets.len(), 1);
+    assert_eq!(secrets[0].secret, "revealed");
+    assert_eq!(secrets[0].contract, format!("contract-{}", CONTRACT));
+}
+
+#[test]
+fn finds_our_initiates() {
+    assert_eq!(adapter().initiated(7).unwrap(), vec![leg("lock")]);
+}
+
+#[test]
+fn reads_leg_state() {
+    let adapter = adapter();
+    assert_eq!(
+        adapter.leg_state(&leg("lock")).unwrap(),
+        LegState::Open {
+            end_time: 1_646_136_060_000
+        }
+    );
+    assert_eq!(adapter.leg_state(&leg("other")).unwrap(), LegState::Closed);
+}
+
+#[test]
+fn sends_unlock_and_refund_deploys() {
+    let adapter = adapter();
+    assert_eq!(adapter.claim(&leg("lock"), "revealed").unwrap(), "unlock");
+    assert_eq!(adapter.refund(&leg("lock")).unwrap(), "refund");
+}
+
+#[test]
+fn reads_transaction_status() {
+    let adapter = adapter();
+    assert_eq!(
+        adapter.transaction_status("aa").unwrap(),
+        TransactionStatus::Succeeded
+    );
+    assert_eq!(
+        adapter.transaction_status("bb").unwrap(),
+        TransactionStatus::Failed("User error: 8".to_string())
+    );
+    assert_eq!(
+        adapter.transaction_status("dd").unwrap(),
+        TransactionStatus::Pending
+    );
+}
@@ -0,0 +1,319 @@
+use casper_types::{ContractPackageHash, PublicKey, SecretKey, U256};
+use swap_client::{hashlock, swap_hashlock, terms::LegTerms, Salt, SignedTerms, SwapTerms};
+use swap_watcher::{mock::MockChain, Claimer, Leg, SwapPair};
+
+const SECRET: &str = "6f9b2c0a1d";
+
+fn leg(chain: &str, contract: &str, end_time: u64) -> Leg {
+    Leg {
+        chain: chain.to_string(),
+        contract: contract.to_string(),
+        hash_type: "sha3-256".to_string(),
+        hashlock: hashlock("sha3-256", SECRET).unwrap(),
+        end_time,
+        salt: None,
+    }
+}
+
+fn pair() -> SwapPair {
+    SwapPair {
+        watched: leg("remote", "remote-swap", 2_000),
+        claim: leg("local", "local-swap", 1_000),
+        terms: None,
+    }
+}
+
+/// `leg` locked with the hashlock of [`SECRET`] salted for swap `swap_id`.
+fn salted(leg: Leg, swap_id: u64) -> Leg {
+    let salt = Salt {
+        contract_package_hash: ContractPackageHash::new([9u8; 32]),
+        swap_id,
+    };
+    Leg {
+        hashlock: swap_hashlock("sha3-256", SECRET, Some(&salt)).unwrap(),
+        salt: Some(salt),
+        ..leg
+    }
+}
+
+/// A claimer following `pair`, with its claim leg open.
+fn follow(pair: &SwapPair) -> (MockChain, MockChain, Claimer) {
+    let remote = MockChain::new("remote");
+    let local = MockChain::new("local");
+    local.open(&pair.claim);
+    let mut claimer = Claimer::new(Default::default())
+        .with_adapter(remote.clone())
+        .with_adapter(local.clone());
+    claimer.watch(pair.clone()).unwrap();
+    (remote, local, claimer)
+}
+
+fn terms_leg(chain: &str, end_time: u64) -> LegTerms {
+    LegTerms {
+        chain: chain.to_string(),
+        asset: "CSPR".to_string(),
+        amount: U256::from(100u64),
+        token_ids: Vec::new(),
+        sender: "account-hash-01".to_string(),
+        receiver: "account-hash-02".to_string(),
+        end_time,
+    }
+}
+
+/// Terms both parties signed, where the initiator locks the claim leg.
+fn signed_terms(secret: &str) -> SignedTerms {
+    let initiator = SecretKey::ed25519_from_bytes([1u8; 32]).unwrap();
+    let participant = SecretKey::ed25519_from_bytes([2u8; 32]).unwrap();
+    let mut signed = SignedTerms::new(SwapTerms {
+        version: 1,
+        hash_type: "sha3-256".to_string(),
+        hashlock: hashlock("sha3-256", secret).unwrap(),
+        initiator: terms_leg("local", 3_000),
+        participant: terms_leg("remote", 2_000),
+        initiator_key: PublicKey::from(&initiator),
+        participant_key: PublicKey::from(&participant),
+    });
+    signed.sign(&initiator).unwrap();
+    signed.sign(&participant).unwrap();
+    signed
+}
+
+fn setup() -> (MockChain, MockChain, Claimer) {
+    follow(&pair())
+}
+
+#[test]
+fn claims_with_revealed_secret() {
+    let (remote, local, mut claimer) = setup();
+    assert!(claimer.poll().is_empty());
+
+    remote.produce_block();
+    remote.reveal("remote-swap", SECRET);
+    let claims = claimer.poll();
+    assert_eq!(claims.len(), 1);
+    assert_eq!(claims[0].secret, SECRET);
+    assert_eq!(claims[0].transaction, "local-1");
+    assert_eq!(local.claims(), vec![(pair().claim, SECRET.to_string())]);
+
+    // nothing left to do
+    remote.reveal("remote-swap", SECRET);
+    assert!(claimer.poll().is_empty());
+    assert_eq!(local.claims().len(), 1);
+    assert_eq!(claimer.claims().len(), 1);
+}
+
+#[test]
+fn ignores_other_swaps_of_the_contract() {
+    let (remote, local, mut claimer) = setup();
+    remote.reveal("remote-swap", "an earlier swap");
+    remote.reveal("other-swap", SECRET);
+    assert!(claimer.poll().is_empty());
+    assert!(local.claims().is_empty());
+}
+
+#[test]
+fn retries_failed_claims() {
+    let (remote, local, mut claimer) = setup();
+    local.fail_next(2);
+    remote.reveal("remote-swap", SECRET);
+    assert!(claimer.poll().is_empty());
+    assert!(claimer.poll().is_empty());
+    assert_eq!(claimer.poll().len(), 1);
+    assert_eq!(local.claims().len(), 1);
+}
+
+#[test]
+fn sends_claims_again_until_one_goes_through() {
+    let (remote, local, mut claimer) = setup();
+    local.revert_next(1);
+    remote.reveal("remote-swap", SECRET);
+    assert!(claimer.poll().is_empty());
+    assert!(local.claims().is_empty());
+    assert!(claimer.claims().is_empty());
+
+    let claims = claimer.poll();
+    assert_eq!(claims.len(), 1);
+    assert_eq!(claims[0].transaction, "local-2");
+    assert_eq!(local.claims().len(), 1);
+}
+
+#[test]
+fn gives_up_once_claim_leg_closed() {
+    let (remote, local, mut claimer) = setup();
+    local.revert_next(1);
+    remote.reveal("remote-swap", SECRET);
+    assert!(claimer.poll().is_empty());
+    // the counterparty got its asset back in the meantime
+    local.reveal("local-swap", "refund");
+    assert!(claimer.poll().is_empty());
+    assert!(local.claims().is_empty());
+    assert_eq!(claimer.expired(), &[pair()]);
+}
+
+#[test]
+fn gives_up_after_claim_leg_expired() {
+    let (remote, local, mut claimer) = setup();
+    remote.reveal("remote-swap", SECRET);
+    local.set_block_time(1_001);
+    assert!(claimer.poll().is_empty());
+    assert!(local.claims().is_empty());
+    assert_eq!(claimer.expired(), &[pair()]);
+}
+
+#[test]
+fn claims_until_the_end_time_included() {
+    let (remote, local, mut claimer) = setup();
+    remote.reveal("remote-swap", SECRET);
+    local.set_block_time(1_000);
+    assert_eq!(claimer.poll().len(), 1);
+    assert_eq!(local.claims().len(), 1);
+}
+
+#[test]
+fn follows_extended_end_time() {
+    let (remote, local, mut claimer) = setup();
+    local.extend("local-swap", 5_000);
+    local.set_block_time(2_000);
+    remote.reveal("remote-swap", SECRET);
+    assert_eq!(claimer.poll().len(), 1);
+    assert!(claimer.expired().is_empty());
+}
+
+#[test]
+fn starts_from_given_height() {
+    let remote = MockChain::new("remote");
+    let local = MockChain::new("local");
+    local.open(&pair().claim);
+    remote.reveal("remote-swap", SECRET);
+    remote.produce_block();
+
+    let mut late = Claimer::new(Default::default())
+        .with_adapter(remote.clone())
+        .with_adapter(local.clone());
+    late.watch(pair()).unwrap();
+    assert!(late.poll().is_empty());
+
+    let mut claimer = Claimer::new(Default::default())
+        .with_adapter(remote)
+        .with_adapter(local);
+    claimer.start_at("remote", 0);
+    claimer.watch(pair()).unwrap();
+    assert_eq!(claimer.poll().len(), 1);
+}
+
+#[test]
+fn state_survives_restart() {
+    let (remote, local, mut claimer) = setup();
+    claimer.poll();
+    let state = serde_json::to_string(claimer.state()).unwrap();
+
+    remote.reveal("remote-swap", SECRET);
+    let mut restarted = Claimer::new(serde_json::from_str(&state).unwrap())
+        .with_adapter(remote)
+        .with_adapter(local.clone());
+    restarted.watch(pair()).unwrap();
+    assert_eq!(restarted.poll().len(), 1);
+    assert_eq!(local.claims().len(), 1);
+}
+
+#[test]
+fn rejects_unknown_chains_and_mismatched_hashlocks() {
+    let (_, _, mut claimer) = setup();
+    let mut unknown = pair();
+    unknown.claim.chain = "elsewhere".to_string();
+    assert!(claimer.watch(unknown).is_err());
+
+    let mut mismatched = pair();
+    mismatched.claim.hashlock = hashlock("sha3-256", "another secret").unwrap();
+    assert!(claimer.watch(mismatched).is_err());
+}
+
+#[test]
+fn follows_pairs_whose_claim_leg_matches_the_terms() {
+    let (remote, local, _) = setup();
+    let mut claimer = Claimer::new(Default::default())
+        .with_adapter(remote.clone())
+        .with_adapter(local.clone());
+    let pair = SwapPair {
+        terms: Some(signed_terms(SECRET)),
+        ..pair()
+    };
+    claimer.watch(pair).unwrap();
+    remote.reveal("remote-swap", SECRET);
+    assert_eq!(claimer.poll().len(), 1);
+}
+
+#[test]
+fn refuses_pairs_whose_claim_leg_differs_from_the_terms() {
+    let (_, local, mut claimer) = setup();
+    let other_hashlock = SwapPair {
+        terms: Some(signed_terms("another secret")),
+        ..pair()
+    };
+    assert!(claimer.watch(other_hashlock).is_err());
+
+    let mut unsigned = signed_terms(SECRET);
+    unsigned.participant_signature = None;
+    let unsigned = SwapPair {
+        terms: Some(unsigned),
+        ..pair()
+    };
+    assert!(claimer.watch(unsigned).is_err());
+
+    let mut too_late = pair();
+    too_late.terms = Some(signed_terms(SECRET));
+    too_late.claim.end_time = 3_001;
+    local.open(&too_late.claim);
+    assert!(claimer.watch(too_late).is_err());
+}
+
+#[test]
+fn claims_salted_legs_with_the_bare_secret() {
+    // the remote leg is locked with the salted hashlock of the local one, and unlocked with
+    // the salted preimage
+    let pair = SwapPair {
+        watched: Leg {
+            salt: None,
+            ..salted(leg("remote", "remote-swap", 2_000), 2)
+        },
+        claim: salted(leg("local", "local-swap", 1_000), 2),
+        terms: None,
+    };
+    let (remote, local, mut claimer) = follow(&pair);
+    remote.reveal("remote-swap", SECRET);
+    assert!(claimer.poll().is_empty());
+    let preimage = pair.claim.salt.unwrap().preimage(SECRET);
+    remote.reveal("remote-swap", &preimage);
+    assert_eq!(claimer.poll().len(), 1);
+    assert_eq!(local.claims(), vec![(pair.claim, SECRET.to_string())]);
+}
+
+#[test]
+fn claims_with_the_secret_of_salted_watched_legs() {
+    // the local leg is locked with the salted hashlock of the remote one
+    let watched = salted(leg("remote", "remote-swap", 2_000), 5);
+    let pair = SwapPair {
+        watched: watched.clone(),
+        claim: Leg {
+            salt: None,
+            ..salted(leg("local", "local-swap", 1_000), 5)
+        },
+        terms: None,
+    };
+    let (remote, local, mut claimer) = follow(&pair);
+    remote.reveal("remote-swap", SECRET);
+    assert_eq!(claimer.poll().len(), 1);
+    let preimage = watched.salt.unwrap().preimage(SECRET);
+    assert_eq!(local.claims(), vec![(pair.claim, preimage)]);
+
+    // both legs salted, each for its own swap: their hashlocks differ
+    let pair = SwapPair {
+        watched,
+        claim: salted(leg("local", "local-swap", 1_000), 6),
+        terms: None,
+    };
+    let (remote, local, mut claimer) = follow(&pair);
+    remote.reveal("remote-swap", SECRET);
+    assert_eq!(claimer.poll().len(), 1);
+    assert_eq!(local.claims(), vec![(pair.claim, SECRET.to_string())]);
+}
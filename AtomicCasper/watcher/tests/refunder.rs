@@ -0,0 +1,151 @@
+use swap_watcher::{mock::MockChain, Leg, Outcome, Refunder};
+
+const BUFFER: u64 = 100;
+const RETRY_DELAY: u64 = 50;
+
+fn leg(contract: &str, end_time: u64) -> Leg {
+    Leg {
+        chain: "local".to_string(),
+        contract: contract.to_string(),
+        hash_type: "sha3-256".to_string(),
+        hashlock: swap_client::hashlock("sha3-256", contract).unwrap(),
+        end_time,
+        salt: None,
+    }
+}
+
+fn setup() -> (MockChain, Refunder) {
+    let chain = MockChain::new("local");
+    let mut refunder = Refunder::new(Default::default(), BUFFER, RETRY_DELAY)
+        .with_max_attempts(3)
+        .with_adapter(chain.clone());
+    refunder.poll();
+    (chain, refunder)
+}
+
+#[test]
+fn refunds_after_end_time_and_buffer() {
+    let (chain, mut refunder) = setup();
+    chain.initiate(&leg("swap", 1_000));
+    assert!(refunder.poll().is_empty());
+    assert_eq!(refunder.tracked(), vec![&leg("swap", 1_000)]);
+
+    chain.set_block_time(1_000 + BUFFER - 1);
+    assert!(refunder.poll().is_empty());
+    assert!(chain.refunds().is_empty());
+
+    chain.set_block_time(1_000 + BUFFER);
+    let outcomes = refunder.poll();
+    assert_eq!(
+        outcomes,
+        vec![Outcome::Refunded {
+            leg: leg("swap", 1_000),
+            transaction: "local-1".to_string(),
+        }]
+    );
+    assert_eq!(chain.refunds(), vec![leg("swap", 1_000)]);
+    assert!(refunder.tracked().is_empty());
+    assert_eq!(refunder.outcomes(), outcomes.as_slice());
+}
+
+#[test]
+fn retries_failed_refunds() {
+    let (chain, mut refunder) = setup();
+    chain.initiate(&leg("swap", 1_000));
+    chain.set_block_time(1_100);
+    chain.fail_next(1);
+    assert!(refunder.poll().is_empty());
+
+    // waits for the retry delay
+    chain.set_block_time(1_100 + RETRY_DELAY - 1);
+    assert!(refunder.poll().is_empty());
+    assert!(chain.refunds().is_empty());
+
+    chain.set_block_time(1_100 + RETRY_DELAY);
+    assert_eq!(refunder.poll().len(), 1);
+    assert_eq!(chain.refunds().len(), 1);
+}
+
+#[test]
+fn retries_refunds_that_revert() {
+    let (chain, mut refunder) = setup();
+    chain.initiate(&leg("swap", 1_000));
+    chain.set_block_time(1_100);
+    chain.revert_next(1);
+    assert!(refunder.poll().is_empty());
+    assert!(chain.refunds().is_empty());
+    assert_eq!(refunder.tracked(), vec![&leg("swap", 1_000)]);
+
+    chain.set_block_time(1_100 + RETRY_DELAY);
+    assert_eq!(
+        refunder.poll(),
+        vec![Outcome::Refunded {
+            leg: leg("swap", 1_000),
+            transaction: "local-2".to_string(),
+        }]
+    );
+    assert_eq!(chain.refunds().len(), 1);
+}
+
+#[test]
+fn abandons_after_max_attempts() {
+    let (chain, mut refunder) = setup();
+    chain.initiate(&leg("swap", 1_000));
+    chain.fail_next(3);
+    let mut outcomes = Vec::new();
+    for attempt in 0..3 {
+        chain.set_block_time(1_100 + attempt * RETRY_DELAY);
+        outcomes.extend(refunder.poll());
+    }
+    assert!(matches!(
+        outcomes.as_slice(),
+        [Outcome::Abandoned { attempts: 3, .. }]
+    ));
+    assert!(refunder.tracked().is_empty());
+}
+
+#[test]
+fn records_swaps_redeemed_in_time() {
+    let (chain, mut refunder) = setup();
+    chain.initiate(&leg("swap", 1_000));
+    chain.reveal("swap", "swap");
+    chain.set_block_time(2_000);
+    assert_eq!(
+        refunder.poll(),
+        vec![Outcome::Closed {
+            leg: leg("swap", 1_000)
+        }]
+    );
+    assert!(chain.refunds().is_empty());
+}
+
+#[test]
+fn follows_extended_end_time() {
+    let (chain, mut refunder) = setup();
+    chain.initiate(&leg("swap", 1_000));
+    chain.extend("swap", 5_000);
+    chain.set_block_time(2_000);
+    assert!(refunder.poll().is_empty());
+    assert_eq!(refunder.tracked(), vec![&leg("swap", 5_000)]);
+
+    chain.set_block_time(5_000 + BUFFER);
+    assert_eq!(refunder.poll().len(), 1);
+    assert_eq!(chain.refunds(), vec![leg("swap", 5_000)]);
+}
+
+#[test]
+fn tracks_swaps_given_by_hand() {
+    let (chain, mut refunder) = setup();
+    chain.initiate(&leg("swap", 1_000));
+    let mut restarted =
+        Refunder::new(Default::default(), BUFFER, RETRY_DELAY).with_adapter(chain.clone());
+    restarted.track(leg("swap", 1_000)).unwrap();
+    assert!(restarted.track(leg("elsewhere", 1_000)).is_ok());
+    let mut other_chain = leg("swap", 1_000);
+    other_chain.chain = "remote".to_string();
+    assert!(restarted.track(other_chain).is_err());
+
+    chain.set_block_time(1_100);
+    assert_eq!(restarted.poll().len(), 2);
+    assert_eq!(refunder.poll().len(), 1);
+}